@@ -0,0 +1,18 @@
+//! Sets the environment variables that the `bigint_from_env!` tests and examples read.
+
+fn main() {
+    for (name, value) in [
+        (
+            "INFALLIBLE_TOBIGINT_TEST_MODULUS",
+            "57896044618658097711785492504343953926634992332820282019728792003956564819949",
+        ),
+        (
+            "INFALLIBLE_TOBIGINT_TEST_HEX",
+            " -0x7fff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff_ffed ",
+        ),
+        ("INFALLIBLE_TOBIGINT_TEST_BINARY", "0b1_0000_0000"),
+        ("INFALLIBLE_TOBIGINT_TEST_MALFORMED", "12z4"),
+    ] {
+        println!("cargo:rustc-env={name}={value}");
+    }
+}
@@ -0,0 +1,111 @@
+//! The `InfallibleToBigInt` and `InfallibleToBigUint` derives for newtypes and fieldless enums.
+
+use proc_macro::{Delimiter, Group, TokenStream, TokenTree};
+
+use crate::{
+    parse::{Data, Fields, Input},
+    respan, tokens, Error,
+};
+
+/// The trait a derive implements.
+#[derive(Clone, Copy)]
+pub(crate) enum Target {
+    BigInt,
+    BigUint,
+}
+
+impl Target {
+    fn trait_name(self) -> &'static str {
+        match self {
+            Target::BigInt => "InfallibleToBigInt",
+            Target::BigUint => "InfallibleToBigUint",
+        }
+    }
+
+    /// Returns the conversion method, its return type, the fast path method and its primitive.
+    fn methods(self) -> (&'static str, &'static str, &'static str, &'static str) {
+        match self {
+            Target::BigInt => ("to_bigint", "BigInt", "to_i128_fast", "i128"),
+            Target::BigUint => ("to_biguint", "BigUint", "to_u128_fast", "u128"),
+        }
+    }
+}
+
+/// Expands a derive of `target` on a newtype or a fieldless enum.
+pub(crate) fn derive(input: TokenStream, target: Target) -> Result<TokenStream, Error> {
+    let input = Input::parse(input)?;
+    let trait_name = target.trait_name();
+    let trait_path = format!("::infallible_tobigint::{trait_name}");
+    let mut predicates = TokenStream::new();
+    // the expression both methods convert, behind a reference
+    let value = match &input.data {
+        Data::Struct(Fields::Named(fields) | Fields::Unnamed(fields)) if fields.len() == 1 => {
+            let field = &fields[0];
+            let mut predicate: TokenStream = field.ty.iter().cloned().collect();
+            predicate.extend(tokens(&format!(": {trait_path},")));
+            // point a missing impl at the field instead of the derive
+            predicates.extend(respan(predicate, field.span));
+            match &field.name {
+                Some(name) => format!("self.{name}"),
+                None => "self.0".to_owned(),
+            }
+        }
+        Data::Struct(_) => {
+            return Err(Error::new(
+                input.name.span(),
+                format!("{trait_name} can only be derived for structs with exactly one field"),
+            ))
+        }
+        Data::Enum(variants) => {
+            if let Some(variant) = variants.iter().find(|variant| variant.has_fields) {
+                return Err(Error::new(
+                    variant.name.span(),
+                    format!("{trait_name} can only be derived for enums without fields"),
+                ));
+            }
+            let Some(repr) = input.int_repr() else {
+                return Err(Error::new(
+                    input.name.span(),
+                    format!("{trait_name} needs an integer repr on the enum, like `#[repr(u8)]`"),
+                ));
+            };
+            if matches!(target, Target::BigUint) && repr.to_string().starts_with('i') {
+                return Err(Error::new(
+                    repr.span(),
+                    format!("{trait_name} can only be derived for enums with an unsigned repr"),
+                ));
+            }
+            // matching instead of casting `*self` does not need the enum to be `Copy`
+            let arms: String = variants
+                .iter()
+                .map(|variant| format!("Self::{0} => Self::{0} as {repr},", variant.name))
+                .collect();
+            format!("match self {{ {arms} }}")
+        }
+        Data::Union => {
+            return Err(Error::new(
+                input.name.span(),
+                format!("{trait_name} cannot be derived for unions"),
+            ))
+        }
+    };
+    Ok(impl_block(&input, target, &value, predicates))
+}
+
+/// Builds the impl of `target` that converts `value`, behind a reference.
+fn impl_block(input: &Input, target: Target, value: &str, predicates: TokenStream) -> TokenStream {
+    let trait_path = format!("::infallible_tobigint::{}", target.trait_name());
+    let (method, bignum, fast, prim) = target.methods();
+    let body = tokens(&format!(
+        "fn {method}(&self) -> ::infallible_tobigint::__private::{bignum} {{
+            {trait_path}::{method}(&{value})
+        }}
+
+        fn {fast}(&self) -> ::core::option::Option<{prim}> {{
+            {trait_path}::{fast}(&{value})
+        }}"
+    ));
+    let mut output = input.impl_header(&trait_path, predicates);
+    output.extend([TokenTree::Group(Group::new(Delimiter::Brace, body))]);
+    output
+}
@@ -0,0 +1,113 @@
+//! Reading integer constants from environment variables during macro expansion.
+
+use proc_macro::{Delimiter, Group, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::{tokens, Error};
+
+/// Expands `bigint_from_env!("NAME")` to a `bigint!` invocation with the value of the variable.
+pub(crate) fn bigint_from_env(input: TokenStream) -> Result<TokenStream, Error> {
+    let (name, span) = parse_name(input)?;
+    let value = std::env::var(&name).map_err(|error| {
+        let reason = match error {
+            std::env::VarError::NotPresent => "is not set",
+            std::env::VarError::NotUnicode(_) => "is not valid unicode",
+        };
+        Error::new(span, format!("environment variable `{name}` {reason}"))
+    })?;
+    let (negative, digits) = validate(value.trim()).map_err(|reason| {
+        Error::new(
+            span,
+            format!("environment variable `{name}` is not an integer: {reason}"),
+        )
+    })?;
+    let literal: Literal = digits.parse().map_err(|_| {
+        Error::new(
+            span,
+            format!("environment variable `{name}` is not an integer literal"),
+        )
+    })?;
+    let mut argument = TokenStream::new();
+    if negative {
+        // a joint sign is printed without a line break before long literals, which `bigint!` could not scan
+        argument.extend([TokenTree::Punct(Punct::new('-', Spacing::Joint))]);
+    }
+    argument.extend([TokenTree::Literal(literal)]);
+    // `env!` makes the compiler track the variable, so changing it recompiles the caller
+    let mut body = tokens("const _: &str = ::core::env!");
+    body.extend([
+        TokenTree::Group(Group::new(
+            Delimiter::Parenthesis,
+            TokenTree::Literal(Literal::string(&name)).into(),
+        )),
+        TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+    ]);
+    body.extend(tokens("::infallible_tobigint::bigint!"));
+    body.extend([TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        argument,
+    ))]);
+    Ok(TokenTree::Group(Group::new(Delimiter::Brace, body)).into())
+}
+
+/// Returns the variable name from the single string literal the macro is called with.
+fn parse_name(input: TokenStream) -> Result<(String, Span), Error> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let literal = match tokens.as_slice() {
+        [TokenTree::Literal(literal)] => literal,
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::None => {
+            return parse_name(group.stream())
+        }
+        _ => {
+            return Err(Error::new(
+                Span::call_site(),
+                "expected the name of an environment variable as a string literal",
+            ))
+        }
+    };
+    let source = literal.to_string();
+    match source
+        .strip_prefix('"')
+        .and_then(|name| name.strip_suffix('"'))
+    {
+        Some(name) if !name.is_empty() && !name.contains(['\\', '"']) => {
+            Ok((name.to_string(), literal.span()))
+        }
+        _ => Err(Error::new(
+            literal.span(),
+            "expected the name of an environment variable as a plain string literal",
+        )),
+    }
+}
+
+/// Checks a value against the literal rules of `bigint!` and returns its sign and its unsigned digits.
+///
+/// The value may start with `-`, use a `0x`, `0o` or `0b` prefix and contain `_` separators.
+fn validate(value: &str) -> Result<(bool, &str), &'static str> {
+    let (negative, unsigned) = match value.strip_prefix('-') {
+        Some(unsigned) => (true, unsigned),
+        None => (false, value),
+    };
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x") => (16, &unsigned[2..]),
+        Some("0o") => (8, &unsigned[2..]),
+        Some("0b") => (2, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+    if let Some(invalid) = digits.chars().find(|&ch| ch != '_' && !ch.is_digit(radix)) {
+        return Err(match invalid {
+            '-' | '+' => "the sign has to be a single leading `-`",
+            _ if invalid.is_ascii_alphanumeric() => {
+                "it contains a digit that is invalid for its radix"
+            }
+            _ => "it contains a character that is not a digit",
+        });
+    }
+    if !digits.chars().any(|ch| ch != '_') {
+        return Err("it has no digits");
+    }
+    // a leading `_` after a prefix is fine, but on its own it would be an identifier
+    if radix == 10 && digits.starts_with('_') {
+        return Err("it starts with `_`");
+    }
+    Ok((negative, unsigned))
+}
@@ -0,0 +1,301 @@
+//! Procedural macros for [`infallible_tobigint`](https://docs.rs/infallible_tobigint).
+//!
+//! Use them through the `derive` feature of `infallible_tobigint` rather than depending on this crate directly. The
+//! macros only use the compiler's `proc_macro` API, so they do not pull in any parsing dependencies.
+
+use proc_macro::{Delimiter, Group, Spacing, Span, TokenStream, TokenTree};
+
+mod convert;
+mod env;
+mod parse;
+
+use parse::{Data, Fields, Input};
+
+/// Derives `to_bigint_fields`, which converts every field of a struct to a `BigInt` and pairs it with its name.
+///
+/// The generated inherent method has the signature `fn to_bigint_fields(&self) -> Vec<(&'static str, BigInt)>` and
+/// lists the fields in declaration order. Tuple struct fields are named by their index. Every field has to implement
+/// `InfallibleToBigInt`, unless it is marked with `#[to_bigint(skip)]`.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint_derive::ToBigIntFields;
+/// use num::BigInt;
+///
+/// #[derive(ToBigIntFields)]
+/// struct Totals {
+///     sent: u64,
+///     dropped: u32,
+///     #[to_bigint(skip)]
+///     label: String,
+/// }
+///
+/// let totals = Totals { sent: u64::MAX, dropped: 3, label: "eu-1".into() };
+/// assert_eq!(totals.to_bigint_fields(), [("sent", BigInt::from(u64::MAX)), ("dropped", BigInt::from(3))]);
+/// ```
+///
+/// Fields that cannot be converted have to be skipped:
+/// ```compile_fail
+/// use infallible_tobigint_derive::ToBigIntFields;
+///
+/// #[derive(ToBigIntFields)]
+/// struct Totals {
+///     sent: u64,
+///     label: String,
+/// }
+/// ```
+#[proc_macro_derive(ToBigIntFields, attributes(to_bigint))]
+pub fn derive_to_bigint_fields(input: TokenStream) -> TokenStream {
+    match to_bigint_fields(input) {
+        Ok(output) => output,
+        Err(error) => error.into_compile_error(),
+    }
+}
+
+/// Derives `InfallibleToBigInt` for newtypes and fieldless enums.
+///
+/// A struct with exactly one field, named or not, converts by delegating to the field, which has to implement
+/// `InfallibleToBigInt`. A fieldless enum needs an integer repr like `#[repr(u8)]` and converts its discriminant. The
+/// generated code only delegates to the conversions of the field or the repr type, so it cannot panic either.
+///
+/// Types with both conversions derive both traits, like `#[derive(InfallibleToBigInt, InfallibleToBigUint)]`.
+///
+/// # Example
+/// ```
+/// // with the derive feature, this also imports the derive
+/// use infallible_tobigint::InfallibleToBigInt;
+/// use num::BigInt;
+///
+/// #[derive(infallible_tobigint_derive::InfallibleToBigInt)]
+/// struct Offset(i64);
+///
+/// #[derive(infallible_tobigint_derive::InfallibleToBigInt)]
+/// #[repr(i8)]
+/// enum Direction {
+///     Down = -1,
+///     Up = 1,
+/// }
+///
+/// assert_eq!(Offset(i64::MIN).to_bigint(), BigInt::from(i64::MIN));
+/// assert_eq!(Direction::Down.to_bigint(), BigInt::from(-1));
+/// assert_eq!(Direction::Up.to_i128_fast(), Some(1));
+/// ```
+///
+/// Structs with more than one field do not compile:
+/// ```compile_fail
+/// #[derive(infallible_tobigint_derive::InfallibleToBigInt)]
+/// struct Range(i64, i64);
+/// ```
+///
+/// Neither do enums with fields:
+/// ```compile_fail
+/// #[derive(infallible_tobigint_derive::InfallibleToBigInt)]
+/// #[repr(u8)]
+/// enum Value {
+///     Small(u8),
+///     Large(u64),
+/// }
+/// ```
+///
+/// Or enums without an integer repr:
+/// ```compile_fail
+/// #[derive(infallible_tobigint_derive::InfallibleToBigInt)]
+/// enum Direction {
+///     Down,
+///     Up,
+/// }
+/// ```
+#[proc_macro_derive(InfallibleToBigInt)]
+pub fn derive_infallible_to_bigint(input: TokenStream) -> TokenStream {
+    match convert::derive(input, convert::Target::BigInt) {
+        Ok(output) => output,
+        Err(error) => error.into_compile_error(),
+    }
+}
+
+/// Derives `InfallibleToBigUint` for newtypes and fieldless enums with an unsigned repr.
+///
+/// This accepts the same shapes as [`InfallibleToBigInt`](derive@InfallibleToBigInt), except that the repr of an enum
+/// has to be unsigned. It only implements `InfallibleToBigUint`, so types with both conversions derive
+/// [`InfallibleToBigInt`](derive@InfallibleToBigInt) too.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::{InfallibleToBigInt, InfallibleToBigUint};
+/// use num::{BigInt, BigUint};
+///
+/// #[derive(infallible_tobigint_derive::InfallibleToBigUint)]
+/// struct BlockHeight {
+///     height: u64,
+/// }
+///
+/// #[derive(
+///     infallible_tobigint_derive::InfallibleToBigInt,
+///     infallible_tobigint_derive::InfallibleToBigUint,
+/// )]
+/// #[repr(u8)]
+/// enum Opcode {
+///     Push = 0x60,
+///     Pop = 0x50,
+/// }
+///
+/// assert_eq!(BlockHeight { height: u64::MAX }.to_biguint(), BigUint::from(u64::MAX));
+/// assert_eq!(Opcode::Push.to_u128_fast(), Some(0x60));
+/// assert_eq!(Opcode::Pop.to_bigint(), BigInt::from(0x50));
+/// ```
+///
+/// Signed reprs do not compile:
+/// ```compile_fail
+/// #[derive(infallible_tobigint_derive::InfallibleToBigUint)]
+/// #[repr(i8)]
+/// enum Direction {
+///     Down = -1,
+///     Up = 1,
+/// }
+/// ```
+#[proc_macro_derive(InfallibleToBigUint)]
+pub fn derive_infallible_to_biguint(input: TokenStream) -> TokenStream {
+    match convert::derive(input, convert::Target::BigUint) {
+        Ok(output) => output,
+        Err(error) => error.into_compile_error(),
+    }
+}
+
+/// Creates a `BigInt` from an integer in an environment variable that is read at compile time.
+///
+/// The value follows the rules of `bigint!`: it may be negative, use a `0x`, `0o` or `0b` prefix and contain `_`
+/// separators, and surrounding whitespace is ignored. The expansion builds the value from a digit array like
+/// `bigint!`, and the compiler tracks the variable, so changing it recompiles the caller. A missing or malformed
+/// variable is a compile error that names it.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint_derive::bigint_from_env;
+/// use num::BigInt;
+///
+/// // set by the build script of this crate
+/// let modulus = bigint_from_env!("INFALLIBLE_TOBIGINT_TEST_MODULUS");
+/// assert_eq!(modulus, (BigInt::from(1) << 255_u32) - 19);
+/// ```
+///
+/// Unset variables do not compile:
+/// ```compile_fail
+/// let value = infallible_tobigint_derive::bigint_from_env!("INFALLIBLE_TOBIGINT_TEST_UNSET");
+/// ```
+///
+/// Neither do values that are not integers:
+/// ```compile_fail
+/// let value = infallible_tobigint_derive::bigint_from_env!("INFALLIBLE_TOBIGINT_TEST_MALFORMED");
+/// ```
+#[proc_macro]
+pub fn bigint_from_env(input: TokenStream) -> TokenStream {
+    match env::bigint_from_env(input) {
+        Ok(output) => output,
+        Err(error) => error.into_compile_error(),
+    }
+}
+
+fn to_bigint_fields(input: TokenStream) -> Result<TokenStream, Error> {
+    let input = Input::parse(input)?;
+    let fields = match &input.data {
+        Data::Struct(fields) => fields,
+        Data::Enum(_) | Data::Union => {
+            return Err(Error::new(
+                input.name.span(),
+                "ToBigIntFields can only be derived for structs",
+            ))
+        }
+    };
+    let mut entries = TokenStream::new();
+    let fields = match fields {
+        Fields::Named(fields) | Fields::Unnamed(fields) => fields.as_slice(),
+        Fields::Unit => &[],
+    };
+    for (index, field) in fields.iter().enumerate() {
+        if field.skip()? {
+            continue;
+        }
+        let (name, member) = match &field.name {
+            Some(name) => (name.to_string(), name.to_string()),
+            None => (index.to_string(), index.to_string()),
+        };
+        let entry = tokens(&format!(
+            "({name:?}, ::infallible_tobigint::InfallibleToBigInt::to_bigint(&self.{member})),"
+        ));
+        // point conversion errors at the offending field instead of the derive
+        entries.extend(respan(entry, field.span));
+    }
+    let mut list = tokens("::infallible_tobigint::__private::Vec::from");
+    list.extend([TokenTree::Group(Group::new(
+        Delimiter::Parenthesis,
+        TokenTree::Group(Group::new(Delimiter::Bracket, entries)).into(),
+    ))]);
+    let mut body = tokens(
+        "/// Returns every convertible field with its name, in declaration order.
+        pub fn to_bigint_fields(&self) -> ::infallible_tobigint::__private::Vec<(&'static str, \
+            ::infallible_tobigint::__private::BigInt)>",
+    );
+    body.extend([TokenTree::Group(Group::new(Delimiter::Brace, list))]);
+    let mut output = input.impl_header("", TokenStream::new());
+    output.extend([TokenTree::Group(Group::new(Delimiter::Brace, body))]);
+    Ok(output)
+}
+
+/// A compile error at a span.
+struct Error {
+    span: Span,
+    message: String,
+}
+
+impl Error {
+    fn new(span: Span, message: impl Into<String>) -> Self {
+        Error {
+            span,
+            message: message.into(),
+        }
+    }
+
+    fn into_compile_error(self) -> TokenStream {
+        respan(
+            tokens(&format!("::core::compile_error! {{ {:?} }}", self.message)),
+            self.span,
+        )
+    }
+}
+
+/// Parses generated source code, which is always valid.
+fn tokens(source: &str) -> TokenStream {
+    source.parse().unwrap_or_default()
+}
+
+/// Moves every token of a stream to the given span.
+fn respan(stream: TokenStream, span: Span) -> TokenStream {
+    stream
+        .into_iter()
+        .map(|mut token| {
+            if let TokenTree::Group(group) = &token {
+                let mut respanned = Group::new(group.delimiter(), respan(group.stream(), span));
+                respanned.set_span(span);
+                token = TokenTree::Group(respanned);
+            } else {
+                token.set_span(span);
+            }
+            token
+        })
+        .collect()
+}
+
+/// Returns whether a token is the given punctuation character.
+fn is_punct(token: &TokenTree, ch: char) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == ch)
+}
+
+/// Returns whether a token is the given identifier.
+fn is_ident(token: &TokenTree, name: &str) -> bool {
+    matches!(token, TokenTree::Ident(ident) if ident.to_string() == name)
+}
+
+/// Returns whether a token is a `-` that forms an arrow with the following `>`.
+fn is_arrow_start(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == '-' && punct.spacing() == Spacing::Joint)
+}
@@ -0,0 +1,380 @@
+//! A small parser for the struct and enum definitions the derives are applied to.
+
+use proc_macro::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+
+use crate::{is_arrow_start, is_ident, is_punct, tokens, Error};
+
+/// A parsed type definition.
+pub(crate) struct Input {
+    /// The contents of the outer attributes, without the `#[]`.
+    attrs: Vec<TokenStream>,
+    pub(crate) name: Ident,
+    /// The tokens between the angle brackets after the name.
+    generics: Vec<TokenTree>,
+    /// The tokens after `where`, up to the body.
+    where_clause: Vec<TokenTree>,
+    pub(crate) data: Data,
+}
+
+/// The body of a type definition.
+pub(crate) enum Data {
+    Struct(Fields),
+    Enum(Vec<Variant>),
+    Union,
+}
+
+/// A single variant of an enum.
+pub(crate) struct Variant {
+    pub(crate) name: Ident,
+    /// Whether the variant has tuple or named fields.
+    pub(crate) has_fields: bool,
+}
+
+/// The fields of a struct.
+pub(crate) enum Fields {
+    Named(Vec<Field>),
+    Unnamed(Vec<Field>),
+    Unit,
+}
+
+/// A single field of a struct.
+pub(crate) struct Field {
+    /// The contents of the outer attributes, without the `#[]`.
+    attrs: Vec<TokenStream>,
+    pub(crate) name: Option<Ident>,
+    /// The tokens of the field type.
+    pub(crate) ty: Vec<TokenTree>,
+    pub(crate) span: Span,
+}
+
+impl Field {
+    /// Returns whether the field is marked with `#[to_bigint(skip)]`.
+    pub(crate) fn skip(&self) -> Result<bool, Error> {
+        let mut skip = false;
+        for attr in &self.attrs {
+            let attr: Vec<TokenTree> = attr.clone().into_iter().collect();
+            let [name, TokenTree::Group(options)] = attr.as_slice() else {
+                continue;
+            };
+            if !is_ident(name, "to_bigint") || options.delimiter() != Delimiter::Parenthesis {
+                continue;
+            }
+            for option in options.stream() {
+                match &option {
+                    TokenTree::Ident(ident) if ident.to_string() == "skip" => skip = true,
+                    TokenTree::Punct(punct) if punct.as_char() == ',' => {}
+                    _ => {
+                        return Err(Error::new(
+                            option.span(),
+                            format!("unknown to_bigint option `{option}`, expected `skip`"),
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(skip)
+    }
+}
+
+impl Input {
+    pub(crate) fn parse(input: TokenStream) -> Result<Self, Error> {
+        let tokens: Vec<TokenTree> = input.into_iter().collect();
+        let mut pos = 0;
+        let attrs = parse_attrs(&tokens, &mut pos);
+        skip_visibility(&tokens, &mut pos);
+        let keyword = match tokens.get(pos) {
+            Some(TokenTree::Ident(keyword)) => keyword.to_string(),
+            _ => return Err(Error::new(Span::call_site(), "expected a type definition")),
+        };
+        let name = match tokens.get(pos + 1) {
+            Some(TokenTree::Ident(name)) => name.clone(),
+            _ => return Err(Error::new(Span::call_site(), "expected a type name")),
+        };
+        pos += 2;
+        let generics = if tokens.get(pos).is_some_and(|token| is_punct(token, '<')) {
+            parse_generics(&tokens, &mut pos)?
+        } else {
+            Vec::new()
+        };
+        let mut where_clause = Vec::new();
+        let data = match keyword.as_str() {
+            "struct" => {
+                let fields = match tokens.get(pos) {
+                    Some(TokenTree::Group(group))
+                        if group.delimiter() == Delimiter::Parenthesis =>
+                    {
+                        pos += 1;
+                        Fields::Unnamed(parse_fields(group, false)?)
+                    }
+                    _ => Fields::Unit,
+                };
+                where_clause = parse_where(&tokens, &mut pos);
+                match (fields, tokens.get(pos)) {
+                    (Fields::Unit, Some(TokenTree::Group(group)))
+                        if group.delimiter() == Delimiter::Brace =>
+                    {
+                        Data::Struct(Fields::Named(parse_fields(group, true)?))
+                    }
+                    (fields, _) => Data::Struct(fields),
+                }
+            }
+            "enum" => {
+                where_clause = parse_where(&tokens, &mut pos);
+                match tokens.get(pos) {
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                        Data::Enum(parse_variants(group)?)
+                    }
+                    _ => return Err(Error::new(name.span(), "expected the variants of the enum")),
+                }
+            }
+            "union" => Data::Union,
+            _ => return Err(Error::new(name.span(), "expected a struct, enum or union")),
+        };
+        Ok(Input {
+            attrs,
+            name,
+            generics,
+            where_clause,
+            data,
+        })
+    }
+
+    /// Returns the integer type named by a `#[repr(...)]` attribute, like `u8` in `#[repr(C, u8)]`.
+    pub(crate) fn int_repr(&self) -> Option<Ident> {
+        const INTS: [&str; 12] = [
+            "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+        ];
+        self.attrs.iter().find_map(|attr| {
+            let attr: Vec<TokenTree> = attr.clone().into_iter().collect();
+            let [name, TokenTree::Group(options)] = attr.as_slice() else {
+                return None;
+            };
+            if !is_ident(name, "repr") || options.delimiter() != Delimiter::Parenthesis {
+                return None;
+            }
+            options
+                .stream()
+                .into_iter()
+                .find_map(|option| match option {
+                    TokenTree::Ident(ident) if INTS.contains(&ident.to_string().as_str()) => {
+                        Some(ident)
+                    }
+                    _ => None,
+                })
+        })
+    }
+
+    /// Builds `impl<...> Trait for Name<...> where ...`, or an inherent impl header if `trait_path` is empty.
+    ///
+    /// The `predicates` are added to the where clause of the type.
+    pub(crate) fn impl_header(&self, trait_path: &str, predicates: TokenStream) -> TokenStream {
+        let mut params = TokenStream::new();
+        let mut args = TokenStream::new();
+        for param in split_top_level(&self.generics, ',') {
+            if param.is_empty() {
+                continue;
+            }
+            let without_default = split_top_level(&param, '=').swap_remove(0);
+            params.extend(without_default);
+            params.extend([comma()]);
+            let arg = match param.as_slice() {
+                [quote, lifetime, ..] if is_punct(quote, '\'') => {
+                    vec![quote.clone(), lifetime.clone()]
+                }
+                [keyword, name, ..] if is_ident(keyword, "const") => vec![name.clone()],
+                [name, ..] => vec![name.clone()],
+                [] => Vec::new(),
+            };
+            args.extend(arg);
+            args.extend([comma()]);
+        }
+        let mut header = tokens("impl");
+        header.extend(angle_bracketed(params));
+        if !trait_path.is_empty() {
+            header.extend(tokens(trait_path));
+            header.extend(tokens("for"));
+        }
+        header.extend([TokenTree::Ident(self.name.clone())]);
+        header.extend(angle_bracketed(args));
+        if !self.where_clause.is_empty() || !predicates.is_empty() {
+            header.extend(tokens("where"));
+            header.extend(self.where_clause.iter().cloned());
+            if !self
+                .where_clause
+                .last()
+                .is_none_or(|token| is_punct(token, ','))
+            {
+                header.extend([comma()]);
+            }
+            header.extend(predicates);
+        }
+        header
+    }
+}
+
+fn comma() -> TokenTree {
+    TokenTree::Punct(Punct::new(',', Spacing::Alone))
+}
+
+fn angle_bracketed(inner: TokenStream) -> TokenStream {
+    let mut stream = TokenStream::from(TokenTree::Punct(Punct::new('<', Spacing::Alone)));
+    stream.extend(inner);
+    stream.extend([TokenTree::Punct(Punct::new('>', Spacing::Alone))]);
+    stream
+}
+
+/// Collects the contents of the outer attributes at `pos`.
+fn parse_attrs(tokens: &[TokenTree], pos: &mut usize) -> Vec<TokenStream> {
+    let mut attrs = Vec::new();
+    while let (Some(hash), Some(TokenTree::Group(group))) = (tokens.get(*pos), tokens.get(*pos + 1))
+    {
+        if !is_punct(hash, '#') || group.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        attrs.push(group.stream());
+        *pos += 2;
+    }
+    attrs
+}
+
+/// Skips `pub`, `pub(crate)` and similar at `pos`.
+fn skip_visibility(tokens: &[TokenTree], pos: &mut usize) {
+    if tokens.get(*pos).is_some_and(|token| is_ident(token, "pub")) {
+        *pos += 1;
+        if let Some(TokenTree::Group(group)) = tokens.get(*pos) {
+            if group.delimiter() == Delimiter::Parenthesis {
+                *pos += 1;
+            }
+        }
+    }
+}
+
+/// Returns the tokens between the `<` at `pos` and its matching `>`, moving `pos` past them.
+fn parse_generics(tokens: &[TokenTree], pos: &mut usize) -> Result<Vec<TokenTree>, Error> {
+    let start = *pos + 1;
+    let mut depth = 0_usize;
+    while let Some(token) = tokens.get(*pos) {
+        if is_punct(token, '<') {
+            depth += 1;
+        } else if is_punct(token, '>') && !(*pos > 0 && is_arrow_start(&tokens[*pos - 1])) {
+            depth -= 1;
+            if depth == 0 {
+                *pos += 1;
+                return Ok(tokens[start..*pos - 1].to_vec());
+            }
+        }
+        *pos += 1;
+    }
+    Err(Error::new(
+        Span::call_site(),
+        "unclosed generic parameter list",
+    ))
+}
+
+/// Returns the predicates of a `where` clause at `pos`, moving `pos` to the body or the closing `;`.
+fn parse_where(tokens: &[TokenTree], pos: &mut usize) -> Vec<TokenTree> {
+    if !tokens
+        .get(*pos)
+        .is_some_and(|token| is_ident(token, "where"))
+    {
+        return Vec::new();
+    }
+    *pos += 1;
+    let start = *pos;
+    while let Some(token) = tokens.get(*pos) {
+        match token {
+            TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => break,
+            token if is_punct(token, ';') => break,
+            _ => *pos += 1,
+        }
+    }
+    tokens[start..*pos].to_vec()
+}
+
+/// Parses the fields inside the braces or parentheses of a struct.
+fn parse_fields(group: &Group, named: bool) -> Result<Vec<Field>, Error> {
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    let mut fields = Vec::new();
+    for field in split_top_level(&tokens, ',') {
+        if field.is_empty() {
+            continue;
+        }
+        let mut pos = 0;
+        let attrs = parse_attrs(&field, &mut pos);
+        skip_visibility(&field, &mut pos);
+        let name = if named {
+            match field.get(pos) {
+                Some(TokenTree::Ident(name)) => Some(name.clone()),
+                _ => return Err(Error::new(group.span(), "expected a field name")),
+            }
+        } else {
+            None
+        };
+        let span = field.get(pos).map_or_else(|| group.span(), TokenTree::span);
+        // the type follows the name and its `:`
+        let ty = field[(pos + if named { 2 } else { 0 }).min(field.len())..].to_vec();
+        fields.push(Field {
+            attrs,
+            name,
+            ty,
+            span,
+        });
+    }
+    Ok(fields)
+}
+
+/// Parses the variants inside the braces of an enum.
+fn parse_variants(group: &Group) -> Result<Vec<Variant>, Error> {
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    let mut variants = Vec::new();
+    // discriminants can contain `<` and `>` as operators, and fields are inside groups, so only top level commas
+    // separate variants
+    for variant in tokens.split(|token| is_punct(token, ',')) {
+        if variant.is_empty() {
+            continue;
+        }
+        let mut pos = 0;
+        parse_attrs(variant, &mut pos);
+        let name = match variant.get(pos) {
+            Some(TokenTree::Ident(name)) => name.clone(),
+            _ => return Err(Error::new(group.span(), "expected a variant name")),
+        };
+        let has_fields = matches!(
+            variant.get(pos + 1),
+            Some(TokenTree::Group(group)) if group.delimiter() != Delimiter::None
+        );
+        variants.push(Variant { name, has_fields });
+    }
+    Ok(variants)
+}
+
+/// Splits tokens at every `separator` outside of angle brackets.
+fn split_top_level(tokens: &[TokenTree], separator: char) -> Vec<Vec<TokenTree>> {
+    let mut parts = vec![Vec::new()];
+    let mut depth = 0_usize;
+    for (i, token) in tokens.iter().enumerate() {
+        if is_punct(token, '<') {
+            depth += 1;
+        } else if is_punct(token, '>') && !(i > 0 && is_arrow_start(&tokens[i - 1])) {
+            depth = depth.saturating_sub(1);
+        } else if depth == 0
+            && is_punct(token, separator)
+            && !(separator == '=' && is_compound(tokens, i))
+        {
+            parts.push(Vec::new());
+            continue;
+        }
+        if let Some(part) = parts.last_mut() {
+            part.push(token.clone());
+        }
+    }
+    parts
+}
+
+/// Returns whether the punctuation at `i` is part of a multi character operator like `==` or `=>`.
+fn is_compound(tokens: &[TokenTree], i: usize) -> bool {
+    let joint_before = i > 0
+        && matches!(&tokens[i - 1], TokenTree::Punct(punct) if punct.spacing() == Spacing::Joint);
+    let joint = matches!(&tokens[i], TokenTree::Punct(punct) if punct.spacing() == Spacing::Joint);
+    joint_before || joint
+}
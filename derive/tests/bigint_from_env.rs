@@ -0,0 +1,25 @@
+use infallible_tobigint_derive::bigint_from_env;
+use num::BigInt;
+
+/// Test a decimal value larger than any primitive
+#[test]
+fn test_decimal() {
+    let modulus = bigint_from_env!("INFALLIBLE_TOBIGINT_TEST_MODULUS");
+    assert_eq!(modulus, (BigInt::from(1) << 255_u32) - 19);
+}
+
+/// Test a negative hex value with separators and surrounding whitespace
+#[test]
+fn test_hex() {
+    let value = bigint_from_env!("INFALLIBLE_TOBIGINT_TEST_HEX");
+    assert_eq!(value, BigInt::from(19) - (BigInt::from(1) << 255_u32));
+}
+
+/// Test a binary value
+#[test]
+fn test_binary() {
+    assert_eq!(
+        bigint_from_env!("INFALLIBLE_TOBIGINT_TEST_BINARY"),
+        BigInt::from(256)
+    );
+}
@@ -0,0 +1,116 @@
+// the derive feature of infallible_tobigint re-exports the derives next to the traits, so the traits are imported
+// anonymously to work with and without it
+use infallible_tobigint::{InfallibleToBigInt as _, InfallibleToBigUint as _};
+use infallible_tobigint_derive::{InfallibleToBigInt, InfallibleToBigUint};
+use std::num::{Saturating, Wrapping};
+
+use num::{BigInt, BigUint};
+
+#[derive(InfallibleToBigInt)]
+struct Offset(pub i128);
+
+#[derive(InfallibleToBigInt, InfallibleToBigUint)]
+struct BlockHeight {
+    height: u64,
+}
+
+#[derive(InfallibleToBigInt)]
+struct Balance(BigInt);
+
+#[derive(InfallibleToBigInt)]
+struct Wrapped<T>(T)
+where
+    T: Copy;
+
+#[derive(InfallibleToBigUint)]
+struct Nested(BlockHeight);
+
+#[derive(InfallibleToBigInt, InfallibleToBigUint)]
+struct Height(u64);
+
+#[derive(InfallibleToBigInt, InfallibleToBigUint)]
+#[repr(u8)]
+enum Opcode {
+    Push = 0x60,
+    Pop = 0x50,
+    /// The discriminant follows the previous one
+    Dup,
+    Shift = 1 << 7 | 1,
+}
+
+#[derive(InfallibleToBigInt)]
+#[repr(i128)]
+enum Extreme {
+    Min = i128::MIN,
+    Max = i128::MAX,
+}
+
+#[derive(InfallibleToBigInt, InfallibleToBigUint)]
+#[repr(u128)]
+enum Wide {
+    Max = u128::MAX,
+}
+
+/// Test newtypes with tuple and named fields
+#[test]
+fn test_newtype() {
+    assert_eq!(Offset(i128::MIN).to_bigint(), BigInt::from(i128::MIN));
+    assert_eq!(Offset(-3).to_i128_fast(), Some(-3));
+    let height = BlockHeight { height: u64::MAX };
+    assert_eq!(height.to_biguint(), BigUint::from(u64::MAX));
+    assert_eq!(height.to_u128_fast(), Some(u64::MAX.into()));
+    assert_eq!(height.to_bigint(), BigInt::from(u64::MAX));
+}
+
+/// Test a newtype that derives both traits
+#[test]
+fn test_both() {
+    let height = Height(u64::MAX);
+    assert_eq!(height.to_bigint(), BigInt::from(u64::MAX));
+    assert_eq!(height.to_i128_fast(), Some(u64::MAX.into()));
+    assert_eq!(height.to_biguint(), BigUint::from(u64::MAX));
+    assert_eq!(height.to_u128_fast(), Some(u64::MAX.into()));
+    // only the unsigned trait is derived here
+    assert_eq!(Nested(BlockHeight { height: 3 }).to_u128_fast(), Some(3));
+}
+
+/// Test newtypes around bignums, generics and other derived types
+#[test]
+fn test_delegation() {
+    let wide = BigInt::from(u128::MAX) << 64_u32;
+    assert_eq!(Balance(wide.clone()).to_bigint(), wide);
+    assert_eq!(Balance(wide).to_i128_fast(), None);
+    assert_eq!(Wrapped(-7_i8).to_bigint(), BigInt::from(-7));
+    assert_eq!(Wrapped(7_u16).to_i128_fast(), Some(7));
+    let nested = Nested(BlockHeight { height: 12 });
+    assert_eq!(nested.to_biguint(), BigUint::from(12_u8));
+}
+
+/// Test derived types inside the arithmetic wrappers
+#[test]
+fn test_wrapped() {
+    assert_eq!(Wrapping(Offset(-3)).to_bigint(), BigInt::from(-3));
+    assert_eq!(
+        Saturating(Offset(i128::MIN)).to_i128_fast(),
+        Some(i128::MIN)
+    );
+    let height = Wrapping(BlockHeight { height: u64::MAX });
+    assert_eq!(height.to_biguint(), BigUint::from(u64::MAX));
+    assert_eq!(height.to_bigint(), BigInt::from(u64::MAX));
+    assert_eq!(Saturating(Opcode::Push).to_bigint(), BigInt::from(0x60));
+}
+
+/// Test the discriminants of fieldless enums
+#[test]
+fn test_enum() {
+    assert_eq!(Opcode::Push.to_biguint(), BigUint::from(0x60_u8));
+    assert_eq!(Opcode::Pop.to_u128_fast(), Some(0x50));
+    assert_eq!(Opcode::Dup.to_bigint(), BigInt::from(0x51));
+    assert_eq!(Opcode::Shift.to_u128_fast(), Some(0x81));
+    assert_eq!(Extreme::Min.to_bigint(), BigInt::from(i128::MIN));
+    assert_eq!(Extreme::Max.to_i128_fast(), Some(i128::MAX));
+    assert_eq!(Wide::Max.to_biguint(), BigUint::from(u128::MAX));
+    // beyond the i128 fast path
+    assert_eq!(Wide::Max.to_i128_fast(), None);
+    assert_eq!(Wide::Max.to_bigint(), BigInt::from(u128::MAX));
+}
@@ -0,0 +1,79 @@
+use infallible_tobigint::InfallibleToBigInt;
+use infallible_tobigint_derive::ToBigIntFields;
+use num::BigInt;
+
+#[derive(ToBigIntFields)]
+struct Totals {
+    sent: u64,
+    #[to_bigint(skip)]
+    #[allow(dead_code)]
+    label: String,
+    dropped: u32,
+    pub bytes: u128,
+}
+
+#[derive(ToBigIntFields)]
+struct Signed(i8, #[to_bigint(skip)] (), pub i128);
+
+#[derive(ToBigIntFields)]
+struct Generic<'a, T: InfallibleToBigInt, const N: usize, U = u8>
+where
+    U: InfallibleToBigInt,
+{
+    scale: T,
+    offset: U,
+    #[to_bigint(skip)]
+    #[allow(dead_code)]
+    name: &'a str,
+}
+
+#[derive(ToBigIntFields)]
+struct Empty;
+
+/// Test a struct with a skipped String field
+#[test]
+fn test_mixed_struct() {
+    let totals = Totals {
+        sent: u64::MAX,
+        label: "edge".to_string(),
+        dropped: 7,
+        bytes: u128::MAX,
+    };
+    assert_eq!(
+        totals.to_bigint_fields(),
+        [
+            ("sent", BigInt::from(u64::MAX)),
+            ("dropped", BigInt::from(7)),
+            ("bytes", BigInt::from(u128::MAX)),
+        ]
+    );
+}
+
+/// Test that tuple struct fields are named by their index, in order
+#[test]
+fn test_tuple_struct() {
+    assert_eq!(
+        Signed(-1, (), i128::MIN).to_bigint_fields(),
+        [("0", BigInt::from(-1)), ("2", BigInt::from(i128::MIN))]
+    );
+}
+
+/// Test generic parameters with bounds, defaults and a where clause
+#[test]
+fn test_generics() {
+    let value: Generic<'_, i64, 3> = Generic {
+        scale: -5_i64,
+        offset: 200_u8,
+        name: "scaled",
+    };
+    assert_eq!(
+        value.to_bigint_fields(),
+        [("scale", BigInt::from(-5)), ("offset", BigInt::from(200))]
+    );
+}
+
+/// Test a struct without fields
+#[test]
+fn test_unit_struct() {
+    assert!(Empty.to_bigint_fields().is_empty());
+}
@@ -0,0 +1,332 @@
+use core::{cmp::Ordering, fmt};
+
+#[cfg(feature = "bigint")]
+use num::BigInt;
+#[cfg(feature = "biguint")]
+use num::BigUint;
+
+#[cfg(feature = "bigint")]
+use crate::cmp::cmp_bigint;
+#[cfg(feature = "biguint")]
+use crate::cmp::cmp_biguint;
+#[cfg(feature = "bigint")]
+use crate::{InfallibleIntoBigInt, InfallibleToBigInt};
+#[cfg(feature = "biguint")]
+use crate::{InfallibleIntoBigUint, InfallibleToBigUint};
+
+/// Wrapper that lets a value stand in for a [`num::BigInt`] without converting it up front.
+///
+/// `AsBigInt<T>` converts into a `BigInt` with `From`, so it can be passed to APIs that take `impl Into<BigInt>`. It
+/// compares equal and ordered against a `BigInt` on its right, compares equal against a `BigInt` on its left, and
+/// displays like the `BigInt` it stands for. Values with an `i128` fast path, like the primitives, are compared and
+/// displayed without allocating.
+///
+/// Because `BigInt` has a second `PartialEq` impl, a comparison like `big == value.into()` no longer infers its right
+/// side and needs a type, as in `big == BigInt::from(value)`.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::as_big::AsBigInt;
+/// use num::BigInt;
+///
+/// let limit = BigInt::from(1) << 70_u32;
+/// assert!(AsBigInt(u64::MAX) < limit);
+/// assert_eq!(AsBigInt(-3_i8), BigInt::from(-3));
+/// assert_eq!(BigInt::from(-3), AsBigInt(-3_i8));
+/// assert_eq!(format!("{:>4}", AsBigInt(-3_i8)), "  -3");
+///
+/// fn total(values: impl IntoIterator<Item = impl Into<BigInt>>) -> BigInt {
+///     values.into_iter().map(Into::into).sum()
+/// }
+/// assert_eq!(total([AsBigInt(u64::MAX), AsBigInt(1)]), BigInt::from(1) << 64_u32);
+/// ```
+#[cfg(feature = "bigint")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AsBigInt<T>(pub T);
+
+#[cfg(feature = "bigint")]
+impl<T: InfallibleToBigInt> InfallibleToBigInt for AsBigInt<T> {
+    fn to_bigint(&self) -> BigInt {
+        self.0.to_bigint()
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        self.0.to_i128_fast()
+    }
+
+    fn into_bigint_by_value(self) -> BigInt {
+        self.0.into_bigint()
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<T: InfallibleToBigInt> From<AsBigInt<T>> for BigInt {
+    fn from(value: AsBigInt<T>) -> BigInt {
+        value.0.into_bigint()
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<T: InfallibleToBigInt> PartialEq<BigInt> for AsBigInt<T> {
+    fn eq(&self, other: &BigInt) -> bool {
+        cmp_bigint(&self.0, other) == Ordering::Equal
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<T: InfallibleToBigInt> PartialEq<AsBigInt<T>> for BigInt {
+    fn eq(&self, other: &AsBigInt<T>) -> bool {
+        cmp_bigint(&other.0, self) == Ordering::Equal
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<T: InfallibleToBigInt> PartialOrd<BigInt> for AsBigInt<T> {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(cmp_bigint(&self.0, other))
+    }
+}
+
+#[cfg(feature = "bigint")]
+impl<T: InfallibleToBigInt> fmt::Display for AsBigInt<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.to_i128_fast() {
+            Some(value) => fmt::Display::fmt(&value, f),
+            None => fmt::Display::fmt(&self.0.to_bigint(), f),
+        }
+    }
+}
+
+/// Wrapper that lets a value stand in for a [`num::BigUint`] without converting it up front.
+///
+/// This is the unsigned twin of [`AsBigInt`], with the same conversions and formatting against `BigUint`. It only
+/// compares against a `BigUint` on its right, so that `biguint == value.into()` keeps inferring its right side.
+/// Values with a `u128` fast path are compared and displayed without allocating.
+///
+/// With the `bigint` feature the wrapper also implements [`InfallibleToBigInt`], so it can adapt a type that only
+/// implements [`InfallibleToBigUint`] to code that takes `impl InfallibleToBigInt`. Unsigned types don't get the
+/// signed conversion on their own; each one that wants it implements both traits.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::as_big::AsBigUint;
+/// use num::BigUint;
+///
+/// assert!(AsBigUint(u128::MAX) < BigUint::from(u128::MAX) + 1_u8);
+/// assert_eq!(BigUint::from(AsBigUint(9_u8)), BigUint::from(9_u8));
+/// assert_eq!(AsBigUint(9_u8).to_string(), "9");
+/// ```
+#[cfg(feature = "biguint")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct AsBigUint<T>(pub T);
+
+#[cfg(feature = "biguint")]
+impl<T: InfallibleToBigUint> InfallibleToBigUint for AsBigUint<T> {
+    fn to_biguint(&self) -> BigUint {
+        self.0.to_biguint()
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        self.0.to_u128_fast()
+    }
+
+    fn into_biguint_by_value(self) -> BigUint {
+        self.0.into_biguint()
+    }
+}
+
+/// Converts any unsigned value to a signed [`num::BigInt`], so types that only implement [`InfallibleToBigUint`]
+/// can be passed where [`InfallibleToBigInt`] is required.
+#[cfg(all(feature = "bigint", feature = "biguint"))]
+impl<T: InfallibleToBigUint> InfallibleToBigInt for AsBigUint<T> {
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from(self.0.to_biguint())
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        self.0
+            .to_u128_fast()
+            .and_then(|value| i128::try_from(value).ok())
+    }
+
+    fn into_bigint_by_value(self) -> BigInt {
+        BigInt::from(self.0.into_biguint())
+    }
+}
+
+#[cfg(feature = "biguint")]
+impl<T: InfallibleToBigUint> From<AsBigUint<T>> for BigUint {
+    fn from(value: AsBigUint<T>) -> BigUint {
+        value.0.into_biguint()
+    }
+}
+
+#[cfg(feature = "biguint")]
+impl<T: InfallibleToBigUint> PartialEq<BigUint> for AsBigUint<T> {
+    fn eq(&self, other: &BigUint) -> bool {
+        cmp_biguint(&self.0, other) == Ordering::Equal
+    }
+}
+
+#[cfg(feature = "biguint")]
+impl<T: InfallibleToBigUint> PartialOrd<BigUint> for AsBigUint<T> {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(cmp_biguint(&self.0, other))
+    }
+}
+
+#[cfg(feature = "biguint")]
+impl<T: InfallibleToBigUint> fmt::Display for AsBigUint<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.to_u128_fast() {
+            Some(value) => fmt::Display::fmt(&value, f),
+            None => fmt::Display::fmt(&self.0.to_biguint(), f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "bigint")]
+    use num::BigInt;
+    #[cfg(feature = "biguint")]
+    use num::BigUint;
+
+    #[cfg(feature = "bigint")]
+    use super::AsBigInt;
+    #[cfg(feature = "biguint")]
+    use super::AsBigUint;
+    #[cfg(feature = "bigint")]
+    use crate::InfallibleToBigInt;
+
+    /// Test i64::MIN against the equal BigInt and its neighbours
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_compare_min() {
+        let min = BigInt::from(i64::MIN);
+        let wrapped = AsBigInt(i64::MIN);
+        assert_eq!(wrapped, min);
+        assert!(wrapped > &min - 1);
+        assert!(wrapped < &min + 1);
+        assert_ne!(wrapped, &min + 1);
+        assert_ne!(wrapped, &min - 1);
+    }
+
+    /// Test equality with the BigInt on the left, against the equal BigInt and its neighbours
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_compare_reversed() {
+        let min = BigInt::from(i64::MIN);
+        assert_eq!(min, AsBigInt(i64::MIN));
+        assert_ne!(&min + 1, AsBigInt(i64::MIN));
+        assert_ne!(&min - 1, AsBigInt(i64::MIN));
+        let huge = BigInt::from(u128::MAX) << 8_u32;
+        assert_eq!(huge, AsBigInt(huge.clone()));
+        assert_ne!(huge, AsBigInt(u128::MAX));
+        assert!(BigInt::from(7) == AsBigInt(7_u8));
+    }
+
+    /// Test comparisons against BigInts beyond the i128 range
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_compare_wide() {
+        let huge = BigInt::from(i128::MAX) + 1;
+        let tiny = BigInt::from(i128::MIN) - 1;
+        for value in [AsBigInt(i128::MIN), AsBigInt(0), AsBigInt(i128::MAX)] {
+            assert!(value < huge);
+            assert!(value > tiny);
+            assert_ne!(value, huge);
+        }
+        let big = AsBigInt(huge.clone());
+        assert_eq!(big, huge);
+        assert!(big > BigInt::from(i128::MAX));
+    }
+
+    /// Test passing the wrapper to a function that takes Into<BigInt>
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_into_bigint() {
+        fn double(value: impl Into<BigInt>) -> BigInt {
+            value.into() * 2
+        }
+        assert_eq!(double(AsBigInt(u128::MAX)), BigInt::from(u128::MAX) * 2);
+        assert_eq!(double(AsBigInt(i8::MIN)), BigInt::from(-256));
+        assert_eq!(AsBigInt(-5_i16).to_bigint(), BigInt::from(-5));
+        assert_eq!(AsBigInt(-5_i16).to_i128_fast(), Some(-5));
+    }
+
+    /// Test that the wrappers display like the bignums they stand for
+    #[test]
+    fn test_display() {
+        #[cfg(feature = "bigint")]
+        {
+            assert_eq!(
+                AsBigInt(i128::MIN).to_string(),
+                BigInt::from(i128::MIN).to_string()
+            );
+            let wide = -(BigInt::from(u128::MAX) << 8_u32);
+            assert_eq!(AsBigInt(wide.clone()).to_string(), wide.to_string());
+            assert_eq!(format!("{:+06}", AsBigInt(42_u8)), "+00042");
+        }
+        #[cfg(feature = "biguint")]
+        {
+            assert_eq!(AsBigUint(u128::MAX).to_string(), u128::MAX.to_string());
+            assert_eq!(format!("{:<4}|", AsBigUint(7_u8)), "7   |");
+        }
+    }
+
+    /// Test the unsigned twin against the equal BigUint and its neighbours
+    #[cfg(feature = "biguint")]
+    #[test]
+    fn test_unsigned() {
+        let max = BigUint::from(u128::MAX);
+        let wrapped = AsBigUint(u128::MAX);
+        assert_eq!(wrapped, max);
+        assert!(wrapped < &max + 1_u8);
+        assert!(wrapped > &max - 1_u8);
+        assert!(AsBigUint(0_u8) < BigUint::from(1_u8));
+        assert_eq!(BigUint::from(AsBigUint(300_u16)), BigUint::from(300_u16));
+        assert_eq!(AsBigUint(max.clone()), max);
+    }
+
+    /// Test the signed conversion of the unsigned twin, on both sides of the i128 fast path
+    #[cfg(all(feature = "bigint", feature = "biguint"))]
+    #[test]
+    fn test_unsigned_to_bigint() {
+        use crate::InfallibleIntoBigInt;
+
+        assert_eq!(AsBigUint(u128::MAX).to_bigint(), BigInt::from(u128::MAX));
+        assert_eq!(AsBigUint(u128::MAX).to_i128_fast(), None);
+        assert_eq!(AsBigUint(i128::MAX as u128).to_i128_fast(), Some(i128::MAX));
+        let wide = BigUint::from(u128::MAX) << 8_u32;
+        assert_eq!(AsBigUint(&wide).to_bigint(), BigInt::from(wide.clone()));
+        assert_eq!(AsBigUint(wide.clone()).into_bigint(), BigInt::from(wide));
+    }
+
+    /// Test the AsBigUint adapter on a downstream type that only implements InfallibleToBigUint
+    #[cfg(all(feature = "bigint", feature = "biguint"))]
+    #[test]
+    fn test_unsigned_only_adapter() {
+        use crate::{InfallibleIntoBigInt, InfallibleToBigUint};
+
+        /// A big-endian byte string, as a downstream crate might define it.
+        struct Bytes(Vec<u8>);
+
+        impl InfallibleToBigUint for Bytes {
+            fn to_biguint(&self) -> BigUint {
+                BigUint::from_bytes_be(&self.0)
+            }
+        }
+
+        fn convert(value: impl InfallibleToBigInt) -> BigInt {
+            value.to_bigint()
+        }
+        let bytes = Bytes(vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(convert(AsBigUint(&bytes)), BigInt::from(u128::MAX) + 1);
+        assert_eq!(AsBigUint(&bytes).to_i128_fast(), None);
+        assert_eq!(convert(AsBigUint(Bytes(Vec::new()))), BigInt::from(0));
+        assert_eq!(AsBigUint(Bytes(vec![7])).into_bigint(), BigInt::from(7));
+    }
+}
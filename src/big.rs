@@ -0,0 +1,321 @@
+#[cfg(feature = "iter")]
+use core::iter::{Product, Sum};
+use core::ops::{Add, Div, Mul, Rem, Sub};
+
+use num::BigInt;
+
+#[cfg(feature = "iter")]
+use crate::iter::{SumAccumulator, ToBigIntIterExt};
+use crate::InfallibleToBigInt;
+
+/// Wrapper that lets primitives take part in [`num::BigInt`] arithmetic without explicit conversions.
+///
+/// `Big<T>` implements `+`, `-`, `*`, `/` and `%` against `BigInt`, `&BigInt` and other `Big<U>` values, in both
+/// operand orders. Every operation converts the wrapped values and produces a `BigInt`, so nothing can overflow.
+///
+/// Division and remainder follow `BigInt`'s truncating semantics: the quotient is rounded towards zero and the
+/// remainder has the sign of the dividend. Dividing by zero panics, just like it does for `BigInt`.
+///
+/// A `BigInt` on the left also accepts `&Big<T>`, and applies primitives that fit into an `i128` without converting
+/// them first. Through `BigInt`'s [`Sum`](core::iter::Sum) and [`Product`](core::iter::Product) impls this lets
+/// iterators of `Big<T>` and `&Big<T>` be summed and multiplied into a `BigInt` directly.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::big::Big;
+/// use num::BigInt;
+///
+/// let count = u64::MAX;
+/// let price = 3_u8;
+/// let fee = BigInt::from(-7);
+///
+/// let total = Big(count) * Big(price) + &fee;
+/// assert_eq!(total, BigInt::from(u64::MAX) * 3 - 7);
+///
+/// let sum: BigInt = [u64::MAX; 4].into_iter().map(Big).sum();
+/// assert_eq!(sum, BigInt::from(u64::MAX) * 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Big<T>(pub T);
+
+macro_rules! impl_big_op {
+    ($($trait:ident::$method:ident),*) => {$(
+        impl<T: InfallibleToBigInt, U: InfallibleToBigInt> $trait<Big<U>> for Big<T> {
+            type Output = BigInt;
+
+            fn $method(self, rhs: Big<U>) -> BigInt {
+                $trait::$method(self.0.to_bigint(), rhs.0.to_bigint())
+            }
+        }
+
+        impl<T: InfallibleToBigInt> $trait<BigInt> for Big<T> {
+            type Output = BigInt;
+
+            fn $method(self, rhs: BigInt) -> BigInt {
+                $trait::$method(self.0.to_bigint(), rhs)
+            }
+        }
+
+        impl<T: InfallibleToBigInt> $trait<&BigInt> for Big<T> {
+            type Output = BigInt;
+
+            fn $method(self, rhs: &BigInt) -> BigInt {
+                $trait::$method(self.0.to_bigint(), rhs)
+            }
+        }
+
+        impl<T: InfallibleToBigInt> $trait<Big<T>> for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: Big<T>) -> BigInt {
+                $trait::$method(self, &rhs)
+            }
+        }
+
+        impl<T: InfallibleToBigInt> $trait<&Big<T>> for BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: &Big<T>) -> BigInt {
+                match rhs.0.to_i128_fast() {
+                    Some(rhs) => $trait::$method(self, rhs),
+                    None => $trait::$method(self, rhs.0.to_bigint()),
+                }
+            }
+        }
+
+        impl<T: InfallibleToBigInt> $trait<Big<T>> for &BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: Big<T>) -> BigInt {
+                $trait::$method(self, &rhs)
+            }
+        }
+
+        impl<T: InfallibleToBigInt> $trait<&Big<T>> for &BigInt {
+            type Output = BigInt;
+
+            fn $method(self, rhs: &Big<T>) -> BigInt {
+                match rhs.0.to_i128_fast() {
+                    Some(rhs) => $trait::$method(self, rhs),
+                    None => $trait::$method(self, rhs.0.to_bigint()),
+                }
+            }
+        }
+    )*};
+}
+
+impl_big_op!(Add::add, Sub::sub, Mul::mul, Div::div, Rem::rem);
+
+/// Wrapper that sums and multiplies primitives into a [`num::BigInt`] through [`Sum`] and [`Product`].
+///
+/// Unlike [`Big<T>`], which goes through `BigInt` addition one value at a time, `Prim<T>` sums in an `i128` and only
+/// spills into a `BigInt` when the running total overflows, just like [`sum_as_bigint`](crate::iter::sum_as_bigint).
+/// Products are taken in an `i128` the same way. Iterators of `&Prim<T>` are summed and multiplied too.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::big::Prim;
+/// use num::BigInt;
+///
+/// let sum: BigInt = [i128::MAX; 3].into_iter().map(Prim).sum();
+/// assert_eq!(sum, BigInt::from(i128::MAX) * 3);
+///
+/// let factorial: BigInt = (1..=40_u8).map(Prim).product();
+/// assert_eq!(factorial, (1..=40).map(BigInt::from).product::<BigInt>());
+/// ```
+#[cfg(feature = "iter")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Prim<T>(pub T);
+
+#[cfg(feature = "iter")]
+impl<T: InfallibleToBigInt> Sum<Prim<T>> for BigInt {
+    fn sum<I: Iterator<Item = Prim<T>>>(iter: I) -> BigInt {
+        let mut sum = SumAccumulator::new();
+        for value in iter {
+            sum.add(&value.0);
+        }
+        sum.finish()
+    }
+}
+
+#[cfg(feature = "iter")]
+impl<'a, T: InfallibleToBigInt> Sum<&'a Prim<T>> for BigInt {
+    fn sum<I: Iterator<Item = &'a Prim<T>>>(iter: I) -> BigInt {
+        let mut sum = SumAccumulator::new();
+        for value in iter {
+            sum.add(&value.0);
+        }
+        sum.finish()
+    }
+}
+
+#[cfg(feature = "iter")]
+impl<T: InfallibleToBigInt> Product<Prim<T>> for BigInt {
+    fn product<I: Iterator<Item = Prim<T>>>(iter: I) -> BigInt {
+        iter.map(|value| value.0).product_bigint()
+    }
+}
+
+#[cfg(feature = "iter")]
+impl<'a, T: InfallibleToBigInt> Product<&'a Prim<T>> for BigInt {
+    fn product<I: Iterator<Item = &'a Prim<T>>>(iter: I) -> BigInt {
+        iter.map(|value| &value.0).product_bigint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, One, Zero};
+
+    use super::Big;
+    #[cfg(feature = "iter")]
+    use super::Prim;
+    #[cfg(feature = "iter")]
+    use crate::iter::sum_as_bigint;
+
+    /// Test expressions mixing different primitive types
+    #[test]
+    fn test_mixed_types() {
+        assert_eq!(Big(u64::MAX) + Big(1_u8), BigInt::from(u64::MAX) + 1);
+        assert_eq!(Big(-3_i8) * Big(u128::MAX), BigInt::from(u128::MAX) * -3);
+        assert_eq!(Big(10_u16) - Big(20_i32), BigInt::from(-10));
+        assert_eq!(Big(7_i64) / Big(2_usize), BigInt::from(3));
+        assert_eq!(Big(7_i64) % Big(2_isize), BigInt::from(1));
+        assert_eq!(Big(1_u8) - BigInt::from(5), BigInt::from(-4));
+        assert_eq!(BigInt::from(5) - Big(1_u8), BigInt::from(4));
+    }
+
+    /// Test operations with references to BigInts
+    #[test]
+    fn test_references() {
+        let fee = BigInt::from(100);
+        assert_eq!(Big(2_u32) * Big(3_u32) + &fee, BigInt::from(106));
+        assert_eq!(Big(1_u32) - &fee, BigInt::from(-99));
+        assert_eq!(&fee / Big(3_u8), BigInt::from(33));
+        assert_eq!(&fee % Big(3_u8), BigInt::from(1));
+        assert_eq!(fee, BigInt::from(100));
+    }
+
+    /// Test truncating division with negative operands
+    #[test]
+    fn test_truncating_division() {
+        assert_eq!(Big(-7) / Big(2), BigInt::from(-3));
+        assert_eq!(Big(-7) % Big(2), BigInt::from(-1));
+        assert_eq!(Big(7) / Big(-2), BigInt::from(-3));
+        assert_eq!(Big(7) % Big(-2), BigInt::from(1));
+    }
+
+    /// Test i128::MIN, which overflows most primitive operations
+    #[test]
+    fn test_i128_min() {
+        let min = BigInt::from(i128::MIN);
+        assert_eq!(Big(i128::MIN) * Big(-1), -min.clone());
+        assert_eq!(Big(i128::MIN) / Big(-1), -min.clone());
+        assert_eq!(Big(i128::MIN) - Big(1), min.clone() - 1);
+        assert_eq!(Big(i128::MIN) + Big(i128::MIN), min.clone() * 2);
+        assert_eq!(Big(i128::MIN) % Big(-1), BigInt::from(0));
+    }
+
+    /// Test summing and multiplying ranges of wrapped values, by value and by reference
+    #[test]
+    fn test_sum_product() {
+        let sum: BigInt = (1..=100_u32).map(Big).sum();
+        assert_eq!(sum, BigInt::from(5050));
+        let factorial: BigInt = (1..=30_u64).map(Big).product();
+        assert_eq!(factorial, (1..=30).map(BigInt::from).product::<BigInt>());
+        let wrapped: Vec<Big<i8>> = (-5..=5).map(Big).collect();
+        assert_eq!(wrapped.iter().sum::<BigInt>(), BigInt::zero());
+        assert_eq!(wrapped[..5].iter().product::<BigInt>(), BigInt::from(-120));
+    }
+
+    /// Test empty iterators
+    #[test]
+    fn test_sum_product_empty() {
+        assert_eq!(
+            core::iter::empty::<Big<u8>>().sum::<BigInt>(),
+            BigInt::zero()
+        );
+        assert_eq!(
+            core::iter::empty::<&Big<u8>>().product::<BigInt>(),
+            BigInt::one()
+        );
+    }
+
+    /// Test values beyond i128, for sums and products and against sum_as_bigint
+    #[cfg(feature = "iter")]
+    #[test]
+    fn test_sum_product_beyond_i128() {
+        let values = [u128::MAX, u128::MAX, 1, u128::MAX];
+        let sum: BigInt = values.iter().copied().map(Big).sum();
+        assert_eq!(sum, sum_as_bigint(values));
+        assert_eq!(sum, BigInt::from(u128::MAX) * 3 + 1);
+        let values = [i128::MIN, i128::MAX, -1, i128::MIN];
+        let wrapped = values.map(Big);
+        assert_eq!(wrapped.iter().sum::<BigInt>(), sum_as_bigint(values));
+        let product: BigInt = wrapped.iter().product();
+        assert_eq!(product, -(BigInt::from(i128::MIN).pow(2) * i128::MAX));
+        let product: BigInt = [Big(u128::MAX), Big(u128::MAX)].into_iter().product();
+        assert_eq!(product, BigInt::from(u128::MAX).pow(2));
+    }
+
+    /// Test summing and multiplying ranges wrapped in Prim, by value and by reference
+    #[cfg(feature = "iter")]
+    #[test]
+    fn test_prim_sum_product() {
+        let sum: BigInt = (1..=100_u32).map(Prim).sum();
+        assert_eq!(sum, BigInt::from(5050));
+        let factorial: BigInt = (1..=40_u64).map(Prim).product();
+        assert_eq!(factorial, (1..=40).map(BigInt::from).product::<BigInt>());
+        let wrapped: Vec<Prim<i8>> = (-5..=5).map(Prim).collect();
+        assert_eq!(wrapped.iter().sum::<BigInt>(), BigInt::zero());
+        assert_eq!(wrapped[..5].iter().product::<BigInt>(), BigInt::from(-120));
+        assert_eq!(wrapped.iter().product::<BigInt>(), BigInt::zero());
+    }
+
+    /// Test that empty iterators of Prim sum to 0 and multiply to 1
+    #[cfg(feature = "iter")]
+    #[test]
+    fn test_prim_empty() {
+        assert_eq!(
+            core::iter::empty::<Prim<u8>>().sum::<BigInt>(),
+            BigInt::zero()
+        );
+        assert_eq!(
+            core::iter::empty::<&Prim<i64>>().sum::<BigInt>(),
+            BigInt::zero()
+        );
+        assert_eq!(
+            core::iter::empty::<Prim<u8>>().product::<BigInt>(),
+            BigInt::one()
+        );
+        assert_eq!(
+            core::iter::empty::<&Prim<i64>>().product::<BigInt>(),
+            BigInt::one()
+        );
+    }
+
+    /// Test many i128::MAX values, whose running total spills out of the i128 fast path, against sum_as_bigint
+    #[cfg(feature = "iter")]
+    #[test]
+    fn test_prim_spill() {
+        let values = [i128::MAX; 1000];
+        let sum: BigInt = values.iter().copied().map(Prim).sum();
+        assert_eq!(sum, BigInt::from(i128::MAX) * 1000);
+        assert_eq!(sum, sum_as_bigint(values));
+        let wrapped = [i128::MIN, i128::MAX, -1, i128::MIN, i128::MIN].map(Prim);
+        assert_eq!(
+            wrapped.iter().sum::<BigInt>(),
+            sum_as_bigint(wrapped.map(|value| value.0))
+        );
+        let wide = [u128::MAX, u128::MAX, 1, u128::MAX];
+        let sum: BigInt = wide.into_iter().map(Prim).sum();
+        assert_eq!(sum, sum_as_bigint(wide));
+        let product: BigInt = [Prim(i128::MAX), Prim(i128::MIN), Prim(-1)]
+            .iter()
+            .product();
+        assert_eq!(
+            product,
+            BigInt::from(i128::MAX) * BigInt::from(i128::MIN) * -1
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use core::cmp::Ordering;
+
+#[cfg(feature = "biguint")]
+use num::BigUint;
+#[cfg(feature = "bigint")]
+use num::{bigint::Sign, BigInt};
+
+#[cfg(feature = "bigint")]
+use crate::InfallibleToBigInt;
+#[cfg(feature = "biguint")]
+use crate::InfallibleToBigUint;
+
+/// Comparison of a primitive against a [`num::BigInt`] without converting the primitive.
+///
+/// The comparison reads the sign and the low digits of the `BigInt` and never allocates, while
+/// `value.to_bigint() == *other` allocates a `BigInt` just to compare it.
+///
+/// # Example
+/// ```
+/// use std::cmp::Ordering;
+///
+/// use infallible_tobigint::cmp::CmpBigInt;
+/// use num::BigInt;
+///
+/// let total = BigInt::from(u64::MAX) * 3;
+/// assert_eq!(u64::MAX.cmp_bigint(&total), Ordering::Less);
+/// assert!(5_u8.cmp_bigint(&BigInt::from(-5)).is_gt());
+/// assert!(i64::MIN.eq_bigint(&BigInt::from(i64::MIN)));
+/// ```
+#[cfg(feature = "bigint")]
+pub trait CmpBigInt {
+    fn cmp_bigint(&self, other: &BigInt) -> Ordering;
+
+    fn eq_bigint(&self, other: &BigInt) -> bool {
+        self.cmp_bigint(other) == Ordering::Equal
+    }
+}
+
+/// Comparison of an unsigned primitive against a [`num::BigUint`] without converting the primitive.
+///
+/// See [`CmpBigInt`] for details.
+#[cfg(feature = "biguint")]
+pub trait CmpBigUint {
+    fn cmp_biguint(&self, other: &BigUint) -> Ordering;
+
+    fn eq_biguint(&self, other: &BigUint) -> bool {
+        self.cmp_biguint(other) == Ordering::Equal
+    }
+}
+
+/// Compares an `i128` against a [`num::BigInt`] without allocating.
+#[cfg(feature = "bigint")]
+fn cmp_i128(value: i128, other: &BigInt) -> Ordering {
+    match i128::try_from(other) {
+        Ok(other) => value.cmp(&other),
+        // other is beyond the i128 range, so its sign decides
+        Err(_) if other.sign() == Sign::Minus => Ordering::Greater,
+        Err(_) => Ordering::Less,
+    }
+}
+
+/// Compares a `u128` against a [`num::BigUint`] without allocating.
+#[cfg(feature = "biguint")]
+fn cmp_u128(value: u128, other: &BigUint) -> Ordering {
+    match u128::try_from(other) {
+        Ok(other) => value.cmp(&other),
+        // other is beyond the u128 range
+        Err(_) => Ordering::Less,
+    }
+}
+
+/// Compares a value against a [`num::BigInt`], without converting it if it has an `i128` fast path.
+#[cfg(feature = "bigint")]
+pub(crate) fn cmp_bigint<T: InfallibleToBigInt + ?Sized>(value: &T, other: &BigInt) -> Ordering {
+    match value.to_i128_fast() {
+        Some(value) => cmp_i128(value, other),
+        None => value.to_bigint().cmp(other),
+    }
+}
+
+/// Compares a value against a [`num::BigUint`], without converting it if it has a `u128` fast path.
+#[cfg(feature = "biguint")]
+pub(crate) fn cmp_biguint<T: InfallibleToBigUint + ?Sized>(value: &T, other: &BigUint) -> Ordering {
+    match value.to_u128_fast() {
+        Some(value) => cmp_u128(value, other),
+        None => value.to_biguint().cmp(other),
+    }
+}
+
+#[cfg(feature = "bigint")]
+macro_rules! impl_cmp_bigint_signed {
+    ($($prim:ty),*) => {$(
+        impl CmpBigInt for $prim {
+            fn cmp_bigint(&self, other: &BigInt) -> Ordering {
+                cmp_i128(*self as i128, other)
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "bigint")]
+impl_cmp_bigint_signed!(i8, i16, i32, i64, i128, isize);
+
+#[cfg(feature = "bigint")]
+macro_rules! impl_cmp_bigint_unsigned {
+    ($($prim:ty),*) => {$(
+        impl CmpBigInt for $prim {
+            fn cmp_bigint(&self, other: &BigInt) -> Ordering {
+                if other.sign() == Sign::Minus {
+                    return Ordering::Greater;
+                }
+                match u128::try_from(other.magnitude()) {
+                    Ok(other) => (*self as u128).cmp(&other),
+                    Err(_) => Ordering::Less,
+                }
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "bigint")]
+impl_cmp_bigint_unsigned!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(feature = "biguint")]
+macro_rules! impl_cmp_biguint {
+    ($($prim:ty),*) => {$(
+        impl CmpBigUint for $prim {
+            fn cmp_biguint(&self, other: &BigUint) -> Ordering {
+                cmp_u128(*self as u128, other)
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "biguint")]
+impl_cmp_biguint!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "bigint")]
+    use core::cmp::Ordering;
+
+    #[cfg(feature = "biguint")]
+    use num::BigUint;
+    #[cfg(feature = "bigint")]
+    use num::{bigint::Sign, BigInt};
+
+    #[cfg(feature = "bigint")]
+    use super::CmpBigInt;
+    #[cfg(feature = "biguint")]
+    use super::CmpBigUint;
+
+    /// Pseudo random values spread over the whole i128 range, plus the boundaries of every type
+    #[cfg(feature = "bigint")]
+    fn spread() -> Vec<i128> {
+        let mut values = vec![0, 1, -1, i128::MIN, i128::MAX];
+        for bits in [8, 16, 32, 64] {
+            let max = (1_i128 << (bits - 1)) - 1;
+            values.extend([max, max + 1, -max - 1, -max - 2, 2 * max + 1, 2 * max + 2]);
+        }
+        let mut state = 0x853c_49e6_748f_ea9b_u64;
+        for _ in 0..500 {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            let wide =
+                (i128::from(state) << 64 | i128::from(state.rotate_left(17))) >> (state % 127);
+            values.push(wide);
+        }
+        values
+    }
+
+    /// Test every type against allocating comparisons on a spread of values, including wide BigInts
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_against_allocating() {
+        fn check<T: CmpBigInt + Into<BigInt> + Copy + TryFrom<i128>>(others: &[BigInt]) {
+            for value in spread()
+                .into_iter()
+                .filter_map(|value| T::try_from(value).ok())
+            {
+                let converted: BigInt = value.into();
+                for other in others {
+                    assert_eq!(value.cmp_bigint(other), converted.cmp(other));
+                    assert_eq!(value.eq_bigint(other), converted == *other);
+                }
+            }
+        }
+        let mut others: Vec<BigInt> = spread().into_iter().map(BigInt::from).collect();
+        others.push(BigInt::from(u128::MAX));
+        others.push(BigInt::from(u128::MAX) << 64_u32);
+        others.push(-(BigInt::from(u128::MAX) << 64_u32));
+        others.push(BigInt::from(i128::MIN) - 1);
+        check::<i8>(&others);
+        check::<i16>(&others);
+        check::<i32>(&others);
+        check::<i64>(&others);
+        check::<i128>(&others);
+        check::<isize>(&others);
+        check::<u8>(&others);
+        check::<u16>(&others);
+        check::<u32>(&others);
+        check::<u64>(&others);
+        check::<u128>(&others);
+        check::<usize>(&others);
+        assert_eq!(
+            u128::MAX.cmp_bigint(&BigInt::from(u128::MAX)),
+            Ordering::Equal
+        );
+    }
+
+    /// Test the corner cases of signs, boundaries and wide values
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_corners() {
+        assert!(i64::MIN.eq_bigint(&BigInt::from(i64::MIN)));
+        assert!(!i64::MIN.eq_bigint(&(BigInt::from(i64::MIN) - 1)));
+        let zero = BigInt::from(0);
+        assert_eq!(zero.sign(), Sign::NoSign);
+        assert!(0_u8.eq_bigint(&zero));
+        assert!(0_i128.eq_bigint(&zero));
+        assert!(0_u32.cmp_bigint(&BigInt::from(-1)).is_gt());
+        assert!(u8::MAX.cmp_bigint(&-(BigInt::from(1) << 500_u32)).is_gt());
+        // a BigInt with a wide intermediate that ends up equal to a u64
+        let narrowed = BigInt::from(u64::MAX) << 192_u32 >> 192_u32;
+        assert!(u64::MAX.eq_bigint(&narrowed));
+        let wide = (BigInt::from(1) << 128_u32) + u64::MAX;
+        assert!(u64::MAX.cmp_bigint(&wide).is_lt());
+        assert!(!u64::MAX.eq_bigint(&wide));
+    }
+
+    /// Test the unsigned comparisons against allocating ones
+    #[cfg(feature = "biguint")]
+    #[test]
+    fn test_biguint() {
+        let mut others: Vec<BigUint> = [0, 1, 255, 256, u64::MAX as u128, u128::MAX]
+            .into_iter()
+            .map(BigUint::from)
+            .collect();
+        others.push(BigUint::from(u128::MAX) + 1_u8);
+        others.push(BigUint::from(u64::MAX) << 256_u32);
+        for value in [0, 1, 255, 256, u64::MAX as u128, u128::MAX - 1, u128::MAX] {
+            for other in &others {
+                assert_eq!(value.cmp_biguint(other), BigUint::from(value).cmp(other));
+                assert_eq!(value.eq_biguint(other), BigUint::from(value) == *other);
+                if let Ok(value) = u8::try_from(value) {
+                    assert_eq!(value.cmp_biguint(other), BigUint::from(value).cmp(other));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use num::{BigInt, BigUint, Zero};
+
+/// The largest exponent for which `10^n` still fits into a `u128`.
+pub(crate) const MAX_TEN_POW_U128: u32 = 38;
+
+/// Powers of ten `10^0..=10^38`, all of which fit into a `u128`.
+pub(crate) const TEN_POW_U128: [u128; MAX_TEN_POW_U128 as usize + 1] = {
+    let mut table = [1_u128; MAX_TEN_POW_U128 as usize + 1];
+    let mut i = 1;
+    while i < table.len() {
+        table[i] = table[i - 1] * 10;
+        i += 1;
+    }
+    table
+};
+
+/// Constructs `2^n` as a [`num::BigUint`].
+///
+/// The value is built by setting a single bit, so no multiplication is involved and exactly the needed digits are
+/// allocated.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::consts::two_pow;
+///
+/// assert_eq!(two_pow(10), 1024_u32.into());
+/// ```
+pub fn two_pow(n: u64) -> BigUint {
+    let mut value = BigUint::zero();
+    value.set_bit(n, true);
+    value
+}
+
+/// Constructs `2^n` as a [`num::BigInt`].
+///
+/// See [`two_pow`] for details.
+pub fn two_pow_bigint(n: u64) -> BigInt {
+    BigInt::from(two_pow(n))
+}
+
+/// Constructs `10^n` as a [`num::BigUint`].
+///
+/// Exponents up to 38 are served from a table of `u128` values. Larger exponents are computed by binary
+/// exponentiation of `10^38`, multiplied by the remaining power from the table.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::consts::ten_pow;
+///
+/// assert_eq!(ten_pow(3), 1000_u32.into());
+/// ```
+pub fn ten_pow(n: u32) -> BigUint {
+    if n <= MAX_TEN_POW_U128 {
+        return BigUint::from(TEN_POW_U128[n as usize]);
+    }
+    let chunk = BigUint::from(TEN_POW_U128[MAX_TEN_POW_U128 as usize]);
+    let rest = BigUint::from(TEN_POW_U128[(n % MAX_TEN_POW_U128) as usize]);
+    chunk.pow(n / MAX_TEN_POW_U128) * rest
+}
+
+/// Constructs `10^n` as a [`num::BigInt`].
+///
+/// See [`ten_pow`] for details.
+pub fn ten_pow_bigint(n: u32) -> BigInt {
+    BigInt::from(ten_pow(n))
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{pow::Pow, BigInt, BigUint, One};
+
+    use super::{ten_pow, ten_pow_bigint, two_pow, two_pow_bigint};
+
+    /// Test the exponent zero
+    #[test]
+    fn test_zero_exponent() {
+        assert_eq!(two_pow(0), BigUint::one());
+        assert_eq!(ten_pow(0), BigUint::one());
+        assert_eq!(two_pow_bigint(0), BigInt::one());
+        assert_eq!(ten_pow_bigint(0), BigInt::one());
+    }
+
+    /// Test the digit boundaries at 2^63 and 2^64
+    #[test]
+    fn test_two_pow_digit_boundaries() {
+        assert_eq!(two_pow(63), BigUint::from(1_u64 << 63));
+        assert_eq!(two_pow(64), BigUint::from(u64::MAX) + 1_u8);
+        assert_eq!(two_pow(64).bits(), 65);
+    }
+
+    /// Test the bit length of a huge power of two
+    #[test]
+    fn test_two_pow_huge() {
+        let value = two_pow(1_000_000);
+        assert_eq!(value.bits(), 1_000_001);
+        assert_eq!(value.count_ones(), 1);
+    }
+
+    /// Test 10^38 against the u128 value
+    #[test]
+    fn test_ten_pow_u128_limit() {
+        assert_eq!(ten_pow(38), BigUint::from(10_u128.pow(38)));
+    }
+
+    /// Test agreement with pow on BigUint
+    #[test]
+    fn test_agreement_with_pow() {
+        for n in 0..200_u32 {
+            assert_eq!(two_pow(n.into()), Pow::pow(BigUint::from(2_u8), n));
+            assert_eq!(ten_pow(n), Pow::pow(BigUint::from(10_u8), n));
+            assert_eq!(ten_pow_bigint(n), Pow::pow(BigInt::from(10_u8), n));
+        }
+    }
+}
@@ -0,0 +1,859 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use num::{bigint::Sign, BigInt, BigUint};
+
+/// Builds a [`num::BigUint`] from bits in least significant first order.
+///
+/// The bits are packed into 32 bit digits before the value is assembled, so construction is linear in the number of
+/// bits. An empty iterator yields zero.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::decode::biguint_from_bits_lsb;
+///
+/// // 0b110 = 6, least significant bit first
+/// assert_eq!(biguint_from_bits_lsb([false, true, true]), 6_u32.into());
+/// ```
+pub fn biguint_from_bits_lsb(iter: impl IntoIterator<Item = bool>) -> BigUint {
+    let mut digits = Vec::new();
+    let mut current = 0_u32;
+    let mut filled = 0;
+    for bit in iter {
+        current |= u32::from(bit) << filled;
+        filled += 1;
+        if filled == u32::BITS {
+            digits.push(current);
+            current = 0;
+            filled = 0;
+        }
+    }
+    if filled > 0 {
+        digits.push(current);
+    }
+    BigUint::new(digits)
+}
+
+/// Builds a [`num::BigUint`] from bits in most significant first order.
+///
+/// The bits are packed into 64 bit words before the value is assembled, so construction is linear in the number of
+/// bits. An empty iterator yields zero.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::decode::biguint_from_bits_msb;
+///
+/// // 0b110 = 6, most significant bit first
+/// assert_eq!(biguint_from_bits_msb([true, true, false]), 6_u32.into());
+/// ```
+pub fn biguint_from_bits_msb(iter: impl IntoIterator<Item = bool>) -> BigUint {
+    let mut words = Vec::new();
+    let mut current = 0_u64;
+    let mut filled = 0;
+    for bit in iter {
+        current = (current << 1) | u64::from(bit);
+        filled += 1;
+        if filled == u64::BITS {
+            words.push(current);
+            current = 0;
+            filled = 0;
+        }
+    }
+    // the full words are most significant first, the partial word holds the lowest `filled` bits
+    let digits = words
+        .iter()
+        .rev()
+        .flat_map(|&word| [word as u32, (word >> 32) as u32])
+        .collect();
+    (BigUint::new(digits) << filled) | BigUint::from(current)
+}
+
+/// Builds a [`num::BigInt`] from a sign and little endian `u64` limbs.
+///
+/// Trailing zero limbs are ignored and an all-zero (or empty) limb stream yields zero regardless of `sign`. Like
+/// [`num::BigInt::from_biguint`], [`Sign::NoSign`] always yields zero. Use
+/// [`to_u64_limbs_le`](crate::encode::to_u64_limbs_le) for the inverse conversion.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::decode::bigint_from_u64_limbs_le;
+/// use num::{bigint::Sign, BigInt};
+///
+/// let value = bigint_from_u64_limbs_le(Sign::Minus, [0, 1]);
+/// assert_eq!(value, -(BigInt::from(1_u8) << 64_u32));
+/// ```
+pub fn bigint_from_u64_limbs_le(sign: Sign, limbs: impl IntoIterator<Item = u64>) -> BigInt {
+    let digits = limbs
+        .into_iter()
+        .flat_map(|limb| [limb as u32, (limb >> 32) as u32])
+        .collect();
+    BigInt::from_biguint(sign, BigUint::new(digits))
+}
+
+/// Builds a [`num::BigInt`] from a sign and little endian `u32` limbs.
+///
+/// See [`bigint_from_u64_limbs_le`] for the handling of zero and [`Sign::NoSign`].
+pub fn bigint_from_u32_limbs_le(sign: Sign, limbs: impl IntoIterator<Item = u32>) -> BigInt {
+    BigInt::from_biguint(sign, BigUint::new(limbs.into_iter().collect()))
+}
+
+/// Error returned when digit values do not form a number in the given radix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RadixDigitError {
+    /// The radix is not between 2 and 36.
+    InvalidRadix {
+        /// The rejected radix.
+        radix: u32,
+    },
+    /// A digit is not smaller than the radix.
+    InvalidDigit {
+        /// The position of the first invalid digit in the slice.
+        index: usize,
+        /// The value of the digit.
+        digit: u8,
+        /// The radix the digit was checked against.
+        radix: u32,
+    },
+}
+
+impl fmt::Display for RadixDigitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RadixDigitError::InvalidRadix { radix } => {
+                write!(f, "radix {radix} is not between 2 and 36")
+            }
+            RadixDigitError::InvalidDigit {
+                index,
+                digit,
+                radix,
+            } => write!(
+                f,
+                "digit {digit} at index {index} is not valid in radix {radix}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for RadixDigitError {}
+
+/// Checks the radix and that every digit is smaller than it.
+fn check_radix_digits(digits: &[u8], radix: u32) -> Result<(), RadixDigitError> {
+    if !(2..=36).contains(&radix) {
+        return Err(RadixDigitError::InvalidRadix { radix });
+    }
+    match digits.iter().position(|&digit| u32::from(digit) >= radix) {
+        Some(index) => Err(RadixDigitError::InvalidDigit {
+            index,
+            digit: digits[index],
+            radix,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Builds a [`num::BigUint`] from digit values in most significant first order.
+///
+/// The digits are values like `[1, 15]`, not ASCII characters. Unlike [`BigUint::from_radix_be`], which panics for a
+/// radix beyond 256 and returns `None` without saying why, invalid input is reported with the position and value of
+/// the first bad digit. The radix has to be between 2 and 36, and an empty slice yields zero.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::decode::{biguint_from_radix_digits_be, RadixDigitError};
+/// use num::BigUint;
+///
+/// assert_eq!(biguint_from_radix_digits_be(&[1, 15], 16), Ok(BigUint::from(0x1f_u8)));
+/// assert_eq!(
+///     biguint_from_radix_digits_be(&[1, 10, 3], 10),
+///     Err(RadixDigitError::InvalidDigit { index: 1, digit: 10, radix: 10 })
+/// );
+/// ```
+pub fn biguint_from_radix_digits_be(digits: &[u8], radix: u32) -> Result<BigUint, RadixDigitError> {
+    check_radix_digits(digits, radix)?;
+    // the radix and digits are valid, so this is always Some
+    Ok(BigUint::from_radix_be(digits, radix).unwrap_or_default())
+}
+
+/// Builds a [`num::BigUint`] from digit values in least significant first order.
+///
+/// See [`biguint_from_radix_digits_be`] for the validation.
+pub fn biguint_from_radix_digits_le(digits: &[u8], radix: u32) -> Result<BigUint, RadixDigitError> {
+    check_radix_digits(digits, radix)?;
+    // the radix and digits are valid, so this is always Some
+    Ok(BigUint::from_radix_le(digits, radix).unwrap_or_default())
+}
+
+/// Builds a [`num::BigInt`] from a sign and digit values in most significant first order.
+///
+/// See [`biguint_from_radix_digits_be`] for the validation. Like [`num::BigInt::from_biguint`], [`Sign::NoSign`] and
+/// zero digits both yield zero.
+pub fn bigint_from_radix_digits_be(
+    sign: Sign,
+    digits: &[u8],
+    radix: u32,
+) -> Result<BigInt, RadixDigitError> {
+    Ok(BigInt::from_biguint(
+        sign,
+        biguint_from_radix_digits_be(digits, radix)?,
+    ))
+}
+
+/// Builds a [`num::BigInt`] from a sign and digit values in least significant first order.
+///
+/// See [`bigint_from_radix_digits_be`] for the handling of the sign.
+pub fn bigint_from_radix_digits_le(
+    sign: Sign,
+    digits: &[u8],
+    radix: u32,
+) -> Result<BigInt, RadixDigitError> {
+    Ok(BigInt::from_biguint(
+        sign,
+        biguint_from_radix_digits_le(digits, radix)?,
+    ))
+}
+
+/// The number of decimal digits packed into one chunk of a [`BigIntBuilder`].
+pub(crate) const CHUNK_DIGITS: u32 = 19;
+
+/// `10^CHUNK_DIGITS`, the largest power of ten that fits into a `u64`.
+pub(crate) const CHUNK_BASE: u64 = 10_u64.pow(CHUNK_DIGITS);
+
+/// Accumulates decimal digits into a [`num::BigUint`] without quadratic cost.
+///
+/// Digits are packed into 19 digit chunks that fit a `u64`. When the value is built, the chunks are combined pairwise
+/// by divide and conquer, so the large multiplications benefit from num-bigint's sub-quadratic algorithms instead of
+/// multiplying the whole accumulated value by ten for every digit.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::decode::BigIntBuilder;
+/// use num::BigUint;
+///
+/// let mut builder = BigIntBuilder::new();
+/// for digit in [1, 2, 3] {
+///     builder.push_digit(digit);
+/// }
+/// assert_eq!(builder.build(), BigUint::from(123_u8));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BigIntBuilder {
+    chunks: Vec<u64>,
+    current: u64,
+    current_len: u32,
+}
+
+impl BigIntBuilder {
+    /// Creates a builder without any digits, which builds zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a decimal digit as the new least significant digit.
+    ///
+    /// # Panics
+    /// Panics if `digit` is larger than 9.
+    pub fn push_digit(&mut self, digit: u8) {
+        assert!(digit < 10, "decimal digit out of range: {digit}");
+        self.current = self.current * 10 + u64::from(digit);
+        self.current_len += 1;
+        if self.current_len == CHUNK_DIGITS {
+            self.chunks.push(self.current);
+            self.current = 0;
+            self.current_len = 0;
+        }
+    }
+
+    /// Returns the number of digits pushed so far.
+    pub fn digit_count(&self) -> usize {
+        self.chunks.len() * CHUNK_DIGITS as usize + self.current_len as usize
+    }
+
+    /// Builds the accumulated value.
+    pub fn build(self) -> BigUint {
+        let mut powers = vec![BigUint::from(CHUNK_BASE)];
+        while 1 << powers.len() < self.chunks.len() {
+            let last = &powers[powers.len() - 1];
+            powers.push(last * last);
+        }
+        let value = combine_chunks(&self.chunks, &powers);
+        value * 10_u64.pow(self.current_len) + self.current
+    }
+}
+
+/// Combines base `10^19` chunks, most significant first, where `powers[k]` holds `(10^19)^(2^k)`.
+fn combine_chunks(chunks: &[u64], powers: &[BigUint]) -> BigUint {
+    match chunks.len() {
+        0 => BigUint::default(),
+        1 => BigUint::from(chunks[0]),
+        len => {
+            // the low half holds the largest power of two number of chunks below len
+            let level = (len - 1).ilog2() as usize;
+            let (high, low) = chunks.split_at(len - (1 << level));
+            combine_chunks(high, powers) * &powers[level] + combine_chunks(low, powers)
+        }
+    }
+}
+
+/// Interprets bytes as an unsigned integer in big or little endian order.
+///
+/// Leading zero bytes are allowed and an empty input yields zero. The `BigInt` variants return the same non-negative
+/// value as a [`num::BigInt`].
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::decode::BytesToBigUint;
+/// use num::BigUint;
+///
+/// let hash = [0x12_u8, 0x34];
+/// assert_eq!(hash.to_biguint_be(), BigUint::from(0x1234_u32));
+/// assert_eq!(hash.to_biguint_le(), BigUint::from(0x3412_u32));
+/// assert_eq!(vec![0_u8, 0, 1].to_biguint_be(), BigUint::from(1_u8));
+/// ```
+pub trait BytesToBigUint {
+    fn to_biguint_be(&self) -> BigUint;
+
+    fn to_biguint_le(&self) -> BigUint;
+
+    fn to_bigint_be(&self) -> BigInt {
+        BigInt::from(self.to_biguint_be())
+    }
+
+    fn to_bigint_le(&self) -> BigInt {
+        BigInt::from(self.to_biguint_le())
+    }
+}
+
+impl BytesToBigUint for [u8] {
+    fn to_biguint_be(&self) -> BigUint {
+        BigUint::from_bytes_be(self)
+    }
+
+    fn to_biguint_le(&self) -> BigUint {
+        BigUint::from_bytes_le(self)
+    }
+}
+
+impl<const N: usize> BytesToBigUint for [u8; N] {
+    fn to_biguint_be(&self) -> BigUint {
+        BigUint::from_bytes_be(self)
+    }
+
+    fn to_biguint_le(&self) -> BigUint {
+        BigUint::from_bytes_le(self)
+    }
+}
+
+impl BytesToBigUint for Vec<u8> {
+    fn to_biguint_be(&self) -> BigUint {
+        BigUint::from_bytes_be(self)
+    }
+
+    fn to_biguint_le(&self) -> BigUint {
+        BigUint::from_bytes_le(self)
+    }
+}
+
+impl<T: BytesToBigUint + ?Sized> BytesToBigUint for &T {
+    fn to_biguint_be(&self) -> BigUint {
+        (**self).to_biguint_be()
+    }
+
+    fn to_biguint_le(&self) -> BigUint {
+        (**self).to_biguint_le()
+    }
+}
+
+/// Interprets bytes as a two's complement signed integer in big or little endian order.
+///
+/// This is the encoding of DER integers and Java's `BigInteger.toByteArray()`: the top bit of the most significant
+/// byte is the sign, so `[0xff]` is -1 while [`BytesToBigUint`] reads it as 255. An empty input yields zero.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::decode::{BytesToBigUint, SignedBytesToBigInt};
+/// use num::BigInt;
+///
+/// assert_eq!([0xff_u8].to_bigint_signed_be(), BigInt::from(-1));
+/// assert_eq!([0xff_u8].to_bigint_be(), BigInt::from(255));
+/// assert_eq!([0x00_u8, 0xff].to_bigint_signed_be(), BigInt::from(255));
+/// ```
+pub trait SignedBytesToBigInt {
+    fn to_bigint_signed_be(&self) -> BigInt;
+
+    fn to_bigint_signed_le(&self) -> BigInt;
+}
+
+impl SignedBytesToBigInt for [u8] {
+    fn to_bigint_signed_be(&self) -> BigInt {
+        BigInt::from_signed_bytes_be(self)
+    }
+
+    fn to_bigint_signed_le(&self) -> BigInt {
+        BigInt::from_signed_bytes_le(self)
+    }
+}
+
+impl<const N: usize> SignedBytesToBigInt for [u8; N] {
+    fn to_bigint_signed_be(&self) -> BigInt {
+        BigInt::from_signed_bytes_be(self)
+    }
+
+    fn to_bigint_signed_le(&self) -> BigInt {
+        BigInt::from_signed_bytes_le(self)
+    }
+}
+
+impl SignedBytesToBigInt for Vec<u8> {
+    fn to_bigint_signed_be(&self) -> BigInt {
+        BigInt::from_signed_bytes_be(self)
+    }
+
+    fn to_bigint_signed_le(&self) -> BigInt {
+        BigInt::from_signed_bytes_le(self)
+    }
+}
+
+impl<T: SignedBytesToBigInt + ?Sized> SignedBytesToBigInt for &T {
+    fn to_bigint_signed_be(&self) -> BigInt {
+        (**self).to_bigint_signed_be()
+    }
+
+    fn to_bigint_signed_le(&self) -> BigInt {
+        (**self).to_bigint_signed_le()
+    }
+}
+
+/// Interprets limbs as an unsigned integer, least significant limb first.
+///
+/// Zero limbs at the most significant end do not affect the value, so fixed width representations like a `[u64; 4]`
+/// for 256 bit integers can be converted as they are. An empty slice yields zero. The `BigInt` variant returns the same
+/// non-negative value as a [`num::BigInt`].
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::decode::LimbsToBigUint;
+/// use num::BigUint;
+///
+/// let value = [0_u64, 1, 0, 0];
+/// assert_eq!(value.to_biguint_limbs_le(), BigUint::from(1_u8) << 64_u32);
+/// assert_eq!([0_u32, 0, 1].to_biguint_limbs_le(), value.to_biguint_limbs_le());
+/// ```
+pub trait LimbsToBigUint {
+    fn to_biguint_limbs_le(&self) -> BigUint;
+
+    fn to_bigint_limbs_le(&self) -> BigInt {
+        BigInt::from(self.to_biguint_limbs_le())
+    }
+}
+
+impl LimbsToBigUint for [u32] {
+    fn to_biguint_limbs_le(&self) -> BigUint {
+        BigUint::from_slice(self)
+    }
+}
+
+impl LimbsToBigUint for [u64] {
+    fn to_biguint_limbs_le(&self) -> BigUint {
+        let mut digits = Vec::with_capacity(self.len() * 2);
+        for &limb in self {
+            digits.extend([limb as u32, (limb >> 32) as u32]);
+        }
+        BigUint::new(digits)
+    }
+}
+
+impl<const N: usize> LimbsToBigUint for [u32; N] {
+    fn to_biguint_limbs_le(&self) -> BigUint {
+        self.as_slice().to_biguint_limbs_le()
+    }
+}
+
+impl<const N: usize> LimbsToBigUint for [u64; N] {
+    fn to_biguint_limbs_le(&self) -> BigUint {
+        self.as_slice().to_biguint_limbs_le()
+    }
+}
+
+impl<T: LimbsToBigUint + ?Sized> LimbsToBigUint for &T {
+    fn to_biguint_limbs_le(&self) -> BigUint {
+        (**self).to_biguint_limbs_le()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{bigint::Sign, BigInt, BigUint, One, Zero};
+
+    use super::{
+        bigint_from_radix_digits_be, bigint_from_radix_digits_le, bigint_from_u32_limbs_le,
+        bigint_from_u64_limbs_le, biguint_from_bits_lsb, biguint_from_bits_msb,
+        biguint_from_radix_digits_be, biguint_from_radix_digits_le, BigIntBuilder, BytesToBigUint,
+        LimbsToBigUint, RadixDigitError, SignedBytesToBigInt,
+    };
+    use crate::encode::to_u64_limbs_le;
+
+    /// Builds the reference value bit by bit via set_bit
+    fn reference_lsb(bits: &[bool]) -> BigUint {
+        let mut value = BigUint::zero();
+        for (i, &bit) in bits.iter().enumerate() {
+            if bit {
+                value.set_bit(i as u64, true);
+            }
+        }
+        value
+    }
+
+    /// Test empty iterators
+    #[test]
+    fn test_empty() {
+        assert_eq!(biguint_from_bits_lsb([]), BigUint::zero());
+        assert_eq!(biguint_from_bits_msb([]), BigUint::zero());
+    }
+
+    /// Test a 1000 bit alternating pattern against set_bit construction
+    #[test]
+    fn test_alternating_pattern() {
+        let bits: Vec<bool> = (0..1000).map(|i| i % 2 == 0).collect();
+        assert_eq!(
+            biguint_from_bits_lsb(bits.iter().copied()),
+            reference_lsb(&bits)
+        );
+        assert_eq!(
+            biguint_from_bits_msb(bits.iter().rev().copied()),
+            reference_lsb(&bits)
+        );
+    }
+
+    /// Test all ones of various lengths
+    #[test]
+    fn test_all_ones() {
+        for len in [1_u32, 31, 32, 33, 63, 64, 65, 128, 1000] {
+            let expected = (BigUint::one() << len) - 1_u8;
+            let bits = || (0..len).map(|_| true);
+            assert_eq!(biguint_from_bits_lsb(bits()), expected);
+            assert_eq!(biguint_from_bits_msb(bits()), expected);
+        }
+    }
+
+    /// Test that leading zeros do not change the value
+    #[test]
+    fn test_leading_zeros() {
+        let bits = [true, false, true];
+        let padded_lsb = bits.iter().copied().chain([false; 100]);
+        let padded_msb = [false; 100].into_iter().chain(bits.iter().rev().copied());
+        assert_eq!(biguint_from_bits_lsb(padded_lsb), BigUint::from(5_u8));
+        assert_eq!(biguint_from_bits_msb(padded_msb), BigUint::from(5_u8));
+    }
+
+    /// Test agreement between the LSB and MSB variants on reversed input
+    #[test]
+    fn test_lsb_msb_agreement() {
+        let mut state = 0x9e37_79b9_u32;
+        for len in [0_usize, 1, 7, 64, 65, 129, 777] {
+            let bits: Vec<bool> = (0..len)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state & 1 == 1
+                })
+                .collect();
+            assert_eq!(
+                biguint_from_bits_lsb(bits.iter().copied()),
+                biguint_from_bits_msb(bits.iter().rev().copied())
+            );
+        }
+    }
+
+    /// Test that zero limbs yield zero regardless of the sign
+    #[test]
+    fn test_limbs_zero() {
+        for sign in [Sign::Minus, Sign::NoSign, Sign::Plus] {
+            let value = bigint_from_u64_limbs_le(sign, [0, 0, 0]);
+            assert_eq!(value, BigInt::zero());
+            assert_eq!(value.sign(), Sign::NoSign);
+            assert_eq!(bigint_from_u32_limbs_le(sign, []), BigInt::zero());
+        }
+        assert_eq!(to_u64_limbs_le(&BigInt::zero()), (Sign::NoSign, vec![]));
+    }
+
+    /// Test single limb values
+    #[test]
+    fn test_limbs_single() {
+        assert_eq!(
+            bigint_from_u64_limbs_le(Sign::Plus, [u64::MAX]),
+            BigInt::from(u64::MAX)
+        );
+        assert_eq!(bigint_from_u64_limbs_le(Sign::Minus, [1]), BigInt::from(-1));
+        assert_eq!(
+            bigint_from_u32_limbs_le(Sign::Minus, [u32::MAX]),
+            -BigInt::from(u32::MAX)
+        );
+        assert_eq!(
+            bigint_from_u64_limbs_le(Sign::Plus, [42, 0, 0]),
+            BigInt::from(42)
+        );
+    }
+
+    /// Test round trips of 10 limb values
+    #[test]
+    fn test_limbs_round_trip() {
+        let limbs: Vec<u64> = (1..=10).map(|i| u64::MAX / i).collect();
+        for sign in [Sign::Minus, Sign::Plus] {
+            let value = bigint_from_u64_limbs_le(sign, limbs.iter().copied());
+            assert_eq!(to_u64_limbs_le(&value), (sign, limbs.clone()));
+            let u32_limbs = limbs
+                .iter()
+                .flat_map(|&limb| [limb as u32, (limb >> 32) as u32]);
+            assert_eq!(bigint_from_u32_limbs_le(sign, u32_limbs), value);
+        }
+    }
+
+    /// Test agreement with iter_u64_digits
+    #[test]
+    fn test_limbs_agreement_with_digits() {
+        let value = -(BigInt::from(u128::MAX) << 100_u32) - 7_u8;
+        let (sign, limbs) = to_u64_limbs_le(&value);
+        assert_eq!(sign, Sign::Minus);
+        assert_eq!(limbs, value.iter_u64_digits().collect::<Vec<_>>());
+        assert_eq!(bigint_from_u64_limbs_le(sign, limbs), value);
+    }
+
+    /// Test the builder against parse_bytes for lengths around the chunk boundaries
+    #[test]
+    fn test_builder_lengths() {
+        for len in [0_usize, 1, 18, 19, 20, 37, 38, 39, 57, 100, 1000, 4321] {
+            let digits: Vec<u8> = (0..len).map(|i| ((i * 7 + 3) % 10) as u8).collect();
+            let mut builder = BigIntBuilder::new();
+            for &digit in &digits {
+                builder.push_digit(digit);
+            }
+            assert_eq!(builder.digit_count(), len);
+            let ascii: Vec<u8> = digits.iter().map(|digit| b'0' + digit).collect();
+            let expected = BigUint::parse_bytes(&ascii, 10).unwrap_or_default();
+            assert_eq!(builder.build(), expected);
+        }
+    }
+
+    /// Test a builder without digits
+    #[test]
+    fn test_builder_empty() {
+        assert_eq!(BigIntBuilder::new().build(), BigUint::zero());
+    }
+
+    /// Test that out of range digits are rejected
+    #[test]
+    #[should_panic(expected = "decimal digit out of range")]
+    fn test_builder_invalid_digit() {
+        BigIntBuilder::new().push_digit(10);
+    }
+
+    /// Test that empty inputs and leading zeros are accepted
+    #[test]
+    fn test_bytes_zero_and_padding() {
+        let empty: &[u8] = &[];
+        assert_eq!(empty.to_biguint_be(), BigUint::zero());
+        assert_eq!(empty.to_biguint_le(), BigUint::zero());
+        assert_eq!(empty.to_bigint_be().sign(), Sign::NoSign);
+        assert_eq!([0_u8; 32].to_biguint_le(), BigUint::zero());
+        assert_eq!([0_u8, 0, 0, 5].to_biguint_be(), BigUint::from(5_u8));
+        assert_eq!([5_u8, 0, 0, 0].to_biguint_le(), BigUint::from(5_u8));
+        assert_eq!(vec![0_u8, 0, 0x80].to_bigint_be(), BigInt::from(0x80));
+    }
+
+    /// Test that both byte orders of the same buffer are each other's reversal
+    #[test]
+    fn test_bytes_endianness() {
+        let buffer: Vec<u8> = (1..=32).collect();
+        let be = buffer.to_biguint_be();
+        let le = buffer.to_biguint_le();
+        assert_ne!(be, le);
+        let reversed: Vec<u8> = buffer.iter().rev().copied().collect();
+        assert_eq!(reversed.to_biguint_le(), be);
+        assert_eq!(reversed.as_slice().to_biguint_be(), le);
+        assert_eq!(be.to_bytes_be(), buffer);
+        assert_eq!(le.to_bytes_le(), buffer);
+        assert_eq!(buffer.to_bigint_le(), BigInt::from(le));
+    }
+
+    /// Test that 64 bytes of 0xFF round trip and are never negative
+    #[test]
+    fn test_bytes_all_ones() {
+        let bytes = [0xff_u8; 64];
+        let value = bytes.to_biguint_be();
+        assert_eq!(value, (BigUint::one() << 512_u32) - 1_u8);
+        assert_eq!(value.to_bytes_be(), bytes);
+        assert_eq!(bytes.to_biguint_le(), value);
+        assert_eq!(bytes.to_bigint_be().sign(), Sign::Plus);
+        let slice: &[u8] = &bytes;
+        assert_eq!(slice.to_bigint_le(), BigInt::from(value));
+    }
+
+    /// Test the two's complement vectors of single bytes and empty input
+    #[test]
+    fn test_signed_bytes_vectors() {
+        assert_eq!([0xff_u8].to_bigint_signed_be(), BigInt::from(-1));
+        assert_eq!([0x80_u8].to_bigint_signed_be(), BigInt::from(-128));
+        assert_eq!([0x7f_u8].to_bigint_signed_le(), BigInt::from(127));
+        assert_eq!([0x00_u8, 0xff].to_bigint_signed_be(), BigInt::from(255));
+        assert_eq!([0xff_u8, 0x00].to_bigint_signed_le(), BigInt::from(255));
+        assert_eq!(
+            [0xff_u8, 0xff, 0xff].to_bigint_signed_be(),
+            BigInt::from(-1)
+        );
+        let empty: &[u8] = &[];
+        assert_eq!(empty.to_bigint_signed_be(), BigInt::zero());
+        assert_eq!(Vec::new().to_bigint_signed_le(), BigInt::zero());
+    }
+
+    /// Test a 33 byte negative value and round trips through to_signed_bytes
+    #[test]
+    fn test_signed_bytes_wide() {
+        // -2^256 needs a 33rd byte for its sign
+        let mut bytes = vec![0_u8; 33];
+        bytes[0] = 0xff;
+        let value = bytes.to_bigint_signed_be();
+        assert_eq!(value, -(BigInt::one() << 256_u32));
+        assert_eq!(value.to_signed_bytes_be(), bytes);
+        for value in [
+            -(BigInt::from(u128::MAX) << 100_u32) + 12345,
+            BigInt::from(i128::MIN),
+            BigInt::from(u128::MAX),
+            BigInt::from(-129),
+        ] {
+            assert_eq!(value.to_signed_bytes_be().to_bigint_signed_be(), value);
+            assert_eq!(value.to_signed_bytes_le().to_bigint_signed_le(), value);
+        }
+    }
+
+    /// Test that u32 and u64 limbs of the same number agree
+    #[test]
+    fn test_limbs_widths_agree() {
+        let wide = [0x0123_4567_89ab_cdef_u64, 0xfedc_ba98_7654_3210, 7];
+        let narrow = [0x89ab_cdef_u32, 0x0123_4567, 0x7654_3210, 0xfedc_ba98, 7, 0];
+        assert_eq!(wide.to_biguint_limbs_le(), narrow.to_biguint_limbs_le());
+        let expected = (BigUint::from(7_u8) << 128_u32)
+            + (BigUint::from(0xfedc_ba98_7654_3210_u64) << 64_u32)
+            + 0x0123_4567_89ab_cdef_u64;
+        assert_eq!(wide.as_slice().to_biguint_limbs_le(), expected);
+        assert_eq!(narrow.to_bigint_limbs_le(), BigInt::from(expected));
+    }
+
+    /// Test empty slices and zero limbs at the most significant end
+    #[test]
+    fn test_limbs_trait_zero() {
+        let empty: &[u64] = &[];
+        assert_eq!(empty.to_biguint_limbs_le(), BigUint::zero());
+        let empty: &[u32] = &[];
+        assert_eq!(empty.to_bigint_limbs_le().sign(), Sign::NoSign);
+        assert_eq!([0_u64; 8].to_biguint_limbs_le(), BigUint::zero());
+        assert_eq!([5_u64, 0, 0, 0].to_biguint_limbs_le(), BigUint::from(5_u8));
+    }
+
+    /// Test that four u64::MAX limbs are 2^256 - 1
+    #[test]
+    fn test_limbs_all_ones() {
+        let expected = (BigUint::one() << 256_u32) - 1_u8;
+        assert_eq!([u64::MAX; 4].to_biguint_limbs_le(), expected);
+        assert_eq!([u32::MAX; 8].to_biguint_limbs_le(), expected);
+        assert_eq!([u64::MAX; 4].to_bigint_limbs_le(), BigInt::from(expected));
+    }
+
+    /// Test known values in bases 2, 10, 16 and 36 in both digit orders
+    #[test]
+    fn test_radix_digits_known() {
+        for (digits, radix, expected) in [
+            (vec![1, 0, 1, 1], 2, BigUint::from(0b1011_u8)),
+            (
+                vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0],
+                10,
+                BigUint::from(1_234_567_890_u32),
+            ),
+            (vec![15, 15, 0, 1], 16, BigUint::from(0xff01_u32)),
+            (vec![35, 0, 1], 36, BigUint::from(35_u32 * 36 * 36 + 1)),
+            (vec![1; 40], 2, (BigUint::one() << 40_u32) - 1_u8),
+        ] {
+            assert_eq!(
+                biguint_from_radix_digits_be(&digits, radix),
+                Ok(expected.clone())
+            );
+            let reversed: Vec<u8> = digits.iter().rev().copied().collect();
+            assert_eq!(biguint_from_radix_digits_le(&reversed, radix), Ok(expected));
+        }
+        let digits: Vec<u8> = "340282366920938463463374607431768211456"
+            .bytes()
+            .map(|byte| byte - b'0')
+            .collect();
+        assert_eq!(
+            biguint_from_radix_digits_be(&digits, 10),
+            Ok(BigUint::from(u128::MAX) + 1_u8)
+        );
+    }
+
+    /// Test that the first invalid digit is reported with its position
+    #[test]
+    fn test_radix_digits_invalid_digit() {
+        let digits = [1, 2, 16, 3, 17];
+        let expected = RadixDigitError::InvalidDigit {
+            index: 2,
+            digit: 16,
+            radix: 16,
+        };
+        assert_eq!(biguint_from_radix_digits_be(&digits, 16), Err(expected));
+        assert_eq!(biguint_from_radix_digits_le(&digits, 16), Err(expected));
+        assert_eq!(
+            bigint_from_radix_digits_be(Sign::Minus, &digits, 16),
+            Err(expected)
+        );
+        assert_eq!(
+            expected.to_string(),
+            "digit 16 at index 2 is not valid in radix 16"
+        );
+        assert!(biguint_from_radix_digits_be(&digits, 18).is_ok());
+    }
+
+    /// Test that radixes outside 2..=36 are rejected
+    #[test]
+    fn test_radix_digits_invalid_radix() {
+        for radix in [0, 1, 37, 256, 1000] {
+            assert_eq!(
+                biguint_from_radix_digits_be(&[0], radix),
+                Err(RadixDigitError::InvalidRadix { radix })
+            );
+            assert_eq!(
+                biguint_from_radix_digits_le(&[], radix),
+                Err(RadixDigitError::InvalidRadix { radix })
+            );
+        }
+        assert_eq!(
+            RadixDigitError::InvalidRadix { radix: 1 }.to_string(),
+            "radix 1 is not between 2 and 36"
+        );
+    }
+
+    /// Test empty slices, zero digits and the signed wrappers
+    #[test]
+    fn test_radix_digits_zero_and_sign() {
+        assert_eq!(biguint_from_radix_digits_be(&[], 10), Ok(BigUint::zero()));
+        assert_eq!(
+            biguint_from_radix_digits_le(&[0, 0], 36),
+            Ok(BigUint::zero())
+        );
+        assert_eq!(
+            bigint_from_radix_digits_be(Sign::Minus, &[], 10).map(|value| value.sign()),
+            Ok(Sign::NoSign)
+        );
+        assert_eq!(
+            bigint_from_radix_digits_be(Sign::Minus, &[4, 2], 10),
+            Ok(BigInt::from(-42))
+        );
+        assert_eq!(
+            bigint_from_radix_digits_le(Sign::Plus, &[2, 4], 10),
+            Ok(BigInt::from(42))
+        );
+        assert_eq!(
+            bigint_from_radix_digits_le(Sign::NoSign, &[2, 4], 10),
+            Ok(BigInt::zero())
+        );
+    }
+}
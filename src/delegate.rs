@@ -0,0 +1,294 @@
+/// Implements [`InfallibleToBigInt`](crate::InfallibleToBigInt) for a type by converting one of its fields or the
+/// result of an expression.
+///
+/// `impl_infallible_to_bigint!(Type => .field)` converts a field, and `impl_infallible_to_bigint!(Type => |v| expr)`
+/// converts the value of `expr`, with `v` bound to `&self`. The field or expression has to implement
+/// `InfallibleToBigInt` itself, and its `i128` fast path is forwarded. Generic types list their parameters in angle
+/// brackets first and may end with a `where` clause of simple `Type: Trait` bounds.
+///
+/// Types with both conversions call this and [`impl_infallible_to_biguint!`](crate::impl_infallible_to_biguint).
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::{impl_infallible_to_bigint, InfallibleToBigInt};
+/// use num::BigInt;
+///
+/// struct Cents(i64);
+/// impl_infallible_to_bigint!(Cents => .0);
+///
+/// struct Reading {
+///     raw: [u8; 2],
+/// }
+///
+/// impl Reading {
+///     fn raw_value(&self) -> i16 {
+///         i16::from_le_bytes(self.raw)
+///     }
+/// }
+/// impl_infallible_to_bigint!(Reading => |v| v.raw_value());
+///
+/// struct Tagged<T> {
+///     value: T,
+///     tag: &'static str,
+/// }
+/// impl_infallible_to_bigint!(<T> Tagged<T> where T: InfallibleToBigInt => .value);
+///
+/// assert_eq!(Cents(-5).to_bigint(), BigInt::from(-5));
+/// assert_eq!(Reading { raw: [0xff, 0xff] }.to_bigint(), BigInt::from(-1));
+/// assert_eq!(Tagged { value: u128::MAX, tag: "max" }.to_bigint(), BigInt::from(u128::MAX));
+/// ```
+#[cfg(feature = "bigint")]
+#[macro_export]
+macro_rules! impl_infallible_to_bigint {
+    (@value $this:ident . $field:tt) => {
+        $this.$field
+    };
+    (@value $this:ident |$value:ident| $conversion:expr) => {{
+        let $value = $this;
+        $conversion
+    }};
+    (
+        <$($param:ident $(: $param_bound:path)?),+ $(,)?> $ty:ty
+        $(where $($bounded:ty: $bound:path),+ $(,)?)? => $($conversion:tt)+
+    ) => {
+        impl<$($param $(: $param_bound)?),+> $crate::InfallibleToBigInt for $ty $(where $($bounded: $bound),+)? {
+            fn to_bigint(&self) -> $crate::__delegate::BigInt {
+                $crate::InfallibleToBigInt::to_bigint(
+                    &$crate::impl_infallible_to_bigint!(@value self $($conversion)+),
+                )
+            }
+
+            fn to_i128_fast(&self) -> ::core::option::Option<i128> {
+                $crate::InfallibleToBigInt::to_i128_fast(
+                    &$crate::impl_infallible_to_bigint!(@value self $($conversion)+),
+                )
+            }
+        }
+    };
+    ($ty:ty => $($conversion:tt)+) => {
+        impl $crate::InfallibleToBigInt for $ty {
+            fn to_bigint(&self) -> $crate::__delegate::BigInt {
+                $crate::InfallibleToBigInt::to_bigint(
+                    &$crate::impl_infallible_to_bigint!(@value self $($conversion)+),
+                )
+            }
+
+            fn to_i128_fast(&self) -> ::core::option::Option<i128> {
+                $crate::InfallibleToBigInt::to_i128_fast(
+                    &$crate::impl_infallible_to_bigint!(@value self $($conversion)+),
+                )
+            }
+        }
+    };
+}
+
+/// Implements [`InfallibleToBigUint`](crate::InfallibleToBigUint) for a type by converting one of its fields or the
+/// result of an expression.
+///
+/// This takes the same forms as [`impl_infallible_to_bigint!`](crate::impl_infallible_to_bigint), and forwards the
+/// `u128` fast path.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::{impl_infallible_to_biguint, InfallibleToBigUint};
+/// use num::BigUint;
+///
+/// struct Millimetres(u64);
+/// impl_infallible_to_biguint!(Millimetres => |v| v.0);
+///
+/// struct Metres(u64);
+/// impl_infallible_to_biguint!(Metres => |v| u128::from(v.0) * 1000);
+///
+/// assert_eq!(Millimetres(1500).to_biguint(), BigUint::from(1500_u16));
+/// assert_eq!(Metres(u64::MAX).to_biguint(), BigUint::from(u64::MAX) * 1000_u16);
+/// ```
+#[cfg(feature = "biguint")]
+#[macro_export]
+macro_rules! impl_infallible_to_biguint {
+    (@value $this:ident . $field:tt) => {
+        $this.$field
+    };
+    (@value $this:ident |$value:ident| $conversion:expr) => {{
+        let $value = $this;
+        $conversion
+    }};
+    (
+        <$($param:ident $(: $param_bound:path)?),+ $(,)?> $ty:ty
+        $(where $($bounded:ty: $bound:path),+ $(,)?)? => $($conversion:tt)+
+    ) => {
+        impl<$($param $(: $param_bound)?),+> $crate::InfallibleToBigUint for $ty $(where $($bounded: $bound),+)? {
+            fn to_biguint(&self) -> $crate::__delegate::BigUint {
+                $crate::InfallibleToBigUint::to_biguint(
+                    &$crate::impl_infallible_to_biguint!(@value self $($conversion)+),
+                )
+            }
+
+            fn to_u128_fast(&self) -> ::core::option::Option<u128> {
+                $crate::InfallibleToBigUint::to_u128_fast(
+                    &$crate::impl_infallible_to_biguint!(@value self $($conversion)+),
+                )
+            }
+        }
+    };
+    ($ty:ty => $($conversion:tt)+) => {
+        impl $crate::InfallibleToBigUint for $ty {
+            fn to_biguint(&self) -> $crate::__delegate::BigUint {
+                $crate::InfallibleToBigUint::to_biguint(
+                    &$crate::impl_infallible_to_biguint!(@value self $($conversion)+),
+                )
+            }
+
+            fn to_u128_fast(&self) -> ::core::option::Option<u128> {
+                $crate::InfallibleToBigUint::to_u128_fast(
+                    &$crate::impl_infallible_to_biguint!(@value self $($conversion)+),
+                )
+            }
+        }
+    };
+}
+
+/// Support code for the delegation macros, which has to be public but is not part of the API.
+pub mod support {
+    #[cfg(feature = "bigint")]
+    pub use num::BigInt;
+    #[cfg(feature = "biguint")]
+    pub use num::BigUint;
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "bigint")]
+    use num::BigInt;
+    #[cfg(feature = "biguint")]
+    use num::BigUint;
+
+    #[cfg(feature = "bigint")]
+    use crate::InfallibleToBigInt;
+    #[cfg(feature = "biguint")]
+    use crate::InfallibleToBigUint;
+
+    #[cfg(feature = "bigint")]
+    struct Offset(i128);
+    #[cfg(feature = "bigint")]
+    impl_infallible_to_bigint!(Offset => .0);
+
+    #[cfg(feature = "bigint")]
+    struct Sensor {
+        raw: u16,
+        negative: bool,
+    }
+
+    #[cfg(feature = "bigint")]
+    impl Sensor {
+        fn reading(&self) -> i32 {
+            if self.negative {
+                -i32::from(self.raw)
+            } else {
+                i32::from(self.raw)
+            }
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    impl_infallible_to_bigint!(Sensor => |sensor| sensor.reading());
+
+    #[cfg(feature = "bigint")]
+    struct Labelled<T> {
+        value: T,
+        #[allow(dead_code)]
+        label: &'static str,
+    }
+
+    #[cfg(feature = "bigint")]
+    impl_infallible_to_bigint!(<T> Labelled<T> where T: InfallibleToBigInt => .value);
+
+    #[cfg(feature = "biguint")]
+    struct Grams(u64);
+    #[cfg(feature = "biguint")]
+    impl_infallible_to_biguint!(Grams => .0);
+
+    #[cfg(feature = "biguint")]
+    struct Kilograms(u64);
+    #[cfg(feature = "biguint")]
+    impl_infallible_to_biguint!(Kilograms => |v| u128::from(v.0) * 1000);
+
+    #[cfg(feature = "biguint")]
+    struct Scaled<T: InfallibleToBigUint, U>(T, U);
+    #[cfg(feature = "biguint")]
+    impl_infallible_to_biguint!(<T: InfallibleToBigUint, U> Scaled<T, U> => .0);
+
+    /// Test a plain newtype
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_newtype() {
+        assert_eq!(Offset(i128::MIN).to_bigint(), BigInt::from(i128::MIN));
+        assert_eq!(Offset(-1).to_i128_fast(), Some(-1));
+    }
+
+    /// Test a conversion through a getter
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_getter() {
+        let sensor = Sensor {
+            raw: u16::MAX,
+            negative: true,
+        };
+        assert_eq!(sensor.to_bigint(), BigInt::from(-65535));
+        assert_eq!(sensor.to_i128_fast(), Some(-65535));
+    }
+
+    /// Test a generic wrapper with a bignum and a primitive inside
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_generic() {
+        let wide = BigInt::from(u128::MAX) << 64_u32;
+        let labelled = Labelled {
+            value: wide.clone(),
+            label: "wide",
+        };
+        assert_eq!(labelled.to_bigint(), wide);
+        assert_eq!(labelled.to_i128_fast(), None);
+        let labelled = Labelled {
+            value: 7_u8,
+            label: "small",
+        };
+        assert_eq!(labelled.to_i128_fast(), Some(7));
+    }
+
+    /// Test the unsigned forms, including a unit scale
+    #[cfg(feature = "biguint")]
+    #[test]
+    fn test_unsigned() {
+        assert_eq!(Grams(u64::MAX).to_biguint(), BigUint::from(u64::MAX));
+        assert_eq!(Grams(3).to_u128_fast(), Some(3));
+        assert_eq!(
+            Kilograms(u64::MAX).to_biguint(),
+            BigUint::from(u64::MAX) * 1000_u16
+        );
+        assert_eq!(
+            Scaled(u128::MAX, "unused").to_biguint(),
+            BigUint::from(u128::MAX)
+        );
+    }
+
+    /// Test a type that calls both macros, one for each conversion
+    #[cfg(all(feature = "bigint", feature = "biguint"))]
+    #[test]
+    fn test_both_macros() {
+        struct Height(u64);
+        impl_infallible_to_bigint!(Height => .0);
+        impl_infallible_to_biguint!(Height => .0);
+
+        struct Pair<T>(T, T);
+        impl_infallible_to_bigint!(<T> Pair<T> where T: InfallibleToBigInt => .0);
+        impl_infallible_to_biguint!(<T> Pair<T> where T: InfallibleToBigUint => .1);
+
+        assert_eq!(Height(u64::MAX).to_bigint(), BigInt::from(u64::MAX));
+        assert_eq!(Height(7).to_i128_fast(), Some(7));
+        assert_eq!(Height(u64::MAX).to_biguint(), BigUint::from(u64::MAX));
+        assert_eq!(Height(7).to_u128_fast(), Some(7));
+        let pair = Pair(1_u8, 2_u8);
+        assert_eq!(pair.to_bigint(), BigInt::from(1));
+        assert_eq!(pair.to_biguint(), BigUint::from(2_u8));
+    }
+}
@@ -0,0 +1,10 @@
+/// Splits a value into its `u32` digits, least significant first, and the number of digits needed.
+pub(crate) fn u128_digits(value: u128) -> ([u32; 4], usize) {
+    let digits = [
+        value as u32,
+        (value >> 32) as u32,
+        (value >> 64) as u32,
+        (value >> 96) as u32,
+    ];
+    (digits, 4 - value.leading_zeros() as usize / 32)
+}
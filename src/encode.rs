@@ -0,0 +1,384 @@
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+use num::{bigint::Sign, BigInt, BigUint};
+
+/// Error returned when a value does not fit into a fixed number of bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FixedWidthOverflow {
+    /// The minimum number of bytes needed to represent the value.
+    pub required: usize,
+    /// The number of bytes that were available.
+    pub available: usize,
+}
+
+impl fmt::Display for FixedWidthOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "value needs {} bytes but only {} are available",
+            self.required, self.available
+        )
+    }
+}
+
+impl core::error::Error for FixedWidthOverflow {}
+
+/// Error returned when a [`num::BigInt`] cannot be written as a fixed width unsigned number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FixedWidthError {
+    /// The value is negative and has no unsigned representation.
+    Negative,
+    /// The value does not fit into the available bytes.
+    Overflow(FixedWidthOverflow),
+}
+
+impl fmt::Display for FixedWidthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixedWidthError::Negative => {
+                write!(f, "negative values have no unsigned representation")
+            }
+            FixedWidthError::Overflow(overflow) => overflow.fmt(f),
+        }
+    }
+}
+
+impl core::error::Error for FixedWidthError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            FixedWidthError::Negative => None,
+            FixedWidthError::Overflow(overflow) => Some(overflow),
+        }
+    }
+}
+
+impl From<FixedWidthOverflow> for FixedWidthError {
+    fn from(overflow: FixedWidthOverflow) -> Self {
+        FixedWidthError::Overflow(overflow)
+    }
+}
+
+/// Returns the number of bytes needed for the unsigned representation of `value`, which is zero for zero.
+fn unsigned_byte_len(value: &BigUint) -> usize {
+    value.bits().div_ceil(8) as usize
+}
+
+/// Splits a [`num::BigInt`] into its sign and little endian `u64` limbs without trailing zero limbs.
+///
+/// Zero yields [`Sign::NoSign`] and no limbs. Use
+/// [`bigint_from_u64_limbs_le`](crate::decode::bigint_from_u64_limbs_le) for the inverse conversion.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::encode::to_u64_limbs_le;
+/// use num::{bigint::Sign, BigInt};
+///
+/// assert_eq!(to_u64_limbs_le(&BigInt::from(-5)), (Sign::Minus, vec![5]));
+/// ```
+pub fn to_u64_limbs_le(value: &BigInt) -> (Sign, Vec<u64>) {
+    (value.sign(), value.magnitude().to_u64_digits())
+}
+
+/// Fixed width serialization of [`num::BigUint`].
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::encode::BigUintEncodeExt;
+/// use num::BigUint;
+///
+/// let bytes = BigUint::from(0x0102_u32).to_fixed_be_bytes::<4>().unwrap();
+/// assert_eq!(bytes, [0, 0, 1, 2]);
+/// ```
+pub trait BigUintEncodeExt {
+    /// Serializes the value as exactly `N` big endian bytes, left padded with zeros.
+    ///
+    /// Returns an error if the value needs more than `N` bytes.
+    fn to_fixed_be_bytes<const N: usize>(&self) -> Result<[u8; N], FixedWidthOverflow>;
+
+    /// Serializes the value as exactly `len` little endian bytes, right padded with zeros.
+    ///
+    /// Returns an error reporting the required length if the value needs more than `len` bytes.
+    fn to_le_bytes_padded(&self, len: usize) -> Result<Vec<u8>, FixedWidthOverflow>;
+
+    /// Writes the value as little endian bytes into `out`, filling the remaining bytes with zeros.
+    ///
+    /// Nothing is allocated. Returns an error reporting the required length if the value needs more than `out.len()`
+    /// bytes, in which case `out` is left untouched.
+    fn write_le_bytes_padded(&self, out: &mut [u8]) -> Result<(), FixedWidthOverflow>;
+}
+
+impl BigUintEncodeExt for BigUint {
+    fn to_fixed_be_bytes<const N: usize>(&self) -> Result<[u8; N], FixedWidthOverflow> {
+        let required = unsigned_byte_len(self);
+        if required > N {
+            return Err(FixedWidthOverflow {
+                required,
+                available: N,
+            });
+        }
+        let mut bytes = [0; N];
+        if required > 0 {
+            bytes[N - required..].copy_from_slice(&self.to_bytes_be());
+        }
+        Ok(bytes)
+    }
+
+    fn to_le_bytes_padded(&self, len: usize) -> Result<Vec<u8>, FixedWidthOverflow> {
+        let mut bytes = vec![0; len];
+        self.write_le_bytes_padded(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn write_le_bytes_padded(&self, out: &mut [u8]) -> Result<(), FixedWidthOverflow> {
+        let required = unsigned_byte_len(self);
+        if required > out.len() {
+            return Err(FixedWidthOverflow {
+                required,
+                available: out.len(),
+            });
+        }
+        let (value, padding) = out.split_at_mut(required);
+        for (chunk, digit) in value.chunks_mut(8).zip(self.iter_u64_digits()) {
+            chunk.copy_from_slice(&digit.to_le_bytes()[..chunk.len()]);
+        }
+        padding.fill(0);
+        Ok(())
+    }
+}
+
+/// Fixed width serialization of [`num::BigInt`].
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::encode::BigIntEncodeExt;
+/// use num::BigInt;
+///
+/// assert_eq!(BigInt::from(258).to_fixed_be_bytes::<3>().unwrap(), [0, 1, 2]);
+/// assert_eq!(BigInt::from(-2).to_fixed_be_bytes_twos_complement::<3>().unwrap(), [0xff, 0xff, 0xfe]);
+/// ```
+pub trait BigIntEncodeExt {
+    /// Serializes a non-negative value as exactly `N` unsigned big endian bytes, left padded with zeros.
+    ///
+    /// Returns an error if the value is negative or needs more than `N` bytes.
+    fn to_fixed_be_bytes<const N: usize>(&self) -> Result<[u8; N], FixedWidthError>;
+
+    /// Serializes the value as exactly `N` big endian bytes in two's complement, sign extended to the full width.
+    ///
+    /// Returns an error if the value lies outside of `-2^(8N-1)..2^(8N-1)`.
+    fn to_fixed_be_bytes_twos_complement<const N: usize>(
+        &self,
+    ) -> Result<[u8; N], FixedWidthOverflow>;
+}
+
+impl BigIntEncodeExt for BigInt {
+    fn to_fixed_be_bytes<const N: usize>(&self) -> Result<[u8; N], FixedWidthError> {
+        if self.sign() == Sign::Minus {
+            return Err(FixedWidthError::Negative);
+        }
+        Ok(self.magnitude().to_fixed_be_bytes()?)
+    }
+
+    fn to_fixed_be_bytes_twos_complement<const N: usize>(
+        &self,
+    ) -> Result<[u8; N], FixedWidthOverflow> {
+        if self.sign() == Sign::NoSign {
+            return Ok([0; N]);
+        }
+        let minimal = self.to_signed_bytes_be();
+        if minimal.len() > N {
+            return Err(FixedWidthOverflow {
+                required: minimal.len(),
+                available: N,
+            });
+        }
+        let fill = if self.sign() == Sign::Minus { 0xff } else { 0 };
+        let mut bytes = [fill; N];
+        bytes[N - minimal.len()..].copy_from_slice(&minimal);
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, BigUint, One, Zero};
+
+    use super::{BigIntEncodeExt, BigUintEncodeExt, FixedWidthError, FixedWidthOverflow};
+
+    /// Test left padding with zeros
+    #[test]
+    fn test_zero_padding() {
+        let bytes = BigUint::from(0xabcd_u32).to_fixed_be_bytes::<8>().unwrap();
+        assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0xab, 0xcd]);
+        assert_eq!(BigUint::zero().to_fixed_be_bytes::<4>().unwrap(), [0; 4]);
+    }
+
+    /// Test values that fit exactly
+    #[test]
+    fn test_exact_fit() {
+        let value = BigUint::one() << 255_u32;
+        let bytes = value.to_fixed_be_bytes::<32>().unwrap();
+        assert_eq!(bytes[0], 0x80);
+        assert!(bytes[1..].iter().all(|&byte| byte == 0));
+        assert_eq!(BigUint::from_bytes_be(&bytes), value);
+        let max = (BigUint::one() << 256_u32) - 1_u8;
+        assert_eq!(max.to_fixed_be_bytes::<32>().unwrap(), [0xff; 32]);
+    }
+
+    /// Test values that are one bit too large
+    #[test]
+    fn test_overflow() {
+        let value = BigUint::one() << 256_u32;
+        assert_eq!(
+            value.to_fixed_be_bytes::<32>(),
+            Err(FixedWidthOverflow {
+                required: 33,
+                available: 32
+            })
+        );
+        assert_eq!(
+            BigInt::from(value).to_fixed_be_bytes::<32>(),
+            Err(FixedWidthError::Overflow(FixedWidthOverflow {
+                required: 33,
+                available: 32
+            }))
+        );
+    }
+
+    /// Test zero width
+    #[test]
+    fn test_zero_width() {
+        assert_eq!(BigUint::zero().to_fixed_be_bytes::<0>().unwrap(), []);
+        assert_eq!(BigInt::zero().to_fixed_be_bytes::<0>().unwrap(), []);
+        assert_eq!(
+            BigInt::zero()
+                .to_fixed_be_bytes_twos_complement::<0>()
+                .unwrap(),
+            []
+        );
+        assert!(BigUint::one().to_fixed_be_bytes::<0>().is_err());
+    }
+
+    /// Test that negative values are rejected by the unsigned mode
+    #[test]
+    fn test_negative_rejected() {
+        assert_eq!(
+            BigInt::from(-1).to_fixed_be_bytes::<32>(),
+            Err(FixedWidthError::Negative)
+        );
+    }
+
+    /// Test the two's complement mode
+    #[test]
+    fn test_twos_complement() {
+        assert_eq!(
+            BigInt::from(-1)
+                .to_fixed_be_bytes_twos_complement::<32>()
+                .unwrap(),
+            [0xff; 32]
+        );
+        assert_eq!(
+            BigInt::from(i64::MIN)
+                .to_fixed_be_bytes_twos_complement::<8>()
+                .unwrap(),
+            i64::MIN.to_be_bytes()
+        );
+        assert_eq!(
+            BigInt::from(i64::MAX)
+                .to_fixed_be_bytes_twos_complement::<8>()
+                .unwrap(),
+            i64::MAX.to_be_bytes()
+        );
+        assert_eq!(
+            BigInt::from(-300)
+                .to_fixed_be_bytes_twos_complement::<16>()
+                .unwrap(),
+            (-300_i128).to_be_bytes()
+        );
+        assert_eq!(
+            BigInt::from(128).to_fixed_be_bytes_twos_complement::<1>(),
+            Err(FixedWidthOverflow {
+                required: 2,
+                available: 1
+            })
+        );
+        assert!(BigInt::from(-129)
+            .to_fixed_be_bytes_twos_complement::<1>()
+            .is_err());
+    }
+
+    /// Test right padding of little endian bytes
+    #[test]
+    fn test_le_padding() {
+        let value = BigUint::from(0x0102_0304_0506_0708_090a_u128);
+        assert_eq!(
+            value.to_le_bytes_padded(12).unwrap(),
+            [10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0]
+        );
+        let mut buffer = [0xee; 4];
+        BigUint::from(0xabcd_u32)
+            .write_le_bytes_padded(&mut buffer)
+            .unwrap();
+        assert_eq!(buffer, [0xcd, 0xab, 0, 0]);
+    }
+
+    /// Test values that fit the length exactly
+    #[test]
+    fn test_le_exact_fit() {
+        let value = BigUint::from(u128::MAX);
+        assert_eq!(value.to_le_bytes_padded(16).unwrap(), [0xff; 16]);
+        assert_eq!(
+            BigUint::from(0x0100_u32).to_le_bytes_padded(2).unwrap(),
+            [0, 1]
+        );
+    }
+
+    /// Test that an overflow reports the needed length
+    #[test]
+    fn test_le_overflow() {
+        let value = BigUint::from(u128::MAX) + 1_u8;
+        assert_eq!(
+            value.to_le_bytes_padded(16),
+            Err(FixedWidthOverflow {
+                required: 17,
+                available: 16
+            })
+        );
+        let mut buffer = [0xee; 3];
+        assert_eq!(
+            BigUint::from(u32::MAX).write_le_bytes_padded(&mut buffer),
+            Err(FixedWidthOverflow {
+                required: 4,
+                available: 3
+            })
+        );
+        assert_eq!(buffer, [0xee; 3]);
+    }
+
+    /// Test zero into a zero length buffer
+    #[test]
+    fn test_le_zero_length() {
+        assert_eq!(BigUint::zero().to_le_bytes_padded(0).unwrap(), []);
+        BigUint::zero().write_le_bytes_padded(&mut []).unwrap();
+    }
+
+    /// Test round trips through from_bytes_le
+    #[test]
+    fn test_le_round_trip() {
+        let values = [
+            BigUint::zero(),
+            BigUint::one(),
+            BigUint::from(u64::MAX),
+            BigUint::new(vec![0x1234_5678; 17]),
+            BigUint::one() << 1000_u32,
+        ];
+        for value in values {
+            for extra in [0, 1, 9] {
+                let len = value.to_bytes_le().len() + extra;
+                let bytes = value.to_le_bytes_padded(len).unwrap();
+                assert_eq!(bytes.len(), len);
+                assert_eq!(BigUint::from_bytes_le(&bytes), value);
+            }
+        }
+    }
+}
@@ -0,0 +1,1121 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{cmp::Ordering, fmt};
+
+use num::{bigint::Sign, BigInt, BigUint};
+#[cfg(feature = "rational")]
+use num::{BigRational, Integer, One, Signed, Zero};
+
+#[cfg(feature = "rational")]
+use crate::InfallibleToBigUint;
+
+/// A finite float decomposed into `(-1)^negative * mantissa * 2^exponent`.
+pub(crate) struct Decomposed {
+    pub(crate) negative: bool,
+    pub(crate) mantissa: u64,
+    pub(crate) exponent: i32,
+}
+
+/// Decomposes a finite `f64` exactly, returning `None` for NaN and infinities.
+pub(crate) fn decompose_f64(x: f64) -> Option<Decomposed> {
+    if !x.is_finite() {
+        return None;
+    }
+    let bits = x.to_bits();
+    let negative = bits >> 63 == 1;
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        // subnormal numbers have no implicit leading bit
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+    Some(Decomposed {
+        negative,
+        mantissa,
+        exponent,
+    })
+}
+
+/// Decomposes a finite `f32` exactly, returning `None` for NaN and infinities.
+pub(crate) fn decompose_f32(x: f32) -> Option<Decomposed> {
+    // every f32 is exactly representable as an f64
+    decompose_f64(f64::from(x))
+}
+
+impl Decomposed {
+    /// Returns the exact value as a rational number.
+    #[cfg(feature = "rational")]
+    pub(crate) fn to_rational(&self) -> BigRational {
+        let sign = if self.negative {
+            Sign::Minus
+        } else {
+            Sign::Plus
+        };
+        let mantissa = BigInt::from_biguint(sign, BigUint::from(self.mantissa));
+        let shift = self.exponent.unsigned_abs();
+        if self.exponent >= 0 {
+            BigRational::from_integer(mantissa << shift)
+        } else {
+            BigRational::new(mantissa, BigInt::one() << shift)
+        }
+    }
+
+    /// Returns the exact magnitude if the value is a whole number, and `None` if it has a fractional part.
+    fn whole_magnitude(&self) -> Option<BigUint> {
+        let shift = self.exponent.unsigned_abs();
+        if self.exponent >= 0 {
+            Some(BigUint::from(self.mantissa) << shift)
+        } else if self.mantissa == 0 {
+            Some(BigUint::ZERO)
+        } else if self.mantissa.trailing_zeros() >= shift {
+            // the mantissa is not zero, so the shift is below 64 here
+            Some(BigUint::from(self.mantissa >> shift))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the magnitude rounded to an integer with the given mode.
+    fn rounded_magnitude(&self, mode: RoundingMode) -> BigUint {
+        let shift = self.exponent.unsigned_abs();
+        if self.exponent >= 0 {
+            return BigUint::from(self.mantissa) << shift;
+        }
+        // the mantissa has at most 53 bits, so a shift of 64 or more leaves a fraction below one half
+        let (integer, fraction, exact) = if shift < 64 {
+            let fraction = self.mantissa & ((1 << shift) - 1);
+            (
+                self.mantissa >> shift,
+                fraction.cmp(&(1 << (shift - 1))),
+                fraction == 0,
+            )
+        } else {
+            (0, Ordering::Less, self.mantissa == 0)
+        };
+        let round_up = match mode {
+            RoundingMode::Trunc => false,
+            RoundingMode::Floor => self.negative && !exact,
+            RoundingMode::Ceil => !self.negative && !exact,
+            RoundingMode::HalfUp => fraction != Ordering::Less,
+            RoundingMode::HalfEven => {
+                fraction == Ordering::Greater || fraction == Ordering::Equal && integer % 2 == 1
+            }
+        };
+        BigUint::from(integer + u64::from(round_up))
+    }
+
+    /// Renders the exact value in plain decimal notation.
+    fn to_exact_decimal(&self) -> String {
+        let sign = if self.negative { "-" } else { "" };
+        let mantissa = BigUint::from(self.mantissa);
+        if self.exponent >= 0 {
+            return format!("{sign}{}", mantissa << self.exponent.unsigned_abs());
+        }
+        // m / 2^k equals m * 5^k / 10^k, so the digits of m * 5^k are placed k digits behind the decimal point
+        let fraction_len = self.exponent.unsigned_abs();
+        let digits = (mantissa * BigUint::from(5_u8).pow(fraction_len)).to_string();
+        let fraction_len = fraction_len as usize;
+        let digits = format!("{digits:0>width$}", width = fraction_len + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - fraction_len);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            format!("{sign}{integer}")
+        } else {
+            format!("{sign}{integer}.{fraction}")
+        }
+    }
+}
+
+#[cfg(feature = "rational")]
+/// Converts a finite `f64` into the exactly equal [`num::BigRational`].
+///
+/// Every finite float is a dyadic rational `m / 2^k`, so the conversion is lossless. Returns `None` for NaN and
+/// infinities. Both zeros convert to zero.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::float::f64_to_rational;
+/// use num::{BigInt, BigRational};
+///
+/// let expected = BigRational::new(BigInt::from(-3), BigInt::from(8));
+/// assert_eq!(f64_to_rational(-0.375), Some(expected));
+/// assert_eq!(f64_to_rational(f64::NAN), None);
+/// ```
+pub fn f64_to_rational(x: f64) -> Option<BigRational> {
+    decompose_f64(x).map(|decomposed| decomposed.to_rational())
+}
+
+#[cfg(feature = "rational")]
+/// Converts a finite `f32` into the exactly equal [`num::BigRational`].
+///
+/// See [`f64_to_rational`] for details.
+pub fn f32_to_rational(x: f32) -> Option<BigRational> {
+    decompose_f32(x).map(|decomposed| decomposed.to_rational())
+}
+
+/// Renders the exact decimal expansion of a finite `f64`.
+///
+/// Every finite float has a terminating decimal expansion, which may be hundreds of digits long. The full value is
+/// returned with a leading `-` for negative values, without exponent notation and without trailing zeros, e.g.
+/// `0.1000000000000000055511151231257827021181583404541015625` for `0.1`. Negative zero renders as `-0`, so the sign
+/// bit stays visible. Returns `None` for NaN and infinities.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::float::f64_exact_decimal;
+///
+/// assert_eq!(f64_exact_decimal(0.1).unwrap(), "0.1000000000000000055511151231257827021181583404541015625");
+/// assert_eq!(f64_exact_decimal(-2.5).unwrap(), "-2.5");
+/// ```
+pub fn f64_exact_decimal(x: f64) -> Option<String> {
+    decompose_f64(x).map(|decomposed| decomposed.to_exact_decimal())
+}
+
+/// Renders the exact decimal expansion of a finite `f32`.
+///
+/// See [`f64_exact_decimal`] for details.
+pub fn f32_exact_decimal(x: f32) -> Option<String> {
+    decompose_f32(x).map(|decomposed| decomposed.to_exact_decimal())
+}
+
+#[cfg(feature = "rational")]
+/// Finds the best rational approximation of `x` whose denominator is at most `max_den`.
+///
+/// The continued fraction convergents (and semiconvergents) of the exact value of `x` are searched, so the result is
+/// the closest fraction with a denominator up to `max_den`. If the exact value already has a small enough
+/// denominator, it is returned unchanged. When two candidates are equally close, the one from the last full
+/// convergent wins.
+///
+/// Returns `None` for NaN, infinities and a `max_den` of zero.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::float::approximate_rational;
+/// use num::{BigInt, BigRational};
+///
+/// let approximation = approximate_rational(std::f64::consts::PI, 1000_u32).unwrap();
+/// assert_eq!(approximation, BigRational::new(BigInt::from(355), BigInt::from(113)));
+/// ```
+pub fn approximate_rational(x: f64, max_den: impl InfallibleToBigUint) -> Option<BigRational> {
+    let max_den = BigInt::from(max_den.to_biguint());
+    if max_den.is_zero() {
+        return None;
+    }
+    let exact = f64_to_rational(x)?;
+    if exact.denom() <= &max_den {
+        return Some(exact);
+    }
+
+    // convergents p0/q0 and p1/q1 of the continued fraction expansion
+    let (mut p0, mut q0, mut p1, mut q1) =
+        (BigInt::zero(), BigInt::one(), BigInt::one(), BigInt::zero());
+    let (mut n, mut d) = (exact.numer().clone(), exact.denom().clone());
+    loop {
+        let (a, r) = n.div_mod_floor(&d);
+        let q2 = &q0 + &a * &q1;
+        if q2 > max_den {
+            break;
+        }
+        let p2 = &p0 + &a * &p1;
+        (p0, q0, p1, q1) = (p1, q1, p2, q2);
+        (n, d) = (d, r);
+    }
+
+    // the best semiconvergent between p0/q0 and p1/q1 that still respects the bound
+    let k = (&max_den - &q0).div_floor(&q1);
+    let semiconvergent = BigRational::new(&p0 + &k * &p1, &q0 + &k * &q1);
+    let convergent = BigRational::new(p1, q1);
+    if (&convergent - &exact).abs() <= (&semiconvergent - &exact).abs() {
+        Some(convergent)
+    } else {
+        Some(semiconvergent)
+    }
+}
+
+/// Error returned when a float does not convert exactly to an integer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FloatToBigIntError {
+    /// The float is NaN.
+    NaN,
+    /// The float is positive or negative infinity.
+    Infinite,
+    /// The float is finite but not a whole number.
+    Fractional {
+        /// The float, widened to `f64`.
+        value: f64,
+    },
+    /// The float is a negative whole number, which has no unsigned representation.
+    Negative,
+}
+
+impl fmt::Display for FloatToBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FloatToBigIntError::NaN => write!(f, "NaN is not an integer"),
+            FloatToBigIntError::Infinite => write!(f, "infinity is not an integer"),
+            FloatToBigIntError::Fractional { value } => {
+                write!(f, "{value} is not a whole number")
+            }
+            FloatToBigIntError::Negative => {
+                write!(f, "negative values have no unsigned representation")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FloatToBigIntError {}
+
+/// Exact conversion of a float to a [`num::BigInt`], which fails for NaN, infinities and fractional values.
+///
+/// Whole floats convert exactly however large they are, so `2f64.powi(100)` and `f64::MAX` succeed. Negative zero
+/// converts to zero.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::float::{FloatToBigIntError, TryToBigInt};
+/// use num::BigInt;
+///
+/// assert_eq!(2f64.powi(100).try_to_bigint(), Ok(BigInt::from(1) << 100_u32));
+/// assert_eq!(0.5_f64.try_to_bigint(), Err(FloatToBigIntError::Fractional { value: 0.5 }));
+/// ```
+pub trait TryToBigInt {
+    fn try_to_bigint(&self) -> Result<BigInt, FloatToBigIntError>;
+}
+
+/// Exact conversion of a float to a [`num::BigUint`], which also fails for negative values.
+///
+/// See [`TryToBigInt`] for details. A negative fractional value reports [`FloatToBigIntError::Fractional`], and
+/// negative zero converts to zero.
+pub trait TryToBigUint {
+    fn try_to_biguint(&self) -> Result<BigUint, FloatToBigIntError>;
+}
+
+/// Decomposes a float for the exact conversions, rejecting non-finite and fractional values.
+fn whole_float(
+    decomposed: Option<Decomposed>,
+    x: f64,
+) -> Result<(bool, BigUint), FloatToBigIntError> {
+    let decomposed = match decomposed {
+        Some(decomposed) => decomposed,
+        None if x.is_nan() => return Err(FloatToBigIntError::NaN),
+        None => return Err(FloatToBigIntError::Infinite),
+    };
+    match decomposed.whole_magnitude() {
+        Some(magnitude) => Ok((decomposed.negative, magnitude)),
+        None => Err(FloatToBigIntError::Fractional { value: x }),
+    }
+}
+
+macro_rules! impl_try_to_bigint {
+    ($($float:ty => $decompose:ident),*) => {$(
+        impl TryToBigInt for $float {
+            fn try_to_bigint(&self) -> Result<BigInt, FloatToBigIntError> {
+                let (negative, magnitude) = whole_float($decompose(*self), f64::from(*self))?;
+                let sign = if negative { Sign::Minus } else { Sign::Plus };
+                Ok(BigInt::from_biguint(sign, magnitude))
+            }
+        }
+
+        impl TryToBigUint for $float {
+            fn try_to_biguint(&self) -> Result<BigUint, FloatToBigIntError> {
+                match whole_float($decompose(*self), f64::from(*self))? {
+                    (true, magnitude) if magnitude != BigUint::ZERO => Err(FloatToBigIntError::Negative),
+                    (_, magnitude) => Ok(magnitude),
+                }
+            }
+        }
+    )*};
+}
+
+impl_try_to_bigint!(f32 => decompose_f32, f64 => decompose_f64);
+
+/// How a float with a fractional part is rounded to an integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Rounds towards negative infinity.
+    Floor,
+    /// Rounds towards positive infinity.
+    Ceil,
+    /// Rounds towards zero.
+    Trunc,
+    /// Rounds to the nearest integer, and ties away from zero, like [`f64::round`].
+    HalfUp,
+    /// Rounds to the nearest integer, and ties to the even one, like IEEE 754 and [`f64::round_ties_even`].
+    HalfEven,
+}
+
+/// Error returned when a NaN or infinite float is converted to an integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NonFiniteError {
+    /// The float is NaN.
+    NaN,
+    /// The float is positive or negative infinity.
+    Infinite,
+}
+
+impl fmt::Display for NonFiniteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NonFiniteError::NaN => write!(f, "NaN is not an integer"),
+            NonFiniteError::Infinite => write!(f, "infinity is not an integer"),
+        }
+    }
+}
+
+impl core::error::Error for NonFiniteError {}
+
+impl From<NonFiniteError> for FloatToBigIntError {
+    fn from(error: NonFiniteError) -> Self {
+        match error {
+            NonFiniteError::NaN => FloatToBigIntError::NaN,
+            NonFiniteError::Infinite => FloatToBigIntError::Infinite,
+        }
+    }
+}
+
+/// Conversion of a float to a [`num::BigInt`], rounding away the fractional part with a [`RoundingMode`].
+///
+/// Every finite float converts, and values beyond the `u128` range are whole numbers that convert exactly. Only NaN
+/// and infinities fail.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::float::{FloatToBigIntRounded, RoundingMode};
+/// use num::BigInt;
+///
+/// assert_eq!(2.5_f64.to_bigint_rounded(RoundingMode::HalfEven), Ok(BigInt::from(2)));
+/// assert_eq!((-2.5_f64).to_bigint_rounded(RoundingMode::HalfUp), Ok(BigInt::from(-3)));
+/// assert_eq!(2f64.powi(200).to_bigint_rounded(RoundingMode::Floor), Ok(BigInt::from(1) << 200_u32));
+/// ```
+pub trait FloatToBigIntRounded {
+    fn to_bigint_rounded(&self, mode: RoundingMode) -> Result<BigInt, NonFiniteError>;
+}
+
+macro_rules! impl_float_to_bigint_rounded {
+    ($($float:ty => $decompose:ident),*) => {$(
+        impl FloatToBigIntRounded for $float {
+            fn to_bigint_rounded(&self, mode: RoundingMode) -> Result<BigInt, NonFiniteError> {
+                let decomposed = match $decompose(*self) {
+                    Some(decomposed) => decomposed,
+                    None if self.is_nan() => return Err(NonFiniteError::NaN),
+                    None => return Err(NonFiniteError::Infinite),
+                };
+                let sign = if decomposed.negative { Sign::Minus } else { Sign::Plus };
+                Ok(BigInt::from_biguint(sign, decomposed.rounded_magnitude(mode)))
+            }
+        }
+    )*};
+}
+
+impl_float_to_bigint_rounded!(f32 => decompose_f32, f64 => decompose_f64);
+
+/// Rounds a magnitude to the nearest `f64`, ties to even, and reports whether the result is exact.
+///
+/// The top 64 bits are converted with a sticky bit for everything below them, which makes the hardware conversion
+/// round exactly like a conversion of the full value would.
+fn magnitude_to_f64(value: &BigUint) -> (f64, bool) {
+    let bits = value.bits();
+    if bits <= 64 {
+        let small = value.iter_u64_digits().next().unwrap_or(0);
+        return (
+            small as f64,
+            bits - value.trailing_zeros().unwrap_or(0) <= 53,
+        );
+    }
+    if bits > 1024 {
+        return (f64::INFINITY, false);
+    }
+    let shift = bits - 64;
+    let top = (value >> shift).iter_u64_digits().next().unwrap_or(0);
+    let lowest_set = value.trailing_zeros().unwrap_or(0);
+    let sticky = u64::from(lowest_set < shift);
+    // shift is at most 960 here, so 2^shift is a normal float
+    let scale = f64::from_bits((1023 + shift) << 52);
+    let rounded = (top | sticky) as f64 * scale;
+    (rounded, rounded.is_finite() && bits - lowest_set <= 53)
+}
+
+/// Conversions from [`num::BigUint`] to floating point numbers.
+pub trait BigUintFloatExt {
+    /// Converts to the nearest `f64`, rounding ties to even, and returns whether the conversion was exact.
+    ///
+    /// The flag is `true` iff the float converts back to exactly the same value. Values beyond the `f64` range become
+    /// infinity with the flag `false`.
+    fn to_f64_with_exactness(&self) -> (f64, bool);
+}
+
+impl BigUintFloatExt for BigUint {
+    fn to_f64_with_exactness(&self) -> (f64, bool) {
+        magnitude_to_f64(self)
+    }
+}
+
+/// Conversions from [`num::BigInt`] to floating point numbers.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::float::BigIntFloatExt;
+/// use num::BigInt;
+///
+/// assert_eq!(BigInt::from(1_u64 << 53).to_f64_with_exactness(), (9007199254740992.0, true));
+/// assert_eq!(BigInt::from((1_u64 << 53) + 1).to_f64_with_exactness(), (9007199254740992.0, false));
+/// ```
+pub trait BigIntFloatExt {
+    /// Converts to the nearest `f64`, rounding ties to even, and returns whether the conversion was exact.
+    ///
+    /// The flag is `true` iff the float converts back to exactly the same value. Values beyond the `f64` range become
+    /// positive or negative infinity with the flag `false`.
+    fn to_f64_with_exactness(&self) -> (f64, bool);
+
+    /// Converts to the nearest `f64` like [`BigIntFloatExt::to_f64_with_exactness`] and returns the exact rounding
+    /// error `self - rounded` as a rational number.
+    ///
+    /// If the value is beyond the `f64` range the float is infinite and the error is instead measured against the
+    /// largest finite float of the same sign, `self - f64::MAX` or `self + f64::MAX`, so it is never zero there.
+    ///
+    /// # Example
+    /// ```
+    /// use infallible_tobigint::float::BigIntFloatExt;
+    /// use num::{BigInt, BigRational};
+    ///
+    /// let (rounded, error) = BigInt::from((1_u64 << 53) + 1).to_f64_with_error();
+    /// assert_eq!(rounded, 9007199254740992.0);
+    /// assert_eq!(error, BigRational::from_integer(BigInt::from(1)));
+    /// ```
+    #[cfg(feature = "rational")]
+    fn to_f64_with_error(&self) -> (f64, BigRational);
+}
+
+impl BigIntFloatExt for BigInt {
+    fn to_f64_with_exactness(&self) -> (f64, bool) {
+        let (magnitude, exact) = magnitude_to_f64(self.magnitude());
+        if self.sign() == Sign::Minus {
+            (-magnitude, exact)
+        } else {
+            (magnitude, exact)
+        }
+    }
+
+    #[cfg(feature = "rational")]
+    fn to_f64_with_error(&self) -> (f64, BigRational) {
+        let (rounded, _) = self.to_f64_with_exactness();
+        let reference = if rounded.is_finite() {
+            rounded
+        } else {
+            f64::MAX.copysign(rounded)
+        };
+        let reference = f64_to_rational(reference).unwrap_or_else(BigRational::zero);
+        (rounded, BigRational::from_integer(self.clone()) - reference)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{bigint::Sign, BigInt, BigUint, Zero};
+    #[cfg(feature = "rational")]
+    use num::{BigRational, Signed};
+
+    #[cfg(feature = "rational")]
+    use super::{approximate_rational, f32_to_rational, f64_to_rational};
+    use super::{
+        f32_exact_decimal, f64_exact_decimal, BigIntFloatExt, BigUintFloatExt, FloatToBigIntError,
+        FloatToBigIntRounded, NonFiniteError, RoundingMode, TryToBigInt, TryToBigUint,
+    };
+
+    #[cfg(feature = "rational")]
+    fn ratio(numer: i64, denom: i64) -> BigRational {
+        BigRational::new(BigInt::from(numer), BigInt::from(denom))
+    }
+
+    /// Test the exact conversion against Ratio::from_float
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_exact_conversion() {
+        let values = [
+            0.0,
+            -0.0,
+            0.1,
+            -2.5,
+            1e300,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            5e-324,
+        ];
+        for x in values {
+            assert_eq!(f64_to_rational(x), BigRational::from_float(x));
+        }
+        assert_eq!(f32_to_rational(0.1), BigRational::from_float(0.1_f32));
+        assert_eq!(f64_to_rational(f64::INFINITY), None);
+        assert_eq!(f32_to_rational(f32::NAN), None);
+    }
+
+    /// Test that NaN and infinities have no approximation
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_approximate_non_finite() {
+        assert_eq!(approximate_rational(f64::NAN, 10_u8), None);
+        assert_eq!(approximate_rational(f64::INFINITY, 10_u8), None);
+        assert_eq!(approximate_rational(f64::NEG_INFINITY, 10_u8), None);
+        assert_eq!(approximate_rational(1.5, 0_u8), None);
+    }
+
+    /// Test pi with a denominator up to 1000
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_approximate_pi() {
+        let pi = std::f64::consts::PI;
+        assert_eq!(approximate_rational(pi, 1000_u32), Some(ratio(355, 113)));
+        assert_eq!(approximate_rational(pi, 10_u32), Some(ratio(22, 7)));
+        assert_eq!(approximate_rational(pi, 1_u32), Some(ratio(3, 1)));
+    }
+
+    /// Test that exact values are returned unchanged when their denominator fits
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_approximate_exact() {
+        assert_eq!(approximate_rational(0.75, 4_u8), Some(ratio(3, 4)));
+        assert_eq!(approximate_rational(-0.375, 8_u8), Some(ratio(-3, 8)));
+        assert_eq!(approximate_rational(0.0, 1_u8), Some(BigRational::zero()));
+        assert_eq!(approximate_rational(1e20, 1_u8), f64_to_rational(1e20));
+    }
+
+    /// Test negative inputs
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_approximate_negative() {
+        let pi = std::f64::consts::PI;
+        assert_eq!(approximate_rational(-pi, 1000_u32), Some(ratio(-355, 113)));
+        assert_eq!(approximate_rational(-0.1, 10_u8), Some(ratio(-1, 10)));
+    }
+
+    /// Test 0.5 with a maximum denominator of 1
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_approximate_half() {
+        assert_eq!(approximate_rational(0.5, 1_u8), Some(BigRational::zero()));
+        assert_eq!(approximate_rational(0.5, 2_u8), Some(ratio(1, 2)));
+    }
+
+    /// Test that the error never grows as the bound grows
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_approximate_monotonic_error() {
+        for x in [
+            std::f64::consts::E,
+            -std::f64::consts::SQRT_2,
+            0.1,
+            1234.5678,
+        ] {
+            let exact = f64_to_rational(x).unwrap();
+            let mut last_error = None;
+            for max_den in 1..2000_u32 {
+                let approximation = approximate_rational(x, max_den).unwrap();
+                assert!(approximation.denom() <= &BigInt::from(max_den));
+                let error = (approximation - &exact).abs();
+                if let Some(last_error) = &last_error {
+                    assert!(&error <= last_error);
+                }
+                last_error = Some(error);
+            }
+        }
+    }
+
+    /// Parses a plain decimal string into an exact rational
+    #[cfg(feature = "rational")]
+    fn parse_decimal(decimal: &str) -> BigRational {
+        let (integer, fraction) = decimal.split_once('.').unwrap_or((decimal, ""));
+        let numer: BigInt = format!("{integer}{fraction}").parse().unwrap();
+        let denom = num::pow(BigInt::from(10), fraction.len());
+        BigRational::new(numer, denom)
+    }
+
+    /// Test the exact expansion of 0.1
+    #[test]
+    fn test_exact_decimal_tenth() {
+        assert_eq!(
+            f64_exact_decimal(0.1).unwrap(),
+            "0.1000000000000000055511151231257827021181583404541015625"
+        );
+        assert_eq!(
+            f32_exact_decimal(0.1).unwrap(),
+            "0.100000001490116119384765625"
+        );
+    }
+
+    /// Test the smallest subnormal 2^-1074
+    #[test]
+    fn test_exact_decimal_subnormal() {
+        let decimal = f64_exact_decimal(5e-324).unwrap();
+        assert!(decimal.starts_with("0.000"));
+        assert!(decimal[2..]
+            .trim_start_matches('0')
+            .starts_with("49406564584124654417656879286822137236505980261432476442558568250067"));
+        assert_eq!(decimal.len() - 2, 1074);
+        assert!(decimal.ends_with('5'));
+    }
+
+    /// Test that negative zero keeps its sign
+    #[test]
+    fn test_exact_decimal_zero() {
+        assert_eq!(f64_exact_decimal(0.0).unwrap(), "0");
+        assert_eq!(f64_exact_decimal(-0.0).unwrap(), "-0");
+        assert_eq!(f32_exact_decimal(-0.0).unwrap(), "-0");
+    }
+
+    /// Test whole numbers like 1e308
+    #[test]
+    fn test_exact_decimal_whole() {
+        let decimal = f64_exact_decimal(1e308).unwrap();
+        assert_eq!(decimal.len(), 309);
+        assert!(decimal
+            .starts_with("1000000000000000010979063629440455417404923096773118463368106829031"));
+        assert_eq!(f64_exact_decimal(-1024.0).unwrap(), "-1024");
+        assert_eq!(f64_exact_decimal(f64::NAN), None);
+        assert_eq!(f32_exact_decimal(f32::INFINITY), None);
+    }
+
+    /// Test round trips through a BigRational parse
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_exact_decimal_round_trip() {
+        let values = [
+            0.1,
+            -0.3,
+            1.0 / 3.0,
+            123456.789,
+            1e308,
+            -1e-300,
+            5e-324,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+        ];
+        for x in values {
+            let decimal = f64_exact_decimal(x).unwrap();
+            assert_eq!(Some(parse_decimal(&decimal)), f64_to_rational(x));
+        }
+        for x in [0.1_f32, -7.25, f32::MAX, f32::MIN_POSITIVE, 1e-45] {
+            let decimal = f32_exact_decimal(x).unwrap();
+            assert_eq!(Some(parse_decimal(&decimal)), f32_to_rational(x));
+        }
+    }
+
+    /// Test exactly representable powers of two, up to the largest finite one
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_exactness_powers_of_two() {
+        for exponent in [0_u32, 1, 52, 53, 64, 65, 100, 1000, 1023] {
+            let value = BigInt::from(1_u8) << exponent;
+            assert_eq!(
+                value.to_f64_with_exactness(),
+                (2_f64.powi(exponent as i32), true)
+            );
+        }
+        let max = f64_to_rational(f64::MAX).unwrap().to_integer();
+        assert_eq!(max.to_f64_with_exactness(), (f64::MAX, true));
+    }
+
+    /// Test 2^53 + 1, the first integer without an exact f64, and round half to even beyond it
+    #[test]
+    fn test_exactness_rounding() {
+        let two_53 = BigInt::from(1_u64 << 53);
+        assert_eq!(
+            (&two_53 + 1_u8).to_f64_with_exactness(),
+            (9007199254740992.0, false)
+        );
+        assert_eq!(
+            (&two_53 + 2_u8).to_f64_with_exactness(),
+            (9007199254740994.0, true)
+        );
+        assert_eq!(
+            (&two_53 + 3_u8).to_f64_with_exactness(),
+            (9007199254740996.0, false)
+        );
+        // ties far below the top 64 bits have to be resolved by the sticky bit
+        let tie = (BigInt::from(1_u8) << 200_u32) + (BigInt::from(1_u8) << 147_u32);
+        assert_eq!(tie.to_f64_with_exactness(), (2_f64.powi(200), false));
+        let above_tie = &tie + 1_u8;
+        assert_eq!(
+            above_tie.to_f64_with_exactness(),
+            (2_f64.powi(200) + 2_f64.powi(148), false)
+        );
+        let odd_tie = (BigInt::from(3_u8) << 199_u32) + (BigInt::from(1_u8) << 146_u32);
+        assert_eq!(
+            odd_tie.to_f64_with_exactness(),
+            (1.5 * 2_f64.powi(200) + 2_f64.powi(147), false)
+        );
+    }
+
+    /// Test negative values
+    #[test]
+    fn test_exactness_negative() {
+        assert_eq!(BigInt::from(-3).to_f64_with_exactness(), (-3.0, true));
+        assert_eq!(
+            BigInt::from(i64::MIN).to_f64_with_exactness(),
+            (i64::MIN as f64, true)
+        );
+        assert_eq!(
+            BigInt::from(i64::MIN + 1).to_f64_with_exactness(),
+            (i64::MIN as f64, false)
+        );
+        assert_eq!(
+            BigInt::from(i128::MIN).to_f64_with_exactness(),
+            (i128::MIN as f64, true)
+        );
+    }
+
+    /// Test values overflowing to infinity
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_exactness_overflow() {
+        let max = f64_to_rational(f64::MAX).unwrap().to_integer();
+        // the halfway point between f64::MAX and 2^1024 rounds to even, which is infinity
+        let halfway = &max + (BigInt::from(1_u8) << 970_u32);
+        assert_eq!(halfway.to_f64_with_exactness(), (f64::INFINITY, false));
+        assert_eq!((&halfway - 1_u8).to_f64_with_exactness(), (f64::MAX, false));
+        let huge = BigInt::from(1_u8) << 5000_u32;
+        assert_eq!(huge.to_f64_with_exactness(), (f64::INFINITY, false));
+        assert_eq!((-&huge).to_f64_with_exactness(), (f64::NEG_INFINITY, false));
+        assert_eq!(
+            huge.magnitude().to_f64_with_exactness(),
+            (f64::INFINITY, false)
+        );
+    }
+
+    /// Test zero and agreement with primitive conversions
+    #[test]
+    fn test_exactness_primitives() {
+        assert_eq!(BigInt::zero().to_f64_with_exactness(), (0.0, true));
+        assert_eq!(BigUint::zero().to_f64_with_exactness(), (0.0, true));
+        for value in [
+            1_u128,
+            12345,
+            u64::MAX as u128,
+            u128::MAX,
+            0xdead_beef << 90,
+            1 << 127 | 1,
+        ] {
+            let (converted, exact) = BigUint::from(value).to_f64_with_exactness();
+            assert_eq!(converted, value as f64);
+            assert_eq!(
+                exact,
+                128 - value.leading_zeros() - value.trailing_zeros() <= 53
+            );
+        }
+    }
+
+    /// Test that representable values have no error
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_error_exact() {
+        for value in [
+            BigInt::zero(),
+            BigInt::from(-1),
+            BigInt::from(1_u64 << 53),
+            BigInt::from(i128::MIN),
+        ] {
+            let (rounded, error) = value.to_f64_with_error();
+            assert_eq!(
+                BigRational::from_integer(value),
+                f64_to_rational(rounded).unwrap()
+            );
+            assert!(error.is_zero());
+        }
+    }
+
+    /// Test that the error is at most half an ULP for pseudo random 200 bit values
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_error_half_ulp() {
+        let mut state = 0x853c_49e6_748f_ea9b_u64;
+        for i in 0..500_u32 {
+            let mut value = BigInt::zero();
+            for _ in 0..4 {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                value = (value << 64_u32) + state;
+            }
+            let value = value >> (56 + i % 150);
+            let value = if i % 2 == 0 { value } else { -value };
+            let (rounded, error) = value.to_f64_with_error();
+            let ulp = f64::from_bits(rounded.abs().to_bits() + 1) - rounded.abs();
+            let half_ulp = f64_to_rational(ulp).unwrap() / BigInt::from(2);
+            assert!(error.abs() <= half_ulp, "{value}");
+            assert_eq!(
+                f64_to_rational(rounded).unwrap() + error,
+                BigRational::from_integer(value)
+            );
+        }
+    }
+
+    /// Test that the error has the sign of value - rounded
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_error_sign() {
+        let two_53 = BigInt::from(1_u64 << 53);
+        let one = BigRational::from_integer(BigInt::from(1));
+        assert_eq!((&two_53 + 1_u8).to_f64_with_error().1, one);
+        assert_eq!((-(&two_53 + 1_u8)).to_f64_with_error().1, -one.clone());
+        // 2^53 + 3 rounds up to 2^53 + 4
+        assert_eq!((&two_53 + 3_u8).to_f64_with_error().1, -one.clone());
+        assert_eq!((-(&two_53 + 3_u8)).to_f64_with_error().1, one);
+    }
+
+    /// Test that the error is measured against f64::MAX beyond the f64 range
+    #[cfg(feature = "rational")]
+    #[test]
+    fn test_error_saturation() {
+        let max = f64_to_rational(f64::MAX).unwrap();
+        let huge = BigInt::from(1_u8) << 1100_u32;
+        let (rounded, error) = huge.to_f64_with_error();
+        assert_eq!(rounded, f64::INFINITY);
+        assert_eq!(error, BigRational::from_integer(huge.clone()) - &max);
+        let (rounded, error) = (-&huge).to_f64_with_error();
+        assert_eq!(rounded, f64::NEG_INFINITY);
+        assert_eq!(error, -BigRational::from_integer(huge) + max);
+    }
+
+    /// Test that NaN and infinities are rejected
+    #[test]
+    fn test_try_non_finite() {
+        assert_eq!(f64::NAN.try_to_bigint(), Err(FloatToBigIntError::NaN));
+        assert_eq!(f32::NAN.try_to_biguint(), Err(FloatToBigIntError::NaN));
+        assert_eq!(
+            f64::INFINITY.try_to_bigint(),
+            Err(FloatToBigIntError::Infinite)
+        );
+        assert_eq!(
+            f64::NEG_INFINITY.try_to_bigint(),
+            Err(FloatToBigIntError::Infinite)
+        );
+        assert_eq!(
+            f32::NEG_INFINITY.try_to_biguint(),
+            Err(FloatToBigIntError::Infinite)
+        );
+    }
+
+    /// Test that fractional values, including subnormals, are rejected with the value
+    #[test]
+    fn test_try_fractional() {
+        assert_eq!(
+            0.5_f64.try_to_bigint(),
+            Err(FloatToBigIntError::Fractional { value: 0.5 })
+        );
+        assert_eq!(
+            (-2.5_f32).try_to_bigint(),
+            Err(FloatToBigIntError::Fractional { value: -2.5 })
+        );
+        assert_eq!(
+            (-0.5_f64).try_to_biguint(),
+            Err(FloatToBigIntError::Fractional { value: -0.5 })
+        );
+        for subnormal in [5e-324, f64::MIN_POSITIVE / 2.0, -5e-324] {
+            assert_eq!(
+                subnormal.try_to_bigint(),
+                Err(FloatToBigIntError::Fractional { value: subnormal })
+            );
+        }
+        let subnormal = f32::from_bits(1);
+        assert_eq!(
+            subnormal.try_to_bigint(),
+            Err(FloatToBigIntError::Fractional {
+                value: f64::from(subnormal)
+            })
+        );
+        assert_eq!(
+            (1.0_f64 + f64::EPSILON).try_to_bigint(),
+            Err(FloatToBigIntError::Fractional {
+                value: 1.0 + f64::EPSILON
+            })
+        );
+    }
+
+    /// Test that negative whole values are rejected for unsigned targets, except negative zero
+    #[test]
+    fn test_try_negative() {
+        assert_eq!(
+            (-3.0_f64).try_to_biguint(),
+            Err(FloatToBigIntError::Negative)
+        );
+        assert_eq!(f32::MIN.try_to_biguint(), Err(FloatToBigIntError::Negative));
+        assert_eq!((-3.0_f64).try_to_bigint(), Ok(BigInt::from(-3)));
+        assert_eq!((-0.0_f64).try_to_biguint(), Ok(BigUint::zero()));
+        assert_eq!((-0.0_f32).try_to_bigint(), Ok(BigInt::zero()));
+        assert_eq!(0.0_f64.try_to_bigint(), Ok(BigInt::zero()));
+    }
+
+    /// Test that large whole values convert exactly
+    #[test]
+    fn test_try_large() {
+        assert_eq!(
+            2f64.powi(100).try_to_bigint(),
+            Ok(BigInt::from(1) << 100_u32)
+        );
+        assert_eq!(
+            2f64.powi(100).try_to_biguint(),
+            Ok(BigUint::from(1_u8) << 100_u32)
+        );
+        let max = (BigUint::from(1_u8) << 53_u32) - 1_u8;
+        assert_eq!(f64::MAX.try_to_biguint(), Ok(max.clone() << 971_u32));
+        assert_eq!(f64::MIN.try_to_bigint(), Ok(-BigInt::from(max << 971_u32)));
+        let max = (BigUint::from(1_u8) << 24_u32) - 1_u8;
+        assert_eq!(f32::MAX.try_to_biguint(), Ok(max << 104_u32));
+        assert_eq!(
+            f64::MAX.try_to_bigint().unwrap().to_string(),
+            f64_exact_decimal(f64::MAX).unwrap()
+        );
+        assert_eq!(
+            (-(2f64.powi(52))).try_to_bigint(),
+            Ok(-(BigInt::from(1) << 52_u32))
+        );
+    }
+
+    /// Test the error messages
+    #[test]
+    fn test_try_display() {
+        assert_eq!(FloatToBigIntError::NaN.to_string(), "NaN is not an integer");
+        assert_eq!(
+            FloatToBigIntError::Infinite.to_string(),
+            "infinity is not an integer"
+        );
+        assert_eq!(
+            FloatToBigIntError::Fractional { value: 0.5 }.to_string(),
+            "0.5 is not a whole number"
+        );
+        assert_eq!(
+            FloatToBigIntError::Negative.to_string(),
+            "negative values have no unsigned representation"
+        );
+    }
+
+    /// Test every rounding mode against the rounding functions of f64, converted exactly
+    #[test]
+    fn test_rounded_matches_std() {
+        let modes = [
+            (RoundingMode::Floor, f64::floor as fn(f64) -> f64),
+            (RoundingMode::Ceil, f64::ceil),
+            (RoundingMode::Trunc, f64::trunc),
+            (RoundingMode::HalfUp, f64::round),
+            (RoundingMode::HalfEven, f64::round_ties_even),
+        ];
+        let mut values = vec![
+            0.0,
+            -0.0,
+            5e-324,
+            -5e-324,
+            0.1,
+            0.49999999999999994,
+            1e300,
+            -1e300,
+            f64::MAX,
+        ];
+        values.extend((-40..=40).map(|i| f64::from(i) / 4.0));
+        values.extend([
+            2f64.powi(52) + 0.5,
+            -(2f64.powi(52) + 0.5),
+            2f64.powi(53) - 1.0,
+        ]);
+        for x in values {
+            for (mode, round) in modes {
+                let expected = round(x).try_to_bigint().ok();
+                assert_eq!(x.to_bigint_rounded(mode).ok(), expected, "{x} {mode:?}");
+            }
+        }
+    }
+
+    /// Test that ties are rounded to even like in IEEE 754
+    #[test]
+    fn test_rounded_half_even() {
+        let round = |x: f64| x.to_bigint_rounded(RoundingMode::HalfEven);
+        assert_eq!(round(0.5), Ok(BigInt::from(0)));
+        assert_eq!(round(1.5), Ok(BigInt::from(2)));
+        assert_eq!(round(2.5), Ok(BigInt::from(2)));
+        assert_eq!(round(3.5), Ok(BigInt::from(4)));
+        assert_eq!(round(2.5000000000000004), Ok(BigInt::from(3)));
+    }
+
+    /// Test negative halves with every mode
+    #[test]
+    fn test_rounded_negative_halves() {
+        let cases = [
+            (-0.5, [-1, 0, 0, -1, 0]),
+            (-1.5, [-2, -1, -1, -2, -2]),
+            (-2.5, [-3, -2, -2, -3, -2]),
+            (-3.5, [-4, -3, -3, -4, -4]),
+        ];
+        let modes = [
+            RoundingMode::Floor,
+            RoundingMode::Ceil,
+            RoundingMode::Trunc,
+            RoundingMode::HalfUp,
+            RoundingMode::HalfEven,
+        ];
+        for (x, expected) in cases {
+            for (mode, expected) in modes.into_iter().zip(expected) {
+                assert_eq!(
+                    x.to_bigint_rounded(mode),
+                    Ok(BigInt::from(expected)),
+                    "{x} {mode:?}"
+                );
+                assert_eq!(
+                    (x as f32).to_bigint_rounded(mode),
+                    Ok(BigInt::from(expected)),
+                    "{x} {mode:?}"
+                );
+            }
+        }
+        assert_eq!(
+            (-0.5_f64)
+                .to_bigint_rounded(RoundingMode::Ceil)
+                .unwrap()
+                .sign(),
+            Sign::NoSign
+        );
+    }
+
+    /// Test that values beyond the u128 range convert exactly with every mode
+    #[test]
+    fn test_rounded_large() {
+        let expected: BigInt = ((BigInt::from(1) << 53_u32) - 1) << 971_u32;
+        for mode in [
+            RoundingMode::Floor,
+            RoundingMode::Ceil,
+            RoundingMode::Trunc,
+            RoundingMode::HalfUp,
+            RoundingMode::HalfEven,
+        ] {
+            assert_eq!(f64::MAX.to_bigint_rounded(mode), Ok(expected.clone()));
+            assert_eq!(f64::MIN.to_bigint_rounded(mode), Ok(-expected.clone()));
+            assert_eq!(
+                2f32.powi(127).to_bigint_rounded(mode),
+                Ok(BigInt::from(1) << 127_u32)
+            );
+        }
+    }
+
+    /// Test that NaN and infinities are rejected
+    #[test]
+    fn test_rounded_non_finite() {
+        assert_eq!(
+            f64::NAN.to_bigint_rounded(RoundingMode::Floor),
+            Err(NonFiniteError::NaN)
+        );
+        assert_eq!(
+            f32::INFINITY.to_bigint_rounded(RoundingMode::Ceil),
+            Err(NonFiniteError::Infinite)
+        );
+        assert_eq!(
+            f64::NEG_INFINITY.to_bigint_rounded(RoundingMode::Trunc),
+            Err(NonFiniteError::Infinite)
+        );
+        assert_eq!(NonFiniteError::NaN.to_string(), "NaN is not an integer");
+        assert_eq!(
+            FloatToBigIntError::from(NonFiniteError::Infinite),
+            FloatToBigIntError::Infinite
+        );
+    }
+}
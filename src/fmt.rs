@@ -0,0 +1,268 @@
+use core::fmt;
+
+use num::{bigint::Sign, BigInt};
+
+/// Display wrapper for a [`num::BigInt`] that honors every integer formatting flag.
+///
+/// The fill character, alignment and width are applied to the whole number, `+` prints a sign for non-negative
+/// values and `0` pads with zeros between the sign and the digits, exactly like formatting a primitive integer.
+/// Precision has no meaning for integers and is ignored. A width smaller than the number never truncates it.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::fmt::BigIntFormatExt;
+/// use num::BigInt;
+///
+/// let value = BigInt::from(-42);
+/// assert_eq!(format!("{:08}", value.padded()), "-0000042");
+/// assert_eq!(format!("{:*^9}", value.padded()), "***-42***");
+/// assert_eq!(format!("{:+}", BigInt::from(42).padded()), "+42");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Padded<'a>(pub &'a BigInt);
+
+impl fmt::Display for Padded<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.0.magnitude().to_str_radix(10);
+        f.pad_integral(self.0.sign() != Sign::Minus, "", &digits)
+    }
+}
+
+/// Formatting wrapper for a [`num::BigInt`] in base 2, 8 or 16.
+///
+/// `Radix` implements [`fmt::Binary`], [`fmt::Octal`], [`fmt::LowerHex`] and [`fmt::UpperHex`] with full support for
+/// fill, alignment, width and the `+`, `0` and `#` flags. Negative values are written as a sign followed by the
+/// magnitude, e.g. `-0x2a` under `{:#x}`, never in two's complement. [`fmt::Display`] uses the radix it was
+/// constructed with, see [`BigIntFormatExt::hex`], [`BigIntFormatExt::octal`] and [`BigIntFormatExt::binary`].
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::fmt::BigIntFormatExt;
+/// use num::BigInt;
+///
+/// let value = BigInt::from(-42);
+/// assert_eq!(format!("{:#x}", value.hex()), "-0x2a");
+/// assert_eq!(format!("{:#010b}", BigInt::from(5).binary()), "0b00000101");
+/// assert_eq!(format!("{:#}", value.octal()), "-0o52");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Radix<'a> {
+    value: &'a BigInt,
+    radix: u32,
+}
+
+impl Radix<'_> {
+    /// Formats the value in `radix` with the given alternate form prefix and letter case.
+    fn pad(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        radix: u32,
+        prefix: &str,
+        upper: bool,
+    ) -> fmt::Result {
+        let mut digits = self.value.magnitude().to_str_radix(radix);
+        if upper {
+            digits.make_ascii_uppercase();
+        }
+        f.pad_integral(self.value.sign() != Sign::Minus, prefix, &digits)
+    }
+}
+
+impl fmt::Binary for Radix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pad(f, 2, "0b", false)
+    }
+}
+
+impl fmt::Octal for Radix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pad(f, 8, "0o", false)
+    }
+}
+
+impl fmt::LowerHex for Radix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pad(f, 16, "0x", false)
+    }
+}
+
+impl fmt::UpperHex for Radix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pad(f, 16, "0x", true)
+    }
+}
+
+impl fmt::Display for Radix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.radix {
+            2 => fmt::Binary::fmt(self, f),
+            8 => fmt::Octal::fmt(self, f),
+            _ => fmt::LowerHex::fmt(self, f),
+        }
+    }
+}
+
+/// Extension methods for formatting [`num::BigInt`] values.
+pub trait BigIntFormatExt {
+    /// Wraps the value in a [`Padded`] to format it with width, fill, alignment and sign flags.
+    fn padded(&self) -> Padded<'_>;
+
+    /// Wraps the value in a [`Radix`] whose [`fmt::Display`] output is lowercase hexadecimal.
+    fn hex(&self) -> Radix<'_>;
+
+    /// Wraps the value in a [`Radix`] whose [`fmt::Display`] output is octal.
+    fn octal(&self) -> Radix<'_>;
+
+    /// Wraps the value in a [`Radix`] whose [`fmt::Display`] output is binary.
+    fn binary(&self) -> Radix<'_>;
+}
+
+impl BigIntFormatExt for BigInt {
+    fn padded(&self) -> Padded<'_> {
+        Padded(self)
+    }
+
+    fn hex(&self) -> Radix<'_> {
+        Radix {
+            value: self,
+            radix: 16,
+        }
+    }
+
+    fn octal(&self) -> Radix<'_> {
+        Radix {
+            value: self,
+            radix: 8,
+        }
+    }
+
+    fn binary(&self) -> Radix<'_> {
+        Radix {
+            value: self,
+            radix: 2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, Num};
+
+    use super::BigIntFormatExt;
+
+    /// Test zero padding after the sign
+    #[test]
+    fn test_zero_padding() {
+        let value = BigInt::from(-42);
+        assert_eq!(format!("{:08}", value.padded()), "-0000042");
+        assert_eq!(format!("{:+08}", BigInt::from(42).padded()), "+0000042");
+        assert_eq!(format!("{:08}", BigInt::from(0).padded()), "00000000");
+        let large = BigInt::from(u128::MAX) * BigInt::from(-1000);
+        assert_eq!(
+            format!("{:045}", large.padded()),
+            format!("-00{}000", u128::MAX)
+        );
+    }
+
+    /// Test right, left and center alignment with custom fill characters
+    #[test]
+    fn test_alignment() {
+        let value = BigInt::from(-42);
+        assert_eq!(format!("{:>6}", value.padded()), "   -42");
+        assert_eq!(format!("{:<6}", value.padded()), "-42   ");
+        assert_eq!(format!("{:^7}", value.padded()), "  -42  ");
+        assert_eq!(format!("{:_^8}", value.padded()), "__-42___");
+        assert_eq!(format!("{:6}", value.padded()), "   -42");
+    }
+
+    /// Test that a width smaller than the number does not truncate it
+    #[test]
+    fn test_narrow_width() {
+        let value = BigInt::from(i128::MIN);
+        assert_eq!(format!("{:3}", value.padded()), i128::MIN.to_string());
+        assert_eq!(format!("{:03}", value.padded()), i128::MIN.to_string());
+    }
+
+    /// Test the plus sign on positive, zero and negative values
+    #[test]
+    fn test_plus_sign() {
+        assert_eq!(format!("{:+}", BigInt::from(42).padded()), "+42");
+        assert_eq!(format!("{:+}", BigInt::from(0).padded()), "+0");
+        assert_eq!(format!("{:+}", BigInt::from(-42).padded()), "-42");
+        assert_eq!(format!("{:>+6}", BigInt::from(42).padded()), "   +42");
+    }
+
+    /// Test agreement with primitive integer formatting
+    #[test]
+    fn test_matches_primitives() {
+        for value in [i64::MIN, -1234, -1, 0, 1, 1234, i64::MAX] {
+            let big = BigInt::from(value);
+            assert_eq!(format!("{:+025}", big.padded()), format!("{value:+025}"));
+            assert_eq!(format!("{:#<25}", big.padded()), format!("{value:#<25}"));
+            assert_eq!(format!("{:^+25}", big.padded()), format!("{value:^+25}"));
+        }
+    }
+
+    /// Test the alternate form of negative values
+    #[test]
+    fn test_radix_negative() {
+        let value = BigInt::from(-255);
+        assert_eq!(format!("{:#x}", value.hex()), "-0xff");
+        assert_eq!(format!("{:#X}", value.hex()), "-0xFF");
+        assert_eq!(format!("{:#o}", value.octal()), "-0o377");
+        assert_eq!(format!("{:#b}", value.binary()), "-0b11111111");
+        assert_eq!(format!("{:#010x}", value.hex()), "-0x00000ff");
+        assert_eq!(format!("{:>#8x}", value.hex()), "   -0xff");
+    }
+
+    /// Test zero with the alternate form and zero padding
+    #[test]
+    fn test_radix_zero() {
+        let zero = BigInt::from(0);
+        assert_eq!(format!("{:#010b}", zero.binary()), "0b00000000");
+        assert_eq!(format!("{:#x}", zero.hex()), "0x0");
+        assert_eq!(format!("{:+#x}", zero.hex()), "+0x0");
+    }
+
+    /// Test uppercase hexadecimal beyond u128
+    #[test]
+    fn test_radix_upper_hex() {
+        let value = BigInt::from(u128::MAX) * BigInt::from(0xab);
+        assert_eq!(
+            format!("{:X}", value.hex()),
+            "AAFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF55"
+        );
+        assert_eq!(
+            format!("{:#X}", value.hex()),
+            "0xAAFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF55"
+        );
+    }
+
+    /// Test that the bare forms agree with to_str_radix and the Display output uses the chosen radix
+    #[test]
+    fn test_radix_bare() {
+        let value = BigInt::from_str_radix("-123456789abcdef0123456789abcdef0123", 16).unwrap();
+        assert_eq!(format!("{:x}", value.hex()), value.to_str_radix(16));
+        assert_eq!(format!("{:o}", value.hex()), value.to_str_radix(8));
+        assert_eq!(format!("{:b}", value.hex()), value.to_str_radix(2));
+        assert_eq!(value.hex().to_string(), value.to_str_radix(16));
+        assert_eq!(value.octal().to_string(), value.to_str_radix(8));
+        assert_eq!(value.binary().to_string(), value.to_str_radix(2));
+        assert_eq!(
+            format!("{:#}", value.hex()),
+            format!("-0x{}", &value.to_str_radix(16)[1..])
+        );
+    }
+
+    /// Test agreement with primitive integer formatting for non-negative values
+    #[test]
+    fn test_radix_matches_primitives() {
+        for value in [0_u64, 1, 42, 0xdead_beef, u64::MAX] {
+            let big = BigInt::from(value);
+            assert_eq!(format!("{:#024x}", big.hex()), format!("{value:#024x}"));
+            assert_eq!(format!("{:^#30o}", big.hex()), format!("{value:^#30o}"));
+            assert_eq!(format!("{:+#X}", big.hex()), format!("{value:+#X}"));
+            assert_eq!(format!("{:_<70b}", big.hex()), format!("{value:_<70b}"));
+        }
+    }
+}
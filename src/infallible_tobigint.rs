@@ -1,4 +1,19 @@
-use num::{bigint::ToBigInt, BigInt};
+use alloc::borrow::ToOwned;
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, sync::Arc};
+use core::{
+    cell::{Cell, RefCell},
+    cmp::Ordering,
+    num::{NonZero, Saturating, Wrapping},
+};
+#[cfg(feature = "std")]
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::Duration,
+};
+
+use num::{bigint::Sign, BigInt, BigUint};
+
+use crate::digits::u128_digits;
 
 /// Allows for type conversion to [`num::BigInt`] without worrying about Results.
 ///
@@ -6,6 +21,21 @@ use num::{bigint::ToBigInt, BigInt};
 /// convertible to `BigInt`. Only `f32` and `f64` cannot be converted if they are not whole numbers. This traits limits
 /// its implementation to those safe types mentioned. So no error checking is needed.
 ///
+/// `bool` converts to `0` or `1`, `char` to its Unicode scalar value and [`std::cmp::Ordering`] to `-1`, `0` or `1`.
+/// With the `std` feature, IP addresses convert to their big-endian numeric value, and an IPv4 address in an
+/// [`IpAddr`](std::net::IpAddr) to its 32 bit value rather than the IPv4-mapped IPv6 address. A
+/// [`Duration`](std::time::Duration) converts to its exact number of nanoseconds.
+///
+/// [`num::BigInt`] and [`num::BigUint`] implement it too, so generic functions accept existing bignums. They are cloned
+/// by `to_bigint`; use [`ToBigIntCow`] to borrow a `BigInt` instead.
+///
+/// The implementations have no panicking branch either: they use `From` conversions, without going through an `Option`
+/// that would have to be unwrapped.
+///
+/// The one exception is [`std::cell::RefCell`], which is read with [`RefCell::borrow`] and so panics if the value is
+/// mutably borrowed, rather than converting a value that is in the middle of an update. [`std::cell::Cell`] is read
+/// with `get`.
+///
 /// # Example
 /// ```
 /// use infallible_tobigint::infallible_tobigint::*;
@@ -24,97 +54,533 @@ use num::{bigint::ToBigInt, BigInt};
 /// ```
 pub trait InfallibleToBigInt {
     fn to_bigint(&self) -> BigInt;
+
+    /// Returns the value as an `i128` if that is possible without allocating.
+    ///
+    /// Accumulators like [`crate::iter::sum_as_bigint`] use this to add primitives in `i128` and only spill into a
+    /// `BigInt` on overflow. The default returns `None`, which always takes the `BigInt` path.
+    fn to_i128_fast(&self) -> Option<i128> {
+        None
+    }
+
+    /// Converts the value by moving it, which [`InfallibleIntoBigInt`] uses to take bignums without copying them.
+    #[doc(hidden)]
+    fn into_bigint_by_value(self) -> BigInt
+    where
+        Self: Sized,
+    {
+        self.to_bigint()
+    }
+}
+
+/// Consuming conversion to [`num::BigInt`], for values that can be given up.
+///
+/// Every [`InfallibleToBigInt`] type implements it by converting the value, so iterator pipelines can use
+/// `map(InfallibleIntoBigInt::into_bigint)` by value. A [`num::BigUint`] is moved into the result without copying its
+/// digits.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::InfallibleIntoBigInt;
+/// use num::{BigInt, BigUint};
+///
+/// let values: Vec<BigInt> = vec![1_u64, u64::MAX].into_iter().map(InfallibleIntoBigInt::into_bigint).collect();
+/// assert_eq!(values, [BigInt::from(1), BigInt::from(u64::MAX)]);
+/// assert_eq!(BigUint::from(7_u8).into_bigint(), BigInt::from(7));
+/// ```
+pub trait InfallibleIntoBigInt {
+    fn into_bigint(self) -> BigInt;
+}
+
+impl<T: InfallibleToBigInt> InfallibleIntoBigInt for T {
+    fn into_bigint(self) -> BigInt {
+        self.into_bigint_by_value()
+    }
+}
+
+/// Conversion to a [`num::BigInt`] that borrows the value if it already is one.
+///
+/// [`InfallibleToBigInt::to_bigint`] always returns an owned value, which clones a `BigInt`. Here the primitives and
+/// [`num::BigUint`] return [`Cow::Owned`], while a `BigInt` returns [`Cow::Borrowed`] itself.
+///
+/// # Example
+/// ```
+/// use std::borrow::Cow;
+///
+/// use infallible_tobigint::ToBigIntCow;
+/// use num::BigInt;
+///
+/// let big = BigInt::from(u128::MAX) << 64_u32;
+/// assert!(matches!(big.to_bigint_cow(), Cow::Borrowed(_)));
+/// assert_eq!(7_u8.to_bigint_cow(), Cow::<BigInt>::Owned(BigInt::from(7)));
+/// ```
+pub trait ToBigIntCow {
+    fn to_bigint_cow(&self) -> Cow<'_, BigInt>;
+}
+
+macro_rules! impl_to_bigint_cow_owned {
+    ($($value:ty),*) => {$(
+        impl ToBigIntCow for $value {
+            fn to_bigint_cow(&self) -> Cow<'_, BigInt> {
+                Cow::Owned(self.to_bigint())
+            }
+        }
+    )*};
+}
+
+impl_to_bigint_cow_owned!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, BigUint);
+
+impl ToBigIntCow for BigInt {
+    fn to_bigint_cow(&self) -> Cow<'_, BigInt> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<T: ToBigIntCow + ?Sized> ToBigIntCow for &T {
+    fn to_bigint_cow(&self) -> Cow<'_, BigInt> {
+        (**self).to_bigint_cow()
+    }
+}
+
+/// Conversion into an existing [`num::BigInt`] that reuses its digit buffer.
+///
+/// `to_bigint` allocates a new `BigInt` on every call. `to_bigint_into` overwrites the target instead and keeps its
+/// allocation, unless the buffer is more than four times longer than the new value needs, in which case num shrinks
+/// it. The result is always equal to a fresh conversion, whatever sign the target had before.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::ToBigIntAssign;
+/// use num::BigInt;
+///
+/// let mut scratch = BigInt::from(-5);
+/// for value in [u64::MAX, 3] {
+///     value.to_bigint_into(&mut scratch);
+///     scratch *= 2;
+/// }
+/// assert_eq!(scratch, BigInt::from(6));
+/// ```
+pub trait ToBigIntAssign {
+    fn to_bigint_into(&self, target: &mut BigInt);
+}
+
+macro_rules! impl_to_bigint_assign_signed {
+    ($($value:ty),*) => {$(
+        impl ToBigIntAssign for $value {
+            fn to_bigint_into(&self, target: &mut BigInt) {
+                let sign = if *self < 0 { Sign::Minus } else { Sign::Plus };
+                assign_u128(target, sign, self.unsigned_abs() as u128);
+            }
+        }
+    )*};
+}
+
+impl_to_bigint_assign_signed!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_to_bigint_assign_unsigned {
+    ($($value:ty),*) => {$(
+        impl ToBigIntAssign for $value {
+            fn to_bigint_into(&self, target: &mut BigInt) {
+                assign_u128(target, Sign::Plus, *self as u128);
+            }
+        }
+    )*};
+}
+
+impl_to_bigint_assign_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl<T: ToBigIntAssign + ?Sized> ToBigIntAssign for &T {
+    fn to_bigint_into(&self, target: &mut BigInt) {
+        (**self).to_bigint_into(target)
+    }
+}
+
+/// Overwrites a [`num::BigInt`] with a sign and magnitude, keeping its digit buffer.
+fn assign_u128(target: &mut BigInt, sign: Sign, magnitude: u128) {
+    let (digits, len) = u128_digits(magnitude);
+    // NoSign only clears the digits, while an empty slice with a sign would also shrink the buffer
+    let sign = if magnitude == 0 { Sign::NoSign } else { sign };
+    target.assign_from_slice(sign, &digits[..len]);
+}
+
+/// Conversion of an optional value to an optional [`num::BigInt`].
+///
+/// Only the outer layer is converted, so an `Option<Option<u8>>` has to be flattened first. The value is converted
+/// by value, so a `BigUint` or `BigInt` inside is moved rather than copied.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::OptionToBigInt;
+/// use num::BigInt;
+///
+/// assert_eq!(Some(u128::MAX).to_bigint(), Some(BigInt::from(u128::MAX)));
+/// assert_eq!(None::<i8>.to_bigint(), None);
+/// assert_eq!(Some(Some(5_u8)).flatten().to_bigint(), Some(BigInt::from(5)));
+/// ```
+pub trait OptionToBigInt {
+    fn to_bigint(self) -> Option<BigInt>;
+}
+
+impl<T: InfallibleToBigInt> OptionToBigInt for Option<T> {
+    fn to_bigint(self) -> Option<BigInt> {
+        self.map(InfallibleIntoBigInt::into_bigint)
+    }
+}
+
+/// Conversion of the success value of a result to a [`num::BigInt`], passing the error through unchanged.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::ResultToBigInt;
+/// use num::BigInt;
+///
+/// assert_eq!("-17".parse::<i64>().to_bigint(), Ok(BigInt::from(-17)));
+/// assert!("x".parse::<i64>().to_bigint().is_err());
+/// ```
+pub trait ResultToBigInt<E> {
+    fn to_bigint(self) -> Result<BigInt, E>;
+}
+
+impl<T: InfallibleToBigInt, E> ResultToBigInt<E> for Result<T, E> {
+    fn to_bigint(self) -> Result<BigInt, E> {
+        self.map(InfallibleIntoBigInt::into_bigint)
+    }
 }
 
 impl InfallibleToBigInt for i8 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for i8, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self))
     }
 }
 
 impl InfallibleToBigInt for i16 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for i16, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self))
     }
 }
 
 impl InfallibleToBigInt for i32 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for i32, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self))
     }
 }
 
 impl InfallibleToBigInt for i64 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for i64, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self))
     }
 }
 
 impl InfallibleToBigInt for i128 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for i128, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(*self)
     }
 }
 
 impl InfallibleToBigInt for isize {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for isize, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        i128::try_from(*self).ok()
+    }
+}
+
+impl InfallibleToBigInt for BigInt {
+    fn to_bigint(&self) -> BigInt {
+        self.clone()
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        i128::try_from(self).ok()
+    }
+
+    fn into_bigint_by_value(self) -> BigInt {
+        self
     }
 }
 
 impl InfallibleToBigInt for u8 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for u8, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self))
     }
 }
 
 impl InfallibleToBigInt for u16 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for u16, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self))
     }
 }
 
 impl InfallibleToBigInt for u32 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for u32, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self))
     }
 }
 
 impl InfallibleToBigInt for u64 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for u64, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self))
     }
 }
 
 impl InfallibleToBigInt for u128 {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for u128, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        i128::try_from(*self).ok()
     }
 }
 
 impl InfallibleToBigInt for usize {
     fn to_bigint(&self) -> BigInt {
-        ToBigInt::to_bigint(self)
-            .expect("to_bigint failed for usize, this should not happen and is most likely a programming error")
+        BigInt::from(*self)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        i128::try_from(*self).ok()
+    }
+}
+
+impl InfallibleToBigInt for Ordering {
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from(*self as i8)
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self as i8))
+    }
+}
+
+impl InfallibleToBigInt for bool {
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from(u8::from(*self))
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(*self))
+    }
+}
+
+impl InfallibleToBigInt for char {
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from(u32::from(*self))
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(u32::from(*self)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl InfallibleToBigInt for Ipv4Addr {
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from(u32::from(*self))
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        Some(i128::from(u32::from(*self)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl InfallibleToBigInt for Ipv6Addr {
+    fn to_bigint(&self) -> BigInt {
+        u128::from(*self).to_bigint()
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        i128::try_from(u128::from(*self)).ok()
+    }
+}
+
+/// Converts an IPv4 address as its 32 bit value, without mapping it into the IPv6 space.
+#[cfg(feature = "std")]
+impl InfallibleToBigInt for IpAddr {
+    fn to_bigint(&self) -> BigInt {
+        match self {
+            IpAddr::V4(address) => address.to_bigint(),
+            IpAddr::V6(address) => address.to_bigint(),
+        }
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        match self {
+            IpAddr::V4(address) => address.to_i128_fast(),
+            IpAddr::V6(address) => address.to_i128_fast(),
+        }
+    }
+}
+
+/// Converts the total number of nanoseconds, which fits into a `u128` even for `Duration::MAX`.
+#[cfg(feature = "std")]
+impl InfallibleToBigInt for Duration {
+    fn to_bigint(&self) -> BigInt {
+        self.as_nanos().to_bigint()
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        i128::try_from(self.as_nanos()).ok()
+    }
+}
+
+/// Converts the non-zero integers through the value they hold.
+macro_rules! impl_to_bigint_nonzero {
+    ($($value:ty),*) => {$(
+        impl InfallibleToBigInt for NonZero<$value> {
+            fn to_bigint(&self) -> BigInt {
+                self.get().to_bigint()
+            }
+
+            fn to_i128_fast(&self) -> Option<i128> {
+                self.get().to_i128_fast()
+            }
+        }
+    )*};
+}
+
+impl_to_bigint_nonzero!(i8, i16, i32, i64, i128, isize);
+
+impl_to_bigint_nonzero!(u8, u16, u32, u64, u128, usize);
+
+/// Converts the arithmetic wrappers through the value they hold.
+macro_rules! impl_to_bigint_wrapper {
+    ($($wrapper:ident),*) => {$(
+        impl<T: InfallibleToBigInt> InfallibleToBigInt for $wrapper<T> {
+            fn to_bigint(&self) -> BigInt {
+                self.0.to_bigint()
+            }
+
+            fn to_i128_fast(&self) -> Option<i128> {
+                self.0.to_i128_fast()
+            }
+        }
+    )*};
+}
+
+impl_to_bigint_wrapper!(Wrapping, Saturating);
+
+/// Converts the value in a cell, reading a `RefCell` with `borrow`.
+impl<T: Copy + InfallibleToBigInt> InfallibleToBigInt for Cell<T> {
+    fn to_bigint(&self) -> BigInt {
+        self.get().to_bigint()
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        self.get().to_i128_fast()
+    }
+}
+
+impl<T: InfallibleToBigInt + ?Sized> InfallibleToBigInt for RefCell<T> {
+    fn to_bigint(&self) -> BigInt {
+        self.borrow().to_bigint()
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        self.borrow().to_i128_fast()
+    }
+}
+
+impl InfallibleToBigInt for BigUint {
+    fn to_bigint(&self) -> BigInt {
+        BigInt::from(self.clone())
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        i128::try_from(self).ok()
+    }
+
+    fn into_bigint_by_value(self) -> BigInt {
+        BigInt::from(self)
+    }
+}
+
+/// Forwards the conversion through references and smart pointers to the value they point to.
+macro_rules! impl_to_bigint_deref {
+    ($($pointer:ty),*) => {$(
+        impl<T: InfallibleToBigInt + ?Sized> InfallibleToBigInt for $pointer {
+            fn to_bigint(&self) -> BigInt {
+                (**self).to_bigint()
+            }
+
+            fn to_i128_fast(&self) -> Option<i128> {
+                (**self).to_i128_fast()
+            }
+        }
+    )*};
+}
+
+impl_to_bigint_deref!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+impl<T: InfallibleToBigInt + ToOwned + ?Sized> InfallibleToBigInt for Cow<'_, T> {
+    fn to_bigint(&self) -> BigInt {
+        (**self).to_bigint()
+    }
+
+    fn to_i128_fast(&self) -> Option<i128> {
+        (**self).to_i128_fast()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use num::bigint::ToBigInt;
+    use std::{
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        cmp::Ordering,
+        num::{NonZero, Saturating, Wrapping},
+        rc::Rc,
+        sync::Arc,
+    };
 
-    use super::InfallibleToBigInt;
+    use num::{
+        bigint::{Sign, ToBigInt},
+        BigInt, BigUint,
+    };
+
+    use super::{
+        InfallibleIntoBigInt, InfallibleToBigInt, OptionToBigInt, ResultToBigInt, ToBigIntAssign,
+        ToBigIntCow,
+    };
 
     /// Test MIN and MAX values of u8
     #[test]
@@ -271,4 +737,690 @@ mod tests {
             ToBigInt::to_bigint(&isize::MAX).unwrap()
         );
     }
+
+    /// Test the i128 fast path of all types, which is missing only above i128::MAX
+    #[test]
+    fn test_to_i128_fast() {
+        assert_eq!(i8::MIN.to_i128_fast(), Some(-128));
+        assert_eq!(i128::MIN.to_i128_fast(), Some(i128::MIN));
+        assert_eq!(isize::MAX.to_i128_fast(), Some(isize::MAX as i128));
+        assert_eq!(u64::MAX.to_i128_fast(), Some(u64::MAX as i128));
+        assert_eq!(usize::MAX.to_i128_fast(), Some(usize::MAX as i128));
+        assert_eq!((i128::MAX as u128).to_i128_fast(), Some(i128::MAX));
+        assert_eq!((i128::MAX as u128 + 1).to_i128_fast(), None);
+    }
+
+    /// Test the From conversions against num's ToBigInt around every digit boundary, including i128::MIN
+    #[test]
+    fn test_digit_boundaries() {
+        let boundaries = [
+            0,
+            1,
+            u32::MAX as i128 - 1,
+            u32::MAX as i128,
+            u32::MAX as i128 + 1,
+            u64::MAX as i128 - 1,
+            u64::MAX as i128,
+            u64::MAX as i128 + 1,
+            i128::MAX,
+        ];
+        for value in boundaries {
+            for value in [value, -value] {
+                assert_eq!(
+                    InfallibleToBigInt::to_bigint(&value),
+                    ToBigInt::to_bigint(&value).unwrap()
+                );
+            }
+            let unsigned = value as u128;
+            assert_eq!(
+                InfallibleToBigInt::to_bigint(&unsigned),
+                ToBigInt::to_bigint(&unsigned).unwrap()
+            );
+        }
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&i128::MIN),
+            -(BigInt::from(1_u8) << 127_u32)
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&u128::MAX),
+            ToBigInt::to_bigint(&u128::MAX).unwrap()
+        );
+        for value in [i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX] {
+            assert_eq!(
+                InfallibleToBigInt::to_bigint(&value),
+                ToBigInt::to_bigint(&value).unwrap()
+            );
+        }
+    }
+
+    /// Test MIN, 1 and MAX values of NonZero<i8>
+    #[test]
+    fn test_nonzero_i8() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i8>::MIN),
+            ToBigInt::to_bigint(&NonZero::<i8>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i8>::new(1).unwrap()),
+            ToBigInt::to_bigint(&NonZero::<i8>::new(1).unwrap().get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i8>::MAX),
+            ToBigInt::to_bigint(&NonZero::<i8>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN, 1 and MAX values of NonZero<i16>
+    #[test]
+    fn test_nonzero_i16() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i16>::MIN),
+            ToBigInt::to_bigint(&NonZero::<i16>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i16>::new(1).unwrap()),
+            ToBigInt::to_bigint(&NonZero::<i16>::new(1).unwrap().get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i16>::MAX),
+            ToBigInt::to_bigint(&NonZero::<i16>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN, 1 and MAX values of NonZero<i32>
+    #[test]
+    fn test_nonzero_i32() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i32>::MIN),
+            ToBigInt::to_bigint(&NonZero::<i32>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i32>::new(1).unwrap()),
+            ToBigInt::to_bigint(&NonZero::<i32>::new(1).unwrap().get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i32>::MAX),
+            ToBigInt::to_bigint(&NonZero::<i32>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN, 1 and MAX values of NonZero<i64>
+    #[test]
+    fn test_nonzero_i64() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i64>::MIN),
+            ToBigInt::to_bigint(&NonZero::<i64>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i64>::new(1).unwrap()),
+            ToBigInt::to_bigint(&NonZero::<i64>::new(1).unwrap().get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i64>::MAX),
+            ToBigInt::to_bigint(&NonZero::<i64>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN, 1 and MAX values of NonZero<i128>
+    #[test]
+    fn test_nonzero_i128() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i128>::MIN),
+            ToBigInt::to_bigint(&NonZero::<i128>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i128>::new(1).unwrap()),
+            ToBigInt::to_bigint(&NonZero::<i128>::new(1).unwrap().get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<i128>::MAX),
+            ToBigInt::to_bigint(&NonZero::<i128>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN, 1 and MAX values of NonZero<isize>
+    #[test]
+    fn test_nonzero_isize() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<isize>::MIN),
+            ToBigInt::to_bigint(&NonZero::<isize>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<isize>::new(1).unwrap()),
+            ToBigInt::to_bigint(&NonZero::<isize>::new(1).unwrap().get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<isize>::MAX),
+            ToBigInt::to_bigint(&NonZero::<isize>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<u8>
+    #[test]
+    fn test_nonzero_u8() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u8>::MIN),
+            ToBigInt::to_bigint(&NonZero::<u8>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u8>::MAX),
+            ToBigInt::to_bigint(&NonZero::<u8>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<u16>
+    #[test]
+    fn test_nonzero_u16() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u16>::MIN),
+            ToBigInt::to_bigint(&NonZero::<u16>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u16>::MAX),
+            ToBigInt::to_bigint(&NonZero::<u16>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<u32>
+    #[test]
+    fn test_nonzero_u32() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u32>::MIN),
+            ToBigInt::to_bigint(&NonZero::<u32>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u32>::MAX),
+            ToBigInt::to_bigint(&NonZero::<u32>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<u64>
+    #[test]
+    fn test_nonzero_u64() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u64>::MIN),
+            ToBigInt::to_bigint(&NonZero::<u64>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u64>::MAX),
+            ToBigInt::to_bigint(&NonZero::<u64>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<u128>
+    #[test]
+    fn test_nonzero_u128() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u128>::MIN),
+            ToBigInt::to_bigint(&NonZero::<u128>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<u128>::MAX),
+            ToBigInt::to_bigint(&NonZero::<u128>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<usize>
+    #[test]
+    fn test_nonzero_usize() {
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<usize>::MIN),
+            ToBigInt::to_bigint(&NonZero::<usize>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigInt::to_bigint(&NonZero::<usize>::MAX),
+            ToBigInt::to_bigint(&NonZero::<usize>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test that non-zero integers keep the i128 fast path of the value they hold
+    #[test]
+    fn test_nonzero_fast_path() {
+        assert_eq!(NonZero::<i128>::MIN.to_i128_fast(), Some(i128::MIN));
+        assert_eq!(NonZero::<u64>::MAX.to_i128_fast(), Some(u64::MAX.into()));
+        assert_eq!(NonZero::<u128>::MAX.to_i128_fast(), None);
+        let reference: &NonZero<i32> = &NonZero::<i32>::MAX;
+        assert_eq!(reference.to_bigint(), BigInt::from(i32::MAX));
+    }
+
+    /// Test that the arithmetic wrappers convert exactly like the value they hold
+    #[test]
+    fn test_wrappers() {
+        fn check<T: InfallibleToBigInt + Copy>(values: [T; 2]) {
+            for value in values {
+                assert_eq!(Wrapping(value).to_bigint(), value.to_bigint());
+                assert_eq!(Saturating(value).to_bigint(), value.to_bigint());
+                assert_eq!(Wrapping(value).to_i128_fast(), value.to_i128_fast());
+                assert_eq!(Saturating(value).to_i128_fast(), value.to_i128_fast());
+            }
+        }
+        assert_eq!(Wrapping(i64::MIN).to_bigint(), BigInt::from(i64::MIN));
+        assert_eq!(Saturating(u128::MAX).to_bigint(), BigInt::from(u128::MAX));
+        assert_eq!(Saturating(u128::MAX).to_i128_fast(), None);
+        check([i8::MIN, i8::MAX]);
+        check([i16::MIN, i16::MAX]);
+        check([i32::MIN, i32::MAX]);
+        check([i64::MIN, i64::MAX]);
+        check([i128::MIN, i128::MAX]);
+        check([isize::MIN, isize::MAX]);
+        check([u8::MIN, u8::MAX]);
+        check([u16::MIN, u16::MAX]);
+        check([u32::MIN, u32::MAX]);
+        check([u64::MIN, u64::MAX]);
+        check([u128::MIN, u128::MAX]);
+        check([usize::MIN, usize::MAX]);
+    }
+
+    /// Test bool as 0 and 1
+    #[test]
+    fn test_bool() {
+        assert_eq!(false.to_bigint(), BigInt::from(0));
+        assert_eq!(true.to_bigint(), BigInt::from(1));
+        assert_eq!(true.to_i128_fast(), Some(1));
+    }
+
+    /// Test char as its scalar value at the ends of the range and around the surrogates
+    #[test]
+    fn test_char() {
+        for c in [
+            '\0',
+            '\u{d7ff}',
+            '\u{e000}',
+            '\u{ffff}',
+            '\u{10000}',
+            char::MAX,
+        ] {
+            assert_eq!(c.to_bigint(), BigInt::from(c as u32));
+            assert_eq!(c.to_i128_fast(), Some(i128::from(c as u32)));
+        }
+        assert_eq!(char::MAX.to_bigint(), BigInt::from(0x10ffff));
+    }
+
+    /// Test that IP addresses convert to their numeric value, with IPv4 addresses as 32 bit values
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ip_addr() {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+        assert_eq!(Ipv4Addr::UNSPECIFIED.to_bigint(), BigInt::from(0));
+        assert_eq!(Ipv4Addr::BROADCAST.to_bigint(), BigInt::from(u32::MAX));
+        assert_eq!(Ipv6Addr::UNSPECIFIED.to_bigint(), BigInt::from(0));
+        let max = Ipv6Addr::from(u128::MAX);
+        assert_eq!(max.to_bigint(), BigInt::from(u128::MAX));
+        assert_eq!(max.to_i128_fast(), None);
+        let v4 = IpAddr::from([10, 0, 0, 1]);
+        assert_eq!(v4.to_bigint(), BigInt::from(0x0a00_0001));
+        assert_eq!(v4.to_i128_fast(), Some(0x0a00_0001));
+        assert_eq!(IpAddr::V6(max).to_bigint(), BigInt::from(u128::MAX));
+    }
+
+    /// Test durations as exact nanoseconds
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_duration() {
+        use std::time::Duration;
+
+        assert_eq!(Duration::ZERO.to_bigint(), BigInt::from(0));
+        assert_eq!(Duration::from_millis(1).to_i128_fast(), Some(1_000_000));
+        let max = BigInt::from(u64::MAX) * 1_000_000_000 + 999_999_999;
+        assert_eq!(Duration::MAX.to_bigint(), max);
+        assert_eq!(
+            Duration::MAX.to_i128_fast(),
+            i128::try_from(Duration::MAX.as_nanos()).ok()
+        );
+    }
+
+    /// Test Ordering as -1, 0 and 1
+    #[test]
+    fn test_ordering() {
+        let less = Ordering::Less.to_bigint();
+        assert_eq!(less, BigInt::from(-1));
+        assert_eq!(less.sign(), Sign::Minus);
+        assert_eq!(Ordering::Equal.to_bigint().sign(), Sign::NoSign);
+        assert_eq!(Ordering::Greater.to_bigint(), BigInt::from(1));
+        assert_eq!(Ordering::Less.to_i128_fast(), Some(-1));
+        let reference: &Ordering = &Ordering::Less;
+        assert_eq!(reference.to_bigint(), BigInt::from(-1));
+    }
+
+    /// Test the wrappers around a downstream newtype and a bignum, which the generic impls cover
+    #[test]
+    fn test_wrappers_generic() {
+        struct Ticks(i64);
+
+        impl InfallibleToBigInt for Ticks {
+            fn to_bigint(&self) -> BigInt {
+                BigInt::from(self.0)
+            }
+
+            fn to_i128_fast(&self) -> Option<i128> {
+                Some(i128::from(self.0))
+            }
+        }
+
+        fn take(value: impl InfallibleToBigInt) -> BigInt {
+            value.to_bigint()
+        }
+        assert_eq!(take(Wrapping(Ticks(i64::MIN))), BigInt::from(i64::MIN));
+        assert_eq!(Saturating(Ticks(-3)).to_i128_fast(), Some(-3));
+        let wrapped = Wrapping(Ticks(5));
+        assert_eq!(take(&wrapped), BigInt::from(5));
+        assert_eq!(take(wrapped), BigInt::from(5));
+        let big = BigInt::from(u128::MAX) << 1_u32;
+        assert_eq!(Wrapping(big.clone()).to_bigint(), big);
+        assert_eq!(Saturating(&big).to_i128_fast(), None);
+        assert_eq!(
+            Wrapping(BigUint::from(u128::MAX)).to_bigint(),
+            BigInt::from(u128::MAX)
+        );
+    }
+
+    /// Test that cells convert identically to the value they hold
+    #[test]
+    fn test_cells() {
+        assert_eq!(
+            Cell::new(u8::MAX).to_bigint(),
+            InfallibleToBigInt::to_bigint(&u8::MAX)
+        );
+        assert_eq!(
+            Cell::new(i64::MIN).to_bigint(),
+            InfallibleToBigInt::to_bigint(&i64::MIN)
+        );
+        assert_eq!(Cell::new(u128::MAX).to_i128_fast(), None);
+        assert_eq!(Cell::new(i8::MIN).to_i128_fast(), Some(-128));
+        assert_eq!(
+            RefCell::new(u64::MAX).to_bigint(),
+            InfallibleToBigInt::to_bigint(&u64::MAX)
+        );
+        assert_eq!(
+            RefCell::new(isize::MIN).to_bigint(),
+            InfallibleToBigInt::to_bigint(&isize::MIN)
+        );
+        let big = -(BigInt::from(u128::MAX) << 64_u32);
+        assert_eq!(RefCell::new(big.clone()).to_bigint(), big);
+        let counter = Cell::new(1_i32);
+        counter.set(counter.get() - 2);
+        assert_eq!(counter.to_bigint(), BigInt::from(-1));
+        let shared = RefCell::new(1_i16);
+        let _reader = shared.borrow();
+        assert_eq!(shared.to_bigint(), BigInt::from(1));
+    }
+
+    /// Test cells around a downstream type, a wrapper and a bignum, which the generic impls cover
+    #[test]
+    fn test_cells_generic() {
+        #[derive(Clone, Copy)]
+        struct Counter(i32);
+
+        impl InfallibleToBigInt for Counter {
+            fn to_bigint(&self) -> BigInt {
+                BigInt::from(self.0)
+            }
+
+            fn to_i128_fast(&self) -> Option<i128> {
+                Some(i128::from(self.0))
+            }
+        }
+
+        let counter = Cell::new(Counter(-1));
+        assert_eq!(counter.to_bigint(), BigInt::from(-1));
+        counter.set(Counter(i32::MIN));
+        assert_eq!(counter.to_i128_fast(), Some(i128::from(i32::MIN)));
+        assert_eq!(Cell::new(Wrapping(-4_i8)).to_bigint(), BigInt::from(-4));
+        let shared = RefCell::new(Counter(7));
+        assert_eq!(Rc::new(shared).to_bigint(), BigInt::from(7));
+        let big = BigUint::from(u128::MAX) << 8_u32;
+        assert_eq!(RefCell::new(big.clone()).to_bigint(), BigInt::from(big));
+    }
+
+    /// Test that converting a mutably borrowed RefCell panics instead of reading the value
+    #[test]
+    #[should_panic(expected = "already mutably borrowed")]
+    fn test_ref_cell_mutably_borrowed() {
+        let shared = RefCell::new(i64::MIN);
+        let _writer = shared.borrow_mut();
+        shared.to_bigint();
+    }
+
+    /// Test consuming conversions of primitives in an iterator pipeline
+    #[test]
+    fn test_into_bigint() {
+        let values = vec![i64::MIN, 0, i64::MAX];
+        let converted: Vec<BigInt> = values
+            .into_iter()
+            .map(InfallibleIntoBigInt::into_bigint)
+            .collect();
+        assert_eq!(
+            converted,
+            [
+                BigInt::from(i64::MIN),
+                BigInt::from(0),
+                BigInt::from(i64::MAX)
+            ]
+        );
+        assert_eq!(u128::MAX.into_bigint(), BigInt::from(u128::MAX));
+        assert_eq!(
+            BigUint::from(u128::MAX).into_bigint(),
+            BigInt::from(u128::MAX)
+        );
+    }
+
+    /// Test nested references and every smart pointer with a signed and an unsigned value
+    #[test]
+    fn test_pointers() {
+        fn convert(value: impl InfallibleToBigInt) -> BigInt {
+            value.to_bigint()
+        }
+        let signed = i64::MIN;
+        let unsigned = u32::MAX;
+        let mut mutable = i64::MIN;
+        let reference: &i64 = &signed;
+        let nested: &&&u32 = &&&unsigned;
+        assert_eq!(convert(reference), BigInt::from(i64::MIN));
+        assert_eq!(convert(nested), BigInt::from(u32::MAX));
+        let mutable_reference: &mut i64 = &mut mutable;
+        assert_eq!(convert(mutable_reference), BigInt::from(i64::MIN));
+        assert_eq!(convert(&mut &mut mutable), BigInt::from(i64::MIN));
+        assert_eq!(convert(Box::new(signed)), BigInt::from(i64::MIN));
+        assert_eq!(convert(Box::new(&unsigned)), BigInt::from(u32::MAX));
+        assert_eq!(convert(Rc::new(signed)), BigInt::from(i64::MIN));
+        assert_eq!(convert(Arc::new(unsigned)), BigInt::from(u32::MAX));
+        assert_eq!(convert(Arc::new(Box::new(signed))), BigInt::from(i64::MIN));
+        assert_eq!(convert(Cow::Borrowed(&signed)), BigInt::from(i64::MIN));
+        assert_eq!(convert(Cow::<u32>::Owned(unsigned)), BigInt::from(u32::MAX));
+        let boxed: Box<dyn InfallibleToBigInt> = Box::new(unsigned);
+        assert_eq!(convert(&boxed), BigInt::from(u32::MAX));
+        assert_eq!(convert(boxed), BigInt::from(u32::MAX));
+    }
+
+    /// Test that pointers keep the i128 fast path of the value they point to
+    #[test]
+    fn test_pointers_fast_path() {
+        assert_eq!((&&-5_i8).to_i128_fast(), Some(-5));
+        assert_eq!(Box::new(u64::MAX).to_i128_fast(), Some(u64::MAX.into()));
+        assert_eq!(Rc::new(u128::MAX).to_i128_fast(), None);
+        assert_eq!(Cow::Borrowed(&7_isize).to_i128_fast(), Some(7));
+    }
+
+    /// Test that a downstream type gets the generic pointer impls, whatever features are enabled
+    #[test]
+    fn test_downstream_pointers() {
+        struct Balance(i64);
+
+        impl InfallibleToBigInt for Balance {
+            fn to_bigint(&self) -> BigInt {
+                BigInt::from(self.0)
+            }
+        }
+
+        fn take(value: impl InfallibleToBigInt) -> BigInt {
+            value.to_bigint()
+        }
+
+        fn generic<T: InfallibleToBigInt>(value: &T) -> BigInt {
+            take(value)
+        }
+
+        let balance = Balance(-7);
+        assert_eq!(take(&balance), BigInt::from(-7));
+        assert_eq!(generic(&balance), BigInt::from(-7));
+        assert_eq!(take(Box::new(balance)), BigInt::from(-7));
+        assert_eq!(
+            take(Rc::new(Arc::new(Balance(i64::MIN)))),
+            BigInt::from(i64::MIN)
+        );
+    }
+
+    /// Test that existing bignums pass through generic conversions unchanged
+    #[test]
+    fn test_bignum_identity() {
+        fn convert(value: impl InfallibleToBigInt) -> BigInt {
+            value.to_bigint()
+        }
+        let signed = -(BigInt::from(u128::MAX) << 64_u32);
+        let unsigned = BigUint::from(u128::MAX) << 64_u32;
+        assert_eq!(convert(&signed), signed);
+        assert_eq!(convert(&unsigned), BigInt::from(unsigned.clone()));
+        assert_eq!(convert(BigUint::default()), BigInt::from(0));
+        assert_eq!(BigInt::from(i128::MIN).to_i128_fast(), Some(i128::MIN));
+        assert_eq!(signed.to_i128_fast(), None);
+        assert_eq!(BigUint::from(7_u8).to_i128_fast(), Some(7));
+        assert_eq!(unsigned.to_i128_fast(), None);
+        assert_eq!(signed.clone().into_bigint(), signed);
+    }
+
+    /// Test that a BigInt is borrowed as a Cow instead of cloned
+    #[test]
+    fn test_to_bigint_cow_borrowed() {
+        let value = BigInt::from(u128::MAX) << 64_u32;
+        let Cow::Borrowed(borrowed) = value.to_bigint_cow() else {
+            panic!("a BigInt was cloned");
+        };
+        assert!(std::ptr::eq(borrowed, &value));
+        let reference: &BigInt = &value;
+        assert!(
+            matches!(reference.to_bigint_cow(), Cow::Borrowed(borrowed) if std::ptr::eq(borrowed, &value))
+        );
+    }
+
+    /// Test that primitives and BigUint are converted into an owned Cow matching the plain conversion
+    #[test]
+    fn test_to_bigint_cow_owned() {
+        fn check(value: impl InfallibleToBigInt + ToBigIntCow) {
+            let converted = value.to_bigint_cow();
+            assert!(matches!(converted, Cow::Owned(_)));
+            assert_eq!(*converted, value.to_bigint());
+        }
+        check(i8::MIN);
+        check(i64::MIN);
+        check(i128::MIN);
+        check(isize::MAX);
+        check(u8::MAX);
+        check(u64::MAX);
+        check(u128::MAX);
+        check(usize::MAX);
+        check(BigUint::from(u128::MAX) << 64_u32);
+    }
+
+    /// Test that assigning equals a fresh conversion, whatever the target held before
+    #[test]
+    fn test_to_bigint_into() {
+        fn check(value: impl InfallibleToBigInt + ToBigIntAssign) {
+            let fresh = InfallibleToBigInt::to_bigint(&value);
+            for previous in [
+                BigInt::from(0),
+                BigInt::from(-1),
+                BigInt::from(u8::MAX),
+                -(BigInt::from(u128::MAX) << 200_u32),
+            ] {
+                let mut target = previous;
+                value.to_bigint_into(&mut target);
+                assert_eq!(target, fresh);
+                assert_eq!(target.sign(), fresh.sign());
+            }
+        }
+        check(i8::MIN);
+        check(i16::MIN);
+        check(i32::MIN);
+        check(i64::MIN);
+        check(i128::MIN);
+        check(isize::MIN);
+        check(0_i8);
+        check(0_i128);
+        check(i8::MAX);
+        check(i16::MAX);
+        check(i32::MAX);
+        check(i64::MAX);
+        check(i128::MAX);
+        check(isize::MAX);
+        check(u8::MIN);
+        check(u64::MIN);
+        check(u128::MIN);
+        check(u8::MAX);
+        check(u16::MAX);
+        check(u32::MAX);
+        check(u64::MAX);
+        check(u128::MAX);
+        check(usize::MAX);
+        let reference: &i32 = &-7;
+        check(reference);
+    }
+
+    /// Error type that is compared by the address of its allocation
+    #[derive(Debug)]
+    struct Unique(Box<u32>);
+
+    /// Test converting through options, which only converts the outer layer
+    #[test]
+    fn test_option() {
+        assert_eq!(None::<u64>.to_bigint(), None);
+        assert_eq!(Some(u128::MAX).to_bigint(), Some(BigInt::from(u128::MAX)));
+        assert_eq!(Some(&i8::MIN).to_bigint(), Some(BigInt::from(i8::MIN)));
+        let nested: Option<Option<u8>> = Some(None);
+        assert_eq!(nested.flatten().to_bigint(), None);
+        let nested: Option<Option<u8>> = Some(Some(3));
+        assert_eq!(
+            nested.map(OptionToBigInt::to_bigint),
+            Some(Some(BigInt::from(3)))
+        );
+    }
+
+    /// Test converting through results, which passes the error through unchanged
+    #[test]
+    fn test_result() {
+        let ok: Result<u128, Unique> = Ok(u128::MAX);
+        assert_eq!(ok.to_bigint().unwrap(), BigInt::from(u128::MAX));
+        let error = Unique(Box::new(7));
+        let address: *const u32 = &*error.0;
+        let err: Result<i64, Unique> = Err(error);
+        let returned = err.to_bigint().unwrap_err();
+        assert_eq!(&*returned.0 as *const u32, address);
+        assert_eq!(*returned.0, 7);
+    }
+
+    /// Test that the implementations, and the digit split behind ToBigIntAssign, contain no panicking calls
+    #[test]
+    fn test_no_panic_paths() {
+        for (file, source) in [
+            (
+                "infallible_tobigint.rs",
+                include_str!("infallible_tobigint.rs"),
+            ),
+            ("digits.rs", include_str!("digits.rs")),
+        ] {
+            let implementation = source.split("#[cfg(test)]").next().unwrap_or_default();
+            for pattern in [
+                ".expect(",
+                ".unwrap(",
+                "panic!(",
+                "unreachable!(",
+                "unimplemented!(",
+            ] {
+                assert!(
+                    !implementation.contains(pattern),
+                    "{file} contains {pattern}"
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,82 @@
+use num::BigRational;
+
+use crate::InfallibleToBigInt;
+
+/// Allows for type conversion to [`num::BigRational`] without worrying about Results.
+///
+/// Every [`InfallibleToBigInt`] type implements it, and converts to the integer as a rational with denominator `1`.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::InfallibleToBigRational;
+/// use num::{BigInt, BigRational};
+///
+/// assert_eq!(153830_u32.to_bigrational(), BigRational::from_integer(BigInt::from(153830)));
+/// assert_eq!((-7_i8).to_bigrational().to_string(), "-7");
+/// ```
+pub trait InfallibleToBigRational {
+    fn to_bigrational(&self) -> BigRational;
+}
+
+impl<T: InfallibleToBigInt + ?Sized> InfallibleToBigRational for T {
+    fn to_bigrational(&self) -> BigRational {
+        BigRational::from_integer(self.to_bigint())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, BigRational, BigUint, Integer, One, Signed};
+
+    use super::InfallibleToBigRational;
+    use crate::InfallibleToBigInt;
+
+    /// Returns whether a ratio is in the reduced form that `Ratio` keeps
+    fn is_reduced(value: &BigRational) -> bool {
+        value.denom().is_positive() && value.numer().gcd(value.denom()).is_one()
+    }
+
+    /// Checks the numerator, the denominator and the reduced form of a converted value
+    fn check(value: impl InfallibleToBigInt) {
+        let rational = value.to_bigrational();
+        assert_eq!(*rational.numer(), value.to_bigint());
+        assert!(rational.denom().is_one());
+        assert!(rational.is_integer());
+        assert!(is_reduced(&rational));
+    }
+
+    /// Test MIN and MAX values of the signed primitives
+    #[test]
+    fn test_signed() {
+        check(i8::MIN);
+        check(i8::MAX);
+        check(i64::MIN);
+        check(i128::MIN);
+        check(i128::MAX);
+        check(isize::MIN);
+    }
+
+    /// Test MIN and MAX values of the unsigned primitives
+    #[test]
+    fn test_unsigned() {
+        check(u8::MIN);
+        check(u8::MAX);
+        check(u64::MAX);
+        check(u128::MIN);
+        check(u128::MAX);
+        check(usize::MAX);
+    }
+
+    /// Test bignums, references and zero
+    #[test]
+    fn test_other_sources() {
+        check(BigInt::from(u128::MAX) << 64_u32);
+        check(BigUint::from(u128::MAX) << 64_u32);
+        let reference: &&i32 = &&-1;
+        check(reference);
+        assert_eq!(
+            0_u8.to_bigrational(),
+            BigRational::from_integer(BigInt::from(0))
+        );
+    }
+}
@@ -1,4 +1,20 @@
-use num::{bigint::ToBigUint, BigUint};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    rc::Rc,
+    sync::Arc,
+};
+use core::{
+    cell::{Cell, RefCell},
+    num::{NonZero, Saturating, Wrapping},
+};
+#[cfg(feature = "std")]
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::Duration,
+};
+
+use num::{bigint::Sign, BigInt, BigUint};
 
 /// Allows for type conversion to [`num::BigUint`] without worrying about Results.
 ///
@@ -6,6 +22,17 @@ use num::{bigint::ToBigUint, BigUint};
 /// be converted if they are not possitive, whole numbers. This traits limits its implementation to those safe types
 /// mentioned. So no error checking is needed.
 ///
+/// `bool` converts to `0` or `1` and `char` to its Unicode scalar value. With the `std` feature, IP addresses convert
+/// to their big-endian numeric value, like `u32::from` and `u128::from` do. An [`IpAddr::V4`](std::net::IpAddr::V4)
+/// converts to its 32 bit value, not to the IPv4-mapped IPv6 address. A [`Duration`](std::time::Duration) converts
+/// to its exact number of nanoseconds.
+///
+/// [`num::BigUint`] implements it as well, by cloning, so generic functions accept existing values.
+///
+/// The implementations have no panicking branch either: they use `From` conversions, without going through an
+/// `Option` that would have to be unwrapped. The exception is [`std::cell::RefCell`], which panics if the value is
+/// mutably borrowed, like [`RefCell::borrow`].
+///
 /// # Example
 /// ```
 /// use infallible_tobigint::infallible_tobiguint::*;
@@ -24,57 +51,460 @@ use num::{bigint::ToBigUint, BigUint};
 /// ```
 pub trait InfallibleToBigUint {
     fn to_biguint(&self) -> BigUint;
+
+    /// Converts the value to its negation as a [`num::BigInt`], so `0` stays `0` and `u128::MAX` becomes `-u128::MAX`.
+    ///
+    /// The magnitude is taken as is, without going through a signed type that could overflow.
+    ///
+    /// # Example
+    /// ```
+    /// use infallible_tobigint::InfallibleToBigUint;
+    /// use num::BigInt;
+    ///
+    /// assert_eq!(u128::MAX.to_negated_bigint(), -BigInt::from(u128::MAX));
+    /// ```
+    fn to_negated_bigint(&self) -> BigInt {
+        self.to_bigint_with_sign(Sign::Minus)
+    }
+
+    /// Converts the value to a [`num::BigInt`] with the given sign, building it directly from the magnitude.
+    ///
+    /// The result is normalized like [`BigInt::from_biguint`]: [`Sign::NoSign`] gives `0` whatever the magnitude, and a
+    /// zero magnitude gives `0` with [`Sign::NoSign`] whatever the sign.
+    ///
+    /// # Example
+    /// ```
+    /// use infallible_tobigint::InfallibleToBigUint;
+    /// use num::{bigint::Sign, BigInt};
+    ///
+    /// assert_eq!(250_u64.to_bigint_with_sign(Sign::Minus), BigInt::from(-250));
+    /// assert_eq!(250_u64.to_bigint_with_sign(Sign::NoSign), BigInt::from(0));
+    /// assert_eq!(0_u64.to_bigint_with_sign(Sign::Minus).sign(), Sign::NoSign);
+    /// ```
+    fn to_bigint_with_sign(&self, sign: Sign) -> BigInt {
+        BigInt::from_biguint(sign, self.to_biguint())
+    }
+
+    /// Returns the value as a `u128` if that is possible without allocating.
+    ///
+    /// [`crate::as_big::AsBigUint`] and the scaled conversions use this to skip the `BigUint`. The default returns
+    /// `None`, which always takes the `BigUint` path.
+    fn to_u128_fast(&self) -> Option<u128> {
+        None
+    }
+
+    /// Converts the value by moving it, which [`InfallibleIntoBigUint`] uses to take a `BigUint` without copying it.
+    #[doc(hidden)]
+    fn into_biguint_by_value(self) -> BigUint
+    where
+        Self: Sized,
+    {
+        self.to_biguint()
+    }
+}
+
+/// Consuming conversion to [`num::BigUint`], for values that can be given up.
+///
+/// Every [`InfallibleToBigUint`] type implements it by converting the value, so iterator pipelines can use
+/// `map(InfallibleIntoBigUint::into_biguint)` by value.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::InfallibleIntoBigUint;
+/// use num::BigUint;
+///
+/// let values: Vec<BigUint> = vec![1_u8, 2].into_iter().map(InfallibleIntoBigUint::into_biguint).collect();
+/// assert_eq!(values, [BigUint::from(1_u8), BigUint::from(2_u8)]);
+/// ```
+pub trait InfallibleIntoBigUint {
+    fn into_biguint(self) -> BigUint;
+}
+
+impl<T: InfallibleToBigUint> InfallibleIntoBigUint for T {
+    fn into_biguint(self) -> BigUint {
+        self.into_biguint_by_value()
+    }
+}
+
+/// Conversion of a signed integer to the [`num::BigUint`] of its absolute value.
+///
+/// The magnitude is taken with `unsigned_abs`, so `i64::MIN` converts to `2^63` instead of overflowing on negation.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::ToBigUintAbs;
+/// use num::BigUint;
+///
+/// assert_eq!((-5_i32).to_biguint_abs(), BigUint::from(5_u8));
+/// assert_eq!(i64::MIN.to_biguint_abs(), BigUint::from(1_u8) << 63_u32);
+/// ```
+pub trait ToBigUintAbs {
+    fn to_biguint_abs(&self) -> BigUint;
+}
+
+macro_rules! impl_to_biguint_abs {
+    ($($value:ty),*) => {$(
+        impl ToBigUintAbs for $value {
+            fn to_biguint_abs(&self) -> BigUint {
+                self.unsigned_abs().to_biguint()
+            }
+        }
+    )*};
+}
+
+impl_to_biguint_abs!(i8, i16, i32, i64, i128, isize);
+
+impl<T: ToBigUintAbs + ?Sized> ToBigUintAbs for &T {
+    fn to_biguint_abs(&self) -> BigUint {
+        (**self).to_biguint_abs()
+    }
+}
+
+/// Decomposition of an integer into its [`Sign`] and the [`num::BigUint`] of its magnitude.
+///
+/// The pair is what [`BigInt::from_biguint`] takes, and follows its convention that zero has [`Sign::NoSign`].
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::ToSignMagnitude;
+/// use num::{bigint::Sign, BigInt, BigUint};
+///
+/// let (sign, magnitude) = i128::MIN.to_sign_magnitude();
+/// assert_eq!((sign, magnitude.clone()), (Sign::Minus, BigUint::from(1_u8) << 127_u32));
+/// assert_eq!(BigInt::from_biguint(sign, magnitude), BigInt::from(i128::MIN));
+/// assert_eq!(0_u8.to_sign_magnitude(), (Sign::NoSign, BigUint::ZERO));
+/// ```
+pub trait ToSignMagnitude {
+    fn to_sign_magnitude(&self) -> (Sign, BigUint);
+}
+
+macro_rules! impl_to_sign_magnitude_signed {
+    ($($value:ty),*) => {$(
+        impl ToSignMagnitude for $value {
+            fn to_sign_magnitude(&self) -> (Sign, BigUint) {
+                let sign = match self.signum() {
+                    -1 => Sign::Minus,
+                    0 => Sign::NoSign,
+                    _ => Sign::Plus,
+                };
+                (sign, self.unsigned_abs().to_biguint())
+            }
+        }
+    )*};
+}
+
+impl_to_sign_magnitude_signed!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_to_sign_magnitude_unsigned {
+    ($($value:ty),*) => {$(
+        impl ToSignMagnitude for $value {
+            fn to_sign_magnitude(&self) -> (Sign, BigUint) {
+                let sign = if *self == 0 { Sign::NoSign } else { Sign::Plus };
+                (sign, self.to_biguint())
+            }
+        }
+    )*};
+}
+
+impl_to_sign_magnitude_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl<T: ToSignMagnitude + ?Sized> ToSignMagnitude for &T {
+    fn to_sign_magnitude(&self) -> (Sign, BigUint) {
+        (**self).to_sign_magnitude()
+    }
+}
+
+/// Conversion of an optional value to an optional [`num::BigUint`].
+///
+/// Only the outer layer is converted, so an `Option<Option<u8>>` has to be flattened first.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::OptionToBigUint;
+/// use num::BigUint;
+///
+/// assert_eq!(Some(u128::MAX).to_biguint(), Some(BigUint::from(u128::MAX)));
+/// assert_eq!(None::<u8>.to_biguint(), None);
+/// ```
+pub trait OptionToBigUint {
+    fn to_biguint(self) -> Option<BigUint>;
+}
+
+impl<T: InfallibleToBigUint> OptionToBigUint for Option<T> {
+    fn to_biguint(self) -> Option<BigUint> {
+        self.map(InfallibleIntoBigUint::into_biguint)
+    }
+}
+
+/// Conversion of the success value of a result to a [`num::BigUint`], passing the error through unchanged.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::ResultToBigUint;
+/// use num::BigUint;
+///
+/// assert_eq!("17".parse::<u64>().to_biguint(), Ok(BigUint::from(17_u8)));
+/// assert!("-17".parse::<u64>().to_biguint().is_err());
+/// ```
+pub trait ResultToBigUint<E> {
+    fn to_biguint(self) -> Result<BigUint, E>;
+}
+
+impl<T: InfallibleToBigUint, E> ResultToBigUint<E> for Result<T, E> {
+    fn to_biguint(self) -> Result<BigUint, E> {
+        self.map(InfallibleIntoBigUint::into_biguint)
+    }
 }
 
 impl InfallibleToBigUint for u8 {
     fn to_biguint(&self) -> BigUint {
-        ToBigUint::to_biguint(self)
-            .expect("to_biguint failed for u8, this should not happen and is most likely a programming error")
+        BigUint::from(*self)
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(u128::from(*self))
     }
 }
 
 impl InfallibleToBigUint for u16 {
     fn to_biguint(&self) -> BigUint {
-        ToBigUint::to_biguint(self)
-            .expect("to_biguint failed for u16, this should not happen and is most likely a programming error")
+        BigUint::from(*self)
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(u128::from(*self))
     }
 }
 
 impl InfallibleToBigUint for u32 {
     fn to_biguint(&self) -> BigUint {
-        ToBigUint::to_biguint(self)
-            .expect("to_biguint failed for u32, this should not happen and is most likely a programming error")
+        BigUint::from(*self)
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(u128::from(*self))
     }
 }
 
 impl InfallibleToBigUint for u64 {
     fn to_biguint(&self) -> BigUint {
-        ToBigUint::to_biguint(self)
-            .expect("to_biguint failed for u64, this should not happen and is most likely a programming error")
+        BigUint::from(*self)
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(u128::from(*self))
     }
 }
 
 impl InfallibleToBigUint for u128 {
     fn to_biguint(&self) -> BigUint {
-        ToBigUint::to_biguint(self)
-            .expect("to_biguint failed for u128, this should not happen and is most likely a programming error")
+        BigUint::from(*self)
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(*self)
     }
 }
 
 impl InfallibleToBigUint for usize {
     fn to_biguint(&self) -> BigUint {
-        ToBigUint::to_biguint(self)
-            .expect("to_biguint failed for usize, this should not happen and is most likely a programming error")
+        BigUint::from(*self)
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        u128::try_from(*self).ok()
+    }
+}
+
+impl InfallibleToBigUint for bool {
+    fn to_biguint(&self) -> BigUint {
+        BigUint::from(u8::from(*self))
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(u128::from(*self))
+    }
+}
+
+impl InfallibleToBigUint for char {
+    fn to_biguint(&self) -> BigUint {
+        BigUint::from(u32::from(*self))
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(u128::from(u32::from(*self)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl InfallibleToBigUint for Ipv4Addr {
+    fn to_biguint(&self) -> BigUint {
+        BigUint::from(u32::from(*self))
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(u128::from(u32::from(*self)))
+    }
+}
+
+#[cfg(feature = "std")]
+impl InfallibleToBigUint for Ipv6Addr {
+    fn to_biguint(&self) -> BigUint {
+        BigUint::from(u128::from(*self))
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(u128::from(*self))
+    }
+}
+
+/// Converts an IPv4 address as its 32 bit value, without mapping it into the IPv6 space.
+#[cfg(feature = "std")]
+impl InfallibleToBigUint for IpAddr {
+    fn to_biguint(&self) -> BigUint {
+        match self {
+            IpAddr::V4(address) => address.to_biguint(),
+            IpAddr::V6(address) => address.to_biguint(),
+        }
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        match self {
+            IpAddr::V4(address) => address.to_u128_fast(),
+            IpAddr::V6(address) => address.to_u128_fast(),
+        }
+    }
+}
+
+/// Converts the total number of nanoseconds, which fits into a `u128` even for `Duration::MAX`.
+#[cfg(feature = "std")]
+impl InfallibleToBigUint for Duration {
+    fn to_biguint(&self) -> BigUint {
+        BigUint::from(self.as_nanos())
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        Some(self.as_nanos())
+    }
+}
+
+/// Converts the non-zero integers through the value they hold.
+macro_rules! impl_to_biguint_nonzero {
+    ($($value:ty),*) => {$(
+        impl InfallibleToBigUint for NonZero<$value> {
+            fn to_biguint(&self) -> BigUint {
+                self.get().to_biguint()
+            }
+
+            fn to_u128_fast(&self) -> Option<u128> {
+                self.get().to_u128_fast()
+            }
+        }
+    )*};
+}
+
+impl_to_biguint_nonzero!(u8, u16, u32, u64, u128, usize);
+
+/// Converts the arithmetic wrappers through the value they hold.
+macro_rules! impl_to_biguint_wrapper {
+    ($($wrapper:ident),*) => {$(
+        impl<T: InfallibleToBigUint> InfallibleToBigUint for $wrapper<T> {
+            fn to_biguint(&self) -> BigUint {
+                self.0.to_biguint()
+            }
+
+            fn to_u128_fast(&self) -> Option<u128> {
+                self.0.to_u128_fast()
+            }
+        }
+    )*};
+}
+
+impl_to_biguint_wrapper!(Wrapping, Saturating);
+
+/// Converts the value in a cell, reading a `RefCell` with `borrow`.
+impl<T: Copy + InfallibleToBigUint> InfallibleToBigUint for Cell<T> {
+    fn to_biguint(&self) -> BigUint {
+        self.get().to_biguint()
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        self.get().to_u128_fast()
+    }
+}
+
+impl<T: InfallibleToBigUint + ?Sized> InfallibleToBigUint for RefCell<T> {
+    fn to_biguint(&self) -> BigUint {
+        self.borrow().to_biguint()
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        self.borrow().to_u128_fast()
+    }
+}
+
+impl InfallibleToBigUint for BigUint {
+    fn to_biguint(&self) -> BigUint {
+        self.clone()
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        u128::try_from(self).ok()
+    }
+
+    fn into_biguint_by_value(self) -> BigUint {
+        self
+    }
+}
+
+/// Forwards the conversion through references and smart pointers to the value they point to.
+macro_rules! impl_to_biguint_deref {
+    ($($pointer:ty),*) => {$(
+        impl<T: InfallibleToBigUint + ?Sized> InfallibleToBigUint for $pointer {
+            fn to_biguint(&self) -> BigUint {
+                (**self).to_biguint()
+            }
+
+            fn to_u128_fast(&self) -> Option<u128> {
+                (**self).to_u128_fast()
+            }
+        }
+    )*};
+}
+
+impl_to_biguint_deref!(&T, &mut T, Box<T>, Rc<T>, Arc<T>);
+
+impl<T: InfallibleToBigUint + ToOwned + ?Sized> InfallibleToBigUint for Cow<'_, T> {
+    fn to_biguint(&self) -> BigUint {
+        (**self).to_biguint()
+    }
+
+    fn to_u128_fast(&self) -> Option<u128> {
+        (**self).to_u128_fast()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::u8;
+    use std::{
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        num::{NonZero, Saturating, Wrapping},
+        rc::Rc,
+        sync::Arc,
+    };
 
-    use num::bigint::ToBigUint;
+    use num::{
+        bigint::{Sign, ToBigUint},
+        BigInt, BigUint,
+    };
 
-    use super::InfallibleToBigUint;
+    use super::{
+        InfallibleIntoBigUint, InfallibleToBigUint, OptionToBigUint, ResultToBigUint, ToBigUintAbs,
+        ToSignMagnitude,
+    };
+    #[cfg(feature = "bigint")]
+    use crate::InfallibleToBigInt;
 
     /// Test MIN and MAX values of u8
     #[test]
@@ -153,4 +583,480 @@ mod tests {
             ToBigUint::to_biguint(&usize::MAX).unwrap()
         );
     }
+
+    /// Test MIN (1) and MAX values of NonZero<u8>
+    #[test]
+    fn test_nonzero_u8() {
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u8>::MIN),
+            ToBigUint::to_biguint(&NonZero::<u8>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u8>::MAX),
+            ToBigUint::to_biguint(&NonZero::<u8>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<u16>
+    #[test]
+    fn test_nonzero_u16() {
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u16>::MIN),
+            ToBigUint::to_biguint(&NonZero::<u16>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u16>::MAX),
+            ToBigUint::to_biguint(&NonZero::<u16>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<u32>
+    #[test]
+    fn test_nonzero_u32() {
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u32>::MIN),
+            ToBigUint::to_biguint(&NonZero::<u32>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u32>::MAX),
+            ToBigUint::to_biguint(&NonZero::<u32>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<u64>
+    #[test]
+    fn test_nonzero_u64() {
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u64>::MIN),
+            ToBigUint::to_biguint(&NonZero::<u64>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u64>::MAX),
+            ToBigUint::to_biguint(&NonZero::<u64>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<u128>
+    #[test]
+    fn test_nonzero_u128() {
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u128>::MIN),
+            ToBigUint::to_biguint(&NonZero::<u128>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<u128>::MAX),
+            ToBigUint::to_biguint(&NonZero::<u128>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test MIN (1) and MAX values of NonZero<usize>
+    #[test]
+    fn test_nonzero_usize() {
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<usize>::MIN),
+            ToBigUint::to_biguint(&NonZero::<usize>::MIN.get()).unwrap()
+        );
+        assert_eq!(
+            InfallibleToBigUint::to_biguint(&NonZero::<usize>::MAX),
+            ToBigUint::to_biguint(&NonZero::<usize>::MAX.get()).unwrap()
+        );
+    }
+
+    /// Test that the arithmetic wrappers convert exactly like the value they hold
+    #[test]
+    fn test_wrappers() {
+        fn check<T: InfallibleToBigUint + Copy>(values: [T; 2]) {
+            for value in values {
+                assert_eq!(Wrapping(value).to_biguint(), value.to_biguint());
+                assert_eq!(Saturating(value).to_biguint(), value.to_biguint());
+                assert_eq!(Wrapping(value).to_u128_fast(), value.to_u128_fast());
+            }
+        }
+        assert_eq!(
+            Saturating(u128::MAX).to_biguint(),
+            ToBigUint::to_biguint(&u128::MAX).unwrap()
+        );
+        check([u8::MIN, u8::MAX]);
+        check([u16::MIN, u16::MAX]);
+        check([u32::MIN, u32::MAX]);
+        check([u64::MIN, u64::MAX]);
+        check([u128::MIN, u128::MAX]);
+        check([usize::MIN, usize::MAX]);
+    }
+
+    /// Test bool as 0 and 1
+    #[test]
+    fn test_bool() {
+        assert_eq!(false.to_biguint(), BigUint::from(0_u8));
+        assert_eq!(true.to_biguint(), BigUint::from(1_u8));
+        assert_eq!(true.to_u128_fast(), Some(1));
+    }
+
+    /// Test char as its scalar value at the ends of the range and around the surrogates
+    #[test]
+    fn test_char() {
+        for c in [
+            '\0',
+            '\u{d7ff}',
+            '\u{e000}',
+            '\u{ffff}',
+            '\u{10000}',
+            char::MAX,
+        ] {
+            assert_eq!(c.to_biguint(), BigUint::from(c as u32));
+            assert_eq!(c.to_u128_fast(), Some(u128::from(c as u32)));
+        }
+    }
+
+    /// Test the lowest and highest addresses of both versions
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ip_addr() {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+        assert_eq!(Ipv4Addr::UNSPECIFIED.to_biguint(), BigUint::from(0_u8));
+        assert_eq!(Ipv4Addr::BROADCAST.to_biguint(), BigUint::from(u32::MAX));
+        assert_eq!(Ipv6Addr::UNSPECIFIED.to_biguint(), BigUint::from(0_u8));
+        let max = Ipv6Addr::new(
+            0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+        );
+        assert_eq!(max.to_biguint(), BigUint::from(u128::MAX));
+        assert_eq!(max.to_u128_fast(), Some(u128::MAX));
+        // an IPv4 address keeps its 32 bit value instead of becoming ::ffff:a.b.c.d
+        let v4 = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+        assert_eq!(v4.to_biguint(), BigUint::from(0xc000_0201_u32));
+        assert_ne!(
+            v4.to_biguint(),
+            Ipv4Addr::new(192, 0, 2, 1).to_ipv6_mapped().to_biguint()
+        );
+        assert_eq!(IpAddr::V6(max).to_biguint(), BigUint::from(u128::MAX));
+    }
+
+    /// Test durations as exact nanoseconds, up to Duration::MAX beyond u64
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_duration() {
+        use std::time::Duration;
+
+        assert_eq!(Duration::ZERO.to_biguint(), BigUint::from(0_u8));
+        assert_eq!(Duration::ZERO.to_u128_fast(), Some(0));
+        assert_eq!(
+            Duration::new(2, 500).to_biguint(),
+            BigUint::from(2_000_000_500_u32)
+        );
+        let max = BigUint::from(u64::MAX) * 1_000_000_000_u32 + 999_999_999_u32;
+        assert!(max > BigUint::from(u64::MAX));
+        assert_eq!(Duration::MAX.to_biguint(), max);
+        assert_eq!(Duration::MAX.to_u128_fast(), Some(Duration::MAX.as_nanos()));
+    }
+
+    /// Test a mixed list of addresses through the iterator adapter
+    #[cfg(all(feature = "std", feature = "iter"))]
+    #[test]
+    fn test_ip_addr_iter() {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+        use crate::iter::ToBigIntIterExt;
+
+        let addresses: Vec<IpAddr> = vec![
+            Ipv4Addr::UNSPECIFIED.into(),
+            Ipv4Addr::BROADCAST.into(),
+            Ipv6Addr::LOCALHOST.into(),
+            Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).into(),
+        ];
+        let values: Vec<BigUint> = addresses.iter().to_biguints().collect();
+        assert_eq!(
+            values,
+            [
+                BigUint::from(0_u8),
+                BigUint::from(u32::MAX),
+                BigUint::from(1_u8),
+                BigUint::from(0x2001_0db8_u128 << 96 | 1),
+            ]
+        );
+        let total: BigInt = addresses.iter().to_bigints().sum();
+        assert_eq!(
+            total,
+            BigInt::from(u32::MAX) + 2 + (BigInt::from(0x2001_0db8_u32) << 96_u32)
+        );
+    }
+
+    /// Test that cells convert identically to the value they hold
+    #[test]
+    fn test_cells() {
+        assert_eq!(
+            Cell::new(u8::MAX).to_biguint(),
+            InfallibleToBigUint::to_biguint(&u8::MAX)
+        );
+        assert_eq!(Cell::new(u128::MAX).to_u128_fast(), Some(u128::MAX));
+        assert_eq!(
+            RefCell::new(u64::MAX).to_biguint(),
+            InfallibleToBigUint::to_biguint(&u64::MAX)
+        );
+        let big = BigUint::from(u128::MAX) << 64_u32;
+        assert_eq!(RefCell::new(big.clone()).to_biguint(), big);
+    }
+
+    /// Test that converting a mutably borrowed RefCell panics instead of reading the value
+    #[test]
+    #[should_panic(expected = "already mutably borrowed")]
+    fn test_ref_cell_mutably_borrowed() {
+        let shared = RefCell::new(u64::MAX);
+        let _writer = shared.borrow_mut();
+        shared.to_biguint();
+    }
+
+    /// Test the absolute value of MIN, zero, one and MAX of every signed type
+    #[test]
+    fn test_to_biguint_abs() {
+        fn check<T: ToBigUintAbs + Into<BigInt> + Copy>(values: [T; 5]) {
+            for value in values {
+                let expected: BigInt = value.into();
+                assert_eq!(value.to_biguint_abs(), *expected.magnitude());
+            }
+        }
+        check([i8::MIN, -1, 0, 1, i8::MAX]);
+        check([i16::MIN, -1, 0, 1, i16::MAX]);
+        check([i32::MIN, -1, 0, 1, i32::MAX]);
+        check([i64::MIN, -1, 0, 1, i64::MAX]);
+        check([i128::MIN, -1, 0, 1, i128::MAX]);
+        check([isize::MIN, -1, 0, 1, isize::MAX]);
+        assert_eq!(i128::MIN.to_biguint_abs(), BigUint::from(1_u8) << 127_u32);
+        fn abs(value: impl ToBigUintAbs) -> BigUint {
+            value.to_biguint_abs()
+        }
+        let reference: &i8 = &-7;
+        assert_eq!(abs(reference), BigUint::from(7_u8));
+    }
+
+    /// Test that MIN, zero and MAX of every type round-trip through their sign and magnitude
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_to_sign_magnitude() {
+        fn check<T: ToSignMagnitude + InfallibleToBigInt>(values: [T; 3]) {
+            for value in values {
+                let (sign, magnitude) = value.to_sign_magnitude();
+                let expected = InfallibleToBigInt::to_bigint(&value);
+                assert_eq!(sign, expected.sign());
+                assert_eq!(magnitude, *expected.magnitude());
+                assert_eq!(BigInt::from_biguint(sign, magnitude), expected);
+            }
+        }
+        check([i8::MIN, 0, i8::MAX]);
+        check([i16::MIN, 0, i16::MAX]);
+        check([i32::MIN, 0, i32::MAX]);
+        check([i64::MIN, 0, i64::MAX]);
+        check([i128::MIN, 0, i128::MAX]);
+        check([isize::MIN, 0, isize::MAX]);
+        check([u8::MIN, 1, u8::MAX]);
+        check([u16::MIN, 1, u16::MAX]);
+        check([u32::MIN, 1, u32::MAX]);
+        check([u64::MIN, 1, u64::MAX]);
+        check([u128::MIN, 1, u128::MAX]);
+        check([usize::MIN, 1, usize::MAX]);
+    }
+
+    /// Test that zero has no sign and a zero magnitude, like in num-bigint
+    #[test]
+    fn test_to_sign_magnitude_zero() {
+        for (sign, magnitude) in [
+            0_i8.to_sign_magnitude(),
+            0_i128.to_sign_magnitude(),
+            0_u64.to_sign_magnitude(),
+            0_usize.to_sign_magnitude(),
+        ] {
+            assert_eq!(sign, Sign::NoSign);
+            assert_eq!(magnitude, BigUint::ZERO);
+        }
+        assert_eq!((-1_i8).to_sign_magnitude().0, Sign::Minus);
+    }
+
+    /// Test the direct digit construction around every digit boundary
+    #[test]
+    fn test_digit_boundaries() {
+        let boundaries = [
+            0,
+            1,
+            u32::MAX as u128 - 1,
+            u32::MAX as u128,
+            u32::MAX as u128 + 1,
+            u64::MAX as u128 - 1,
+            u64::MAX as u128,
+            u64::MAX as u128 + 1,
+            1 << 96,
+            u128::MAX - 1,
+            u128::MAX,
+        ];
+        for value in boundaries {
+            assert_eq!(
+                InfallibleToBigUint::to_biguint(&value),
+                ToBigUint::to_biguint(&value).unwrap()
+            );
+            assert_eq!(
+                InfallibleToBigUint::to_biguint(&value).bits(),
+                128 - value.leading_zeros() as u64
+            );
+        }
+        for value in [0, 1, u32::MAX as u64, u32::MAX as u64 + 1, u64::MAX] {
+            assert_eq!(
+                InfallibleToBigUint::to_biguint(&value),
+                ToBigUint::to_biguint(&value).unwrap()
+            );
+        }
+    }
+
+    /// Test the u128 fast path of the primitives and pointers to them
+    #[test]
+    fn test_to_u128_fast() {
+        assert_eq!(u8::MAX.to_u128_fast(), Some(255));
+        assert_eq!(u64::MAX.to_u128_fast(), Some(u64::MAX.into()));
+        assert_eq!(u128::MAX.to_u128_fast(), Some(u128::MAX));
+        assert_eq!(usize::MAX.to_u128_fast(), Some(usize::MAX as u128));
+        assert_eq!((&&7_u16).to_u128_fast(), Some(7));
+        assert_eq!(Cow::Borrowed(&7_u32).to_u128_fast(), Some(7));
+    }
+
+    /// Test that an existing BigUint passes through generic conversions unchanged
+    #[test]
+    fn test_biguint_identity() {
+        fn convert(value: impl InfallibleToBigUint) -> BigUint {
+            value.to_biguint()
+        }
+        let value = BigUint::from(u128::MAX) << 64_u32;
+        assert_eq!(convert(&value), value);
+        assert_eq!(value.clone().into_biguint(), value);
+        assert_eq!(value.to_u128_fast(), None);
+        assert_eq!(BigUint::from(u128::MAX).to_u128_fast(), Some(u128::MAX));
+    }
+
+    /// Test negating unsigned values
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_to_negated_bigint() {
+        assert_eq!(u128::MAX.to_negated_bigint(), -BigInt::from(u128::MAX));
+        assert_eq!(0_u8.to_negated_bigint(), BigInt::from(0));
+        assert_eq!(0_u64.to_negated_bigint().sign(), Sign::NoSign);
+        assert_eq!(1_u16.to_negated_bigint(), BigInt::from(-1));
+        for value in [
+            0,
+            1,
+            u32::MAX as u128,
+            u64::MAX as u128 + 1,
+            i128::MAX as u128 + 1,
+        ] {
+            assert_eq!(
+                value.to_negated_bigint(),
+                -InfallibleToBigInt::to_bigint(&value)
+            );
+        }
+        assert_eq!(
+            usize::MAX.to_negated_bigint(),
+            -InfallibleToBigInt::to_bigint(&usize::MAX)
+        );
+    }
+
+    /// Test every sign with zero, one and MAX of every type
+    #[test]
+    fn test_to_bigint_with_sign() {
+        fn check<T: InfallibleToBigUint>(values: [T; 3]) {
+            for value in values {
+                let magnitude = value.to_biguint();
+                let zero = magnitude == BigUint::ZERO;
+                let plus = value.to_bigint_with_sign(Sign::Plus);
+                let minus = value.to_bigint_with_sign(Sign::Minus);
+                let no_sign = value.to_bigint_with_sign(Sign::NoSign);
+                assert_eq!(plus, BigInt::from(magnitude.clone()));
+                assert_eq!(minus, -BigInt::from(magnitude));
+                assert_eq!(no_sign, BigInt::ZERO);
+                assert_eq!(no_sign.sign(), Sign::NoSign);
+                assert_eq!(plus.sign(), if zero { Sign::NoSign } else { Sign::Plus });
+                assert_eq!(minus.sign(), if zero { Sign::NoSign } else { Sign::Minus });
+            }
+        }
+        check([0, 1, u8::MAX]);
+        check([0, 1, u16::MAX]);
+        check([0, 1, u32::MAX]);
+        check([0, 1, u64::MAX]);
+        check([0, 1, u128::MAX]);
+        check([0, 1, usize::MAX]);
+    }
+
+    /// Test consuming conversions of primitives in an iterator pipeline
+    #[test]
+    fn test_into_biguint() {
+        let converted: Vec<_> = vec![0_usize, usize::MAX]
+            .into_iter()
+            .map(InfallibleIntoBigUint::into_biguint)
+            .collect();
+        assert_eq!(
+            converted,
+            [
+                ToBigUint::to_biguint(&0_usize).unwrap(),
+                ToBigUint::to_biguint(&usize::MAX).unwrap()
+            ]
+        );
+        assert_eq!(
+            u8::MAX.into_biguint(),
+            ToBigUint::to_biguint(&u8::MAX).unwrap()
+        );
+    }
+
+    /// Test nested references and every smart pointer
+    #[test]
+    fn test_pointers() {
+        fn convert(value: impl InfallibleToBigUint) -> BigUint {
+            value.to_biguint()
+        }
+        let value = u64::MAX;
+        let mut mutable = u8::MAX;
+        let expected = ToBigUint::to_biguint(&u64::MAX).unwrap();
+        let reference: &u64 = &value;
+        let nested: &&&u64 = &&&value;
+        assert_eq!(convert(reference), expected);
+        assert_eq!(convert(nested), expected);
+        let mutable_reference: &mut u8 = &mut mutable;
+        assert_eq!(
+            convert(mutable_reference),
+            ToBigUint::to_biguint(&u8::MAX).unwrap()
+        );
+        assert_eq!(convert(Box::new(value)), expected);
+        assert_eq!(convert(Rc::new(&value)), expected);
+        assert_eq!(convert(Arc::new(value)), expected);
+        assert_eq!(convert(Cow::Borrowed(&value)), expected);
+        assert_eq!(convert(Cow::<u64>::Owned(value)), expected);
+        let boxed: Box<dyn InfallibleToBigUint> = Box::new(value);
+        assert_eq!(convert(&boxed), expected);
+    }
+
+    /// Test converting through options and results
+    #[test]
+    fn test_option_result() {
+        assert_eq!(None::<u64>.to_biguint(), None);
+        assert_eq!(Some(u128::MAX).to_biguint(), Some(BigUint::from(u128::MAX)));
+        let nested: Option<Option<u8>> = Some(Some(3));
+        assert_eq!(nested.flatten().to_biguint(), Some(BigUint::from(3_u8)));
+        let ok: Result<u16, &str> = Ok(u16::MAX);
+        assert_eq!(ok.to_biguint(), Ok(BigUint::from(u16::MAX)));
+        let err: Result<u16, &str> = Err("bad input");
+        assert_eq!(err.to_biguint(), Err("bad input"));
+    }
+
+    /// Test that the implementations contain no panicking calls
+    #[test]
+    fn test_no_panic_paths() {
+        let source = include_str!("infallible_tobiguint.rs");
+        let implementation = source.split("#[cfg(test)]").next().unwrap_or_default();
+        for pattern in [
+            ".expect(",
+            ".unwrap(",
+            "panic!(",
+            "unreachable!(",
+            "unimplemented!(",
+        ] {
+            assert!(
+                !implementation.contains(pattern),
+                "infallible_tobiguint.rs contains {pattern}"
+            );
+        }
+    }
 }
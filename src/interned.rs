@@ -0,0 +1,122 @@
+use std::{borrow::Cow, sync::OnceLock};
+
+use num::BigInt;
+
+use crate::InfallibleToBigInt;
+
+/// The smallest value in the table.
+const MIN: i128 = -128;
+/// The largest value in the table.
+const MAX: i128 = 255;
+
+/// Returns the table of every value from [`MIN`] to [`MAX`], building it on first use.
+fn table() -> &'static [BigInt] {
+    static TABLE: OnceLock<Vec<BigInt>> = OnceLock::new();
+    TABLE.get_or_init(|| (MIN..=MAX).map(BigInt::from).collect())
+}
+
+/// Converts a value into a [`num::BigInt`], borrowing small values from a shared table instead of allocating.
+///
+/// Values from −128 to 255 are returned as `Cow::Borrowed` from a static table that is built on the first call, so
+/// repeated lookups of the same small value return the same reference. Every other value is converted as usual and
+/// returned as `Cow::Owned`.
+///
+/// # Example
+/// ```
+/// use std::borrow::Cow;
+///
+/// use infallible_tobigint::interned::interned;
+/// use num::BigInt;
+///
+/// assert!(matches!(interned(200_u8), Cow::Borrowed(_)));
+/// assert!(matches!(interned(256_u16), Cow::Owned(_)));
+/// assert_eq!(*interned(-7_i64), BigInt::from(-7));
+/// ```
+pub fn interned(value: impl InfallibleToBigInt) -> Cow<'static, BigInt> {
+    interned_ref(&value)
+}
+
+/// Converts every value of a slice like [`interned`].
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::interned::interned_slice;
+/// use num::BigInt;
+///
+/// let values = interned_slice(&[1_u64, u64::MAX]);
+/// assert_eq!(*values[1], BigInt::from(u64::MAX));
+/// ```
+pub fn interned_slice<T: InfallibleToBigInt>(values: &[T]) -> Vec<Cow<'static, BigInt>> {
+    values.iter().map(interned_ref).collect()
+}
+
+fn interned_ref(value: &impl InfallibleToBigInt) -> Cow<'static, BigInt> {
+    match value.to_i128_fast() {
+        Some(small @ MIN..=MAX) => Cow::Borrowed(&table()[(small - MIN) as usize]),
+        _ => Cow::Owned(value.to_bigint()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, thread};
+
+    use num::BigInt;
+
+    use super::{interned, interned_slice};
+
+    /// Test which values are borrowed at the boundaries of the table
+    #[test]
+    fn test_boundaries() {
+        assert!(matches!(interned(-128_i8), Cow::Borrowed(_)));
+        assert!(matches!(interned(-129_i16), Cow::Owned(_)));
+        assert!(matches!(interned(255_u8), Cow::Borrowed(_)));
+        assert!(matches!(interned(256_u16), Cow::Owned(_)));
+        assert!(matches!(interned(0_u128), Cow::Borrowed(_)));
+        assert!(matches!(interned(u128::MAX), Cow::Owned(_)));
+        assert!(matches!(interned(i128::MIN), Cow::Owned(_)));
+        assert_eq!(*interned(-129_i16), BigInt::from(-129));
+        assert_eq!(*interned(u128::MAX), BigInt::from(u128::MAX));
+    }
+
+    /// Test that repeated lookups of a small value, from any type, return the same reference
+    #[test]
+    fn test_identity() {
+        let (Cow::Borrowed(a), Cow::Borrowed(b)) = (interned(42_u8), interned(42_i64)) else {
+            panic!("small values should be borrowed");
+        };
+        assert!(core::ptr::eq(a, b));
+        let values = interned_slice(&[42_usize, 43, 1000]);
+        assert!(matches!(values[0], Cow::Borrowed(c) if core::ptr::eq(a, c)));
+        assert!(matches!(values[2], Cow::Owned(_)));
+    }
+
+    /// Test every entry of the table
+    #[test]
+    fn test_all_entries() {
+        for value in -128_i16..=255 {
+            assert_eq!(*interned(value), BigInt::from(value));
+        }
+    }
+
+    /// Test that the table is built once when threads race to use it
+    #[test]
+    fn test_threads() {
+        let addresses: Vec<usize> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    scope.spawn(|| match interned(-1_i32) {
+                        Cow::Borrowed(value) => value as *const BigInt as usize,
+                        Cow::Owned(_) => 0,
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect()
+        });
+        assert_ne!(addresses[0], 0);
+        assert!(addresses.iter().all(|&address| address == addresses[0]));
+    }
+}
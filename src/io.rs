@@ -0,0 +1,426 @@
+use std::{fmt, io};
+
+use num::{bigint::Sign, BigInt, BigUint, Integer, ToPrimitive};
+
+use crate::decode::{BigIntBuilder, CHUNK_BASE, CHUNK_DIGITS};
+
+/// The size of the chunks read from or written to the underlying stream.
+const BUFFER_SIZE: usize = 8 * 1024;
+
+/// Error returned when a number cannot be parsed from a stream.
+#[derive(Debug)]
+pub enum StreamParseError {
+    /// Reading from the underlying stream failed.
+    Io(io::Error),
+    /// The input ended before any digit was found, e.g. because it was empty or only contained a sign.
+    NoDigits,
+    /// A byte that is neither a digit nor trailing whitespace was found.
+    InvalidByte {
+        /// The offset of the byte from the start of the stream.
+        offset: u64,
+        /// The offending byte.
+        byte: u8,
+    },
+}
+
+impl fmt::Display for StreamParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamParseError::Io(error) => write!(f, "failed to read number: {error}"),
+            StreamParseError::NoDigits => write!(f, "input does not contain any digits"),
+            StreamParseError::InvalidByte { offset, byte } => {
+                write!(f, "invalid byte {byte:#04x} at offset {offset}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for StreamParseError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            StreamParseError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for StreamParseError {
+    fn from(error: io::Error) -> Self {
+        StreamParseError::Io(error)
+    }
+}
+
+/// Where the parser currently is within the number.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    LeadingWhitespace,
+    AfterSign,
+    Digits,
+    TrailingWhitespace,
+}
+
+/// Parses a decimal [`num::BigInt`] from a reader without loading the whole text at once.
+///
+/// The input is read in chunks, so only the digits accumulated so far are kept in memory. Leading and trailing ASCII
+/// whitespace is skipped and an optional `+` or `-` sign may precede the digits. The digits are combined with a
+/// [`BigIntBuilder`], so parsing numbers with millions of digits stays fast.
+///
+/// Returns [`StreamParseError::InvalidByte`] with its offset for the first byte that does not fit this format,
+/// [`StreamParseError::NoDigits`] if no digit was found and [`StreamParseError::Io`] if reading fails.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::io::read_bigint_decimal;
+/// use num::BigInt;
+///
+/// let value = read_bigint_decimal("  -123456789012345678901234567890\n".as_bytes()).unwrap();
+/// assert_eq!(value, "-123456789012345678901234567890".parse::<BigInt>().unwrap());
+/// ```
+pub fn read_bigint_decimal<R: io::Read>(mut reader: R) -> Result<BigInt, StreamParseError> {
+    let mut buffer = vec![0; BUFFER_SIZE];
+    let mut builder = BigIntBuilder::new();
+    let mut state = State::LeadingWhitespace;
+    let mut sign = Sign::Plus;
+    let mut offset = 0_u64;
+    loop {
+        let read = match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error.into()),
+        };
+        for &byte in &buffer[..read] {
+            state = match (state, byte) {
+                (State::LeadingWhitespace, byte) if byte.is_ascii_whitespace() => state,
+                (State::LeadingWhitespace, b'+') => State::AfterSign,
+                (State::LeadingWhitespace, b'-') => {
+                    sign = Sign::Minus;
+                    State::AfterSign
+                }
+                (State::LeadingWhitespace | State::AfterSign | State::Digits, b'0'..=b'9') => {
+                    builder.push_digit(byte - b'0');
+                    State::Digits
+                }
+                (State::Digits | State::TrailingWhitespace, byte) if byte.is_ascii_whitespace() => {
+                    State::TrailingWhitespace
+                }
+                _ => return Err(StreamParseError::InvalidByte { offset, byte }),
+            };
+            offset += 1;
+        }
+    }
+    if builder.digit_count() == 0 {
+        return Err(StreamParseError::NoDigits);
+    }
+    Ok(BigInt::from_biguint(sign, builder.build()))
+}
+
+/// Collects small writes into larger chunks and counts the written bytes.
+struct ChunkedWriter<'a, W: io::Write> {
+    inner: &'a mut W,
+    buffer: Vec<u8>,
+    written: usize,
+}
+
+impl<W: io::Write> ChunkedWriter<'_, W> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if self.buffer.len() + bytes.len() > BUFFER_SIZE {
+            self.flush()?;
+        }
+        self.buffer.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.write_all(&self.buffer)?;
+        self.written += self.buffer.len();
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+/// Writes a value below `powers[level]`, or below `10^CHUNK_DIGITS` at level zero, splitting it at `powers[level - 1]`.
+///
+/// With `pad` set the value is written with leading zeros to exactly `CHUNK_DIGITS * 2^level` digits.
+fn write_digits<W: io::Write>(
+    writer: &mut ChunkedWriter<'_, W>,
+    value: &BigUint,
+    powers: &[BigUint],
+    level: usize,
+    pad: bool,
+) -> io::Result<()> {
+    if level == 0 {
+        // the value is below 10^19 here, so it always fits into a u64
+        let chunk = value.to_u64().unwrap_or_default();
+        let digits = if pad {
+            format!("{chunk:0width$}", width = CHUNK_DIGITS as usize)
+        } else {
+            chunk.to_string()
+        };
+        return writer.write(digits.as_bytes());
+    }
+    let (high, low) = value.div_rem(&powers[level - 1]);
+    if !pad && high == BigUint::ZERO {
+        write_digits(writer, &low, powers, level - 1, false)
+    } else {
+        write_digits(writer, &high, powers, level - 1, pad)?;
+        write_digits(writer, &low, powers, level - 1, true)
+    }
+}
+
+/// Writes a [`num::BigInt`] in decimal without building the whole string in memory.
+///
+/// The value is split into 19 digit blocks by dividing by `10^(19 * 2^k)` in a divide and conquer fashion, so values
+/// with millions of digits are written quickly. The output is identical to `value.to_string()` and is passed to the
+/// writer in chunks of a few kilobytes.
+///
+/// Returns the number of bytes written. If the writer fails the error is returned and the output written so far is
+/// left as is.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::io::write_bigint_decimal;
+/// use num::BigInt;
+///
+/// let value = BigInt::from(i128::MIN) * 1000;
+/// let mut out = Vec::new();
+/// let written = write_bigint_decimal(&mut out, &value).unwrap();
+/// assert_eq!(out, value.to_string().into_bytes());
+/// assert_eq!(written, out.len());
+/// ```
+pub fn write_bigint_decimal<W: io::Write>(w: &mut W, value: &BigInt) -> io::Result<usize> {
+    let mut writer = ChunkedWriter {
+        inner: w,
+        buffer: Vec::with_capacity(BUFFER_SIZE),
+        written: 0,
+    };
+    if value.sign() == Sign::Minus {
+        writer.write(b"-")?;
+    }
+    let magnitude = value.magnitude();
+    let mut powers = vec![BigUint::from(CHUNK_BASE)];
+    while powers[powers.len() - 1] <= *magnitude {
+        let last = &powers[powers.len() - 1];
+        powers.push(last * last);
+    }
+    write_digits(&mut writer, magnitude, &powers, powers.len() - 1, false)?;
+    writer.flush()?;
+    Ok(writer.written)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use num::{BigInt, One};
+
+    use super::{read_bigint_decimal, write_bigint_decimal, StreamParseError};
+
+    fn ten_pow(exponent: u32) -> BigInt {
+        BigInt::from(10).pow(exponent)
+    }
+
+    /// Reader that hands out at most a few bytes per read, to exercise chunk boundaries
+    struct Trickle<'a>(&'a [u8]);
+
+    impl io::Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let len = self.0.len().min(buf.len()).min(3);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    /// Test a one million digit input against its closed form
+    #[test]
+    fn test_million_digits() {
+        let nines = vec![b'9'; 1_000_000];
+        assert_eq!(
+            read_bigint_decimal(&nines[..]).unwrap(),
+            ten_pow(1_000_000) - BigInt::one()
+        );
+        let mut pattern = b"-".to_vec();
+        pattern.extend(b"1234567890".repeat(100_000));
+        let expected: BigInt = (ten_pow(1_000_000) - 1) / (ten_pow(10) - 1) * 1234567890;
+        assert_eq!(read_bigint_decimal(&pattern[..]).unwrap(), -expected);
+    }
+
+    /// Test inputs against parse_bytes
+    #[test]
+    fn test_against_parse_bytes() {
+        let digits: Vec<u8> = (0..50_000)
+            .map(|i| b'0' + ((i * 7 + 3) % 10) as u8)
+            .collect();
+        let expected = BigInt::parse_bytes(&digits, 10).unwrap();
+        assert_eq!(read_bigint_decimal(&digits[..]).unwrap(), expected);
+        assert_eq!(read_bigint_decimal(Trickle(&digits)).unwrap(), expected);
+        for input in [
+            "0",
+            "-0",
+            "+17",
+            "-18446744073709551616",
+            "00000000000000000000042",
+        ] {
+            assert_eq!(
+                read_bigint_decimal(input.as_bytes()).unwrap(),
+                BigInt::parse_bytes(input.as_bytes(), 10).unwrap()
+            );
+        }
+    }
+
+    /// Test surrounding whitespace, including a trailing newline
+    #[test]
+    fn test_whitespace() {
+        assert_eq!(read_bigint_decimal(&b"42\n"[..]).unwrap(), BigInt::from(42));
+        assert_eq!(
+            read_bigint_decimal(&b"\t \r\n-42 \r\n"[..]).unwrap(),
+            BigInt::from(-42)
+        );
+    }
+
+    /// Test invalid bytes and their offsets
+    #[test]
+    fn test_invalid_byte() {
+        assert!(matches!(
+            read_bigint_decimal(&b"  12x4"[..]),
+            Err(StreamParseError::InvalidByte {
+                offset: 4,
+                byte: b'x'
+            })
+        ));
+        assert!(matches!(
+            read_bigint_decimal(&b"12 34"[..]),
+            Err(StreamParseError::InvalidByte {
+                offset: 3,
+                byte: b'3'
+            })
+        ));
+        assert!(matches!(
+            read_bigint_decimal(&b"- 1"[..]),
+            Err(StreamParseError::InvalidByte {
+                offset: 1,
+                byte: b' '
+            })
+        ));
+        assert!(matches!(
+            read_bigint_decimal(Trickle(b"123456-")),
+            Err(StreamParseError::InvalidByte {
+                offset: 6,
+                byte: b'-'
+            })
+        ));
+    }
+
+    /// Test a sign without digits and an empty reader
+    #[test]
+    fn test_no_digits() {
+        assert!(matches!(
+            read_bigint_decimal(&b"-"[..]),
+            Err(StreamParseError::NoDigits)
+        ));
+        assert!(matches!(
+            read_bigint_decimal(&b"+\n"[..]),
+            Err(StreamParseError::InvalidByte { .. })
+        ));
+        assert!(matches!(
+            read_bigint_decimal(&b""[..]),
+            Err(StreamParseError::NoDigits)
+        ));
+        assert!(matches!(
+            read_bigint_decimal(&b"   "[..]),
+            Err(StreamParseError::NoDigits)
+        ));
+    }
+
+    /// Test that read errors are passed through
+    #[test]
+    fn test_io_error() {
+        struct Failing;
+
+        impl io::Read for Failing {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::Error::other("broken pipe"))
+            }
+        }
+
+        assert!(matches!(
+            read_bigint_decimal(Failing),
+            Err(StreamParseError::Io(_))
+        ));
+    }
+
+    /// Writes a value and checks it against to_string
+    fn check_write(value: &BigInt) {
+        let mut out = Vec::new();
+        let written = write_bigint_decimal(&mut out, value).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), value.to_string());
+        assert_eq!(written, value.to_string().len());
+    }
+
+    /// Test writing zero and small values
+    #[test]
+    fn test_write_small() {
+        check_write(&BigInt::from(0));
+        check_write(&BigInt::from(7));
+        check_write(&BigInt::from(-7));
+        check_write(&BigInt::from(u64::MAX));
+        check_write(&BigInt::from(10_000_000_000_000_000_000_u64));
+        check_write(&BigInt::from(i128::MIN));
+        check_write(&BigInt::from(u128::MAX));
+    }
+
+    /// Test values with runs of zeros across block boundaries
+    #[test]
+    fn test_write_zero_blocks() {
+        for exponent in [19, 38, 57, 76, 100, 152, 1000] {
+            let power = ten_pow(exponent);
+            check_write(&power);
+            check_write(&(&power - 1_u8));
+            check_write(&(&power + 1_u8));
+            check_write(&-(&power * 3_u8 + 5_u8));
+        }
+    }
+
+    /// Test a 100k digit value byte for byte against to_string
+    #[test]
+    fn test_write_large() {
+        let digits: Vec<u8> = (0..100_000)
+            .map(|i| b'1' + ((i * 13 + 5) % 9) as u8)
+            .collect();
+        let value = read_bigint_decimal(&digits[..]).unwrap();
+        check_write(&value);
+        check_write(&-value);
+    }
+
+    /// Test that a failing writer returns an error instead of panicking
+    #[test]
+    fn test_write_error() {
+        /// Writer that accepts a limited number of bytes
+        struct Limited(usize);
+
+        impl io::Write for Limited {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                if self.0 == 0 {
+                    return Err(io::Error::other("disk full"));
+                }
+                let len = buf.len().min(self.0);
+                self.0 -= len;
+                Ok(len)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let value = ten_pow(50_000) - 1;
+        let error = write_bigint_decimal(&mut Limited(10_000), &value).unwrap_err();
+        assert_eq!(error.to_string(), "disk full");
+        assert!(write_bigint_decimal(&mut Limited(0), &BigInt::from(1)).is_err());
+        assert_eq!(
+            write_bigint_decimal(&mut Limited(5), &BigInt::from(-1234)).unwrap(),
+            5
+        );
+    }
+}
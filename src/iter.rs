@@ -0,0 +1,1058 @@
+use alloc::{
+    collections::{BTreeSet, BinaryHeap, VecDeque},
+    vec::Vec,
+};
+use core::{fmt, iter::FusedIterator, num::NonZeroUsize};
+
+use num::{BigInt, BigUint, Integer, One, Zero};
+
+use crate::{InfallibleToBigInt, InfallibleToBigUint};
+
+/// Sums values in an `i128` and only spills into a [`num::BigInt`] when that overflows.
+#[derive(Debug, Clone)]
+pub(crate) struct SumAccumulator {
+    fast: i128,
+    spill: BigInt,
+}
+
+impl SumAccumulator {
+    pub(crate) fn new() -> Self {
+        SumAccumulator {
+            fast: 0,
+            spill: BigInt::zero(),
+        }
+    }
+
+    /// Adds a value, without allocating if it and the running total fit into an `i128`.
+    pub(crate) fn add(&mut self, value: &impl InfallibleToBigInt) {
+        match value.to_i128_fast() {
+            Some(value) => self.add_i128(value),
+            None => self.spill += value.to_bigint(),
+        }
+    }
+
+    pub(crate) fn add_i128(&mut self, value: i128) {
+        match self.fast.checked_add(value) {
+            Some(sum) => self.fast = sum,
+            None => {
+                self.spill += self.fast;
+                self.fast = value;
+            }
+        }
+    }
+
+    pub(crate) fn add_bigint(&mut self, value: BigInt) {
+        self.spill += value;
+    }
+
+    pub(crate) fn finish(self) -> BigInt {
+        self.spill + self.fast
+    }
+}
+
+/// Converts every value of an iterator of results into a [`num::BigInt`], stopping at the first error.
+///
+/// No items are consumed after the first `Err`, which is returned unchanged.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::iter::try_to_bigints;
+/// use num::BigInt;
+///
+/// let values = try_to_bigints(["1", "-2"].iter().map(|s| s.parse::<i64>())).unwrap();
+/// assert_eq!(values, [BigInt::from(1), BigInt::from(-2)]);
+/// assert!(try_to_bigints(["1", "x"].iter().map(|s| s.parse::<i64>())).is_err());
+/// ```
+pub fn try_to_bigints<T: InfallibleToBigInt, E>(
+    iter: impl IntoIterator<Item = Result<T, E>>,
+) -> Result<Vec<BigInt>, E> {
+    iter.into_iter()
+        .map(|value| value.map(|value| value.to_bigint()))
+        .collect()
+}
+
+/// Converts every value of an iterator of results into a [`num::BigUint`], stopping at the first error.
+///
+/// No items are consumed after the first `Err`, which is returned unchanged.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::iter::try_to_biguints;
+/// use num::BigUint;
+///
+/// let values = try_to_biguints(["1", "2"].iter().map(|s| s.parse::<u64>())).unwrap();
+/// assert_eq!(values, [BigUint::from(1_u8), BigUint::from(2_u8)]);
+/// ```
+pub fn try_to_biguints<T: InfallibleToBigUint, E>(
+    iter: impl IntoIterator<Item = Result<T, E>>,
+) -> Result<Vec<BigUint>, E> {
+    iter.into_iter()
+        .map(|value| value.map(|value| value.to_biguint()))
+        .collect()
+}
+
+/// Converts every value of a deque into a [`num::BigInt`], keeping their order from front to back.
+///
+/// # Example
+/// ```
+/// use std::collections::VecDeque;
+///
+/// use infallible_tobigint::iter::vec_deque_to_bigints;
+/// use num::BigInt;
+///
+/// let deque = VecDeque::from([u64::MAX, 0]);
+/// assert_eq!(vec_deque_to_bigints(&deque), [BigInt::from(u64::MAX), BigInt::from(0)]);
+/// ```
+pub fn vec_deque_to_bigints<T: InfallibleToBigInt>(deque: &VecDeque<T>) -> VecDeque<BigInt> {
+    deque.iter().map(InfallibleToBigInt::to_bigint).collect()
+}
+
+/// Converts every value of a heap into a [`num::BigInt`], building a new heap of the converted values.
+///
+/// # Example
+/// ```
+/// use std::collections::BinaryHeap;
+///
+/// use infallible_tobigint::iter::binary_heap_to_bigints;
+/// use num::BigInt;
+///
+/// let mut heap = binary_heap_to_bigints(&BinaryHeap::from([3_u32, 7, 1]));
+/// assert_eq!(heap.pop(), Some(BigInt::from(7)));
+/// ```
+pub fn binary_heap_to_bigints<T: InfallibleToBigInt>(heap: &BinaryHeap<T>) -> BinaryHeap<BigInt> {
+    heap.iter().map(InfallibleToBigInt::to_bigint).collect()
+}
+
+/// Converts every value of a set into a [`num::BigInt`].
+///
+/// # Example
+/// ```
+/// use std::collections::BTreeSet;
+///
+/// use infallible_tobigint::iter::btree_set_to_bigints;
+/// use num::BigInt;
+///
+/// let set = btree_set_to_bigints(&BTreeSet::from([5_i8, -5]));
+/// assert_eq!(set.first(), Some(&BigInt::from(-5)));
+/// ```
+pub fn btree_set_to_bigints<T: InfallibleToBigInt>(set: &BTreeSet<T>) -> BTreeSet<BigInt> {
+    set.iter().map(InfallibleToBigInt::to_bigint).collect()
+}
+
+/// Sums the values of an iterator exactly.
+///
+/// Primitives are added in an `i128` as long as the running total fits, so no `BigInt` is allocated per element.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::iter::sum_as_bigint;
+/// use num::BigInt;
+///
+/// assert_eq!(sum_as_bigint([i128::MAX, i128::MAX]), BigInt::from(i128::MAX) * 2);
+/// ```
+pub fn sum_as_bigint<T: InfallibleToBigInt>(iter: impl IntoIterator<Item = T>) -> BigInt {
+    let mut sum = SumAccumulator::new();
+    for value in iter {
+        sum.add(&value);
+    }
+    sum.finish()
+}
+
+/// Sums the values of an iterator of results exactly, stopping at the first error.
+///
+/// Like [`sum_as_bigint`] this adds primitives without allocating. No items are consumed after the first `Err`,
+/// which is returned unchanged.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::iter::try_sum_as_bigint;
+/// use num::BigInt;
+///
+/// let lines = ["18446744073709551615", "18446744073709551615"];
+/// let total = try_sum_as_bigint(lines.iter().map(|line| line.parse::<u64>())).unwrap();
+/// assert_eq!(total, BigInt::from(u64::MAX) * 2);
+/// ```
+pub fn try_sum_as_bigint<T: InfallibleToBigInt, E>(
+    iter: impl IntoIterator<Item = Result<T, E>>,
+) -> Result<BigInt, E> {
+    let mut sum = SumAccumulator::new();
+    for value in iter {
+        sum.add(&value?);
+    }
+    Ok(sum.finish())
+}
+
+/// Computes the exact sum of every consecutive chunk of `chunk` values, including a final partial chunk.
+///
+/// Each sum uses the same `i128` fast path as [`sum_as_bigint`], so elements are not converted one by one.
+///
+/// # Example
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use infallible_tobigint::iter::chunk_sums_bigint;
+/// use num::BigInt;
+///
+/// let sums = chunk_sums_bigint(&[1, 2, 3, 4, 5], NonZeroUsize::new(2).unwrap());
+/// assert_eq!(sums, [BigInt::from(3), BigInt::from(7), BigInt::from(5)]);
+/// ```
+pub fn chunk_sums_bigint<T: InfallibleToBigInt>(data: &[T], chunk: NonZeroUsize) -> Vec<BigInt> {
+    data.chunks(chunk.get())
+        .map(|values| {
+            let mut sum = SumAccumulator::new();
+            for value in values {
+                sum.add(value);
+            }
+            sum.finish()
+        })
+        .collect()
+}
+
+/// Computes the exact sum of every window of `k` consecutive values, sliding by one value at a time.
+///
+/// Only the first window is summed in full. Every following sum is derived from the previous one by adding the
+/// value that enters the window and subtracting the one that leaves it. Slices shorter than `k` have no windows.
+///
+/// # Example
+/// ```
+/// use std::num::NonZeroUsize;
+///
+/// use infallible_tobigint::iter::rolling_sums_bigint;
+/// use num::BigInt;
+///
+/// let sums = rolling_sums_bigint(&[1, 2, 3, 4], NonZeroUsize::new(3).unwrap());
+/// assert_eq!(sums, [BigInt::from(6), BigInt::from(9)]);
+/// ```
+pub fn rolling_sums_bigint<T: InfallibleToBigInt>(data: &[T], k: NonZeroUsize) -> Vec<BigInt> {
+    let k = k.get();
+    if data.len() < k {
+        return Vec::new();
+    }
+    let mut first = SumAccumulator::new();
+    for value in &data[..k] {
+        first.add(value);
+    }
+    let mut sums = Vec::with_capacity(data.len() - k + 1);
+    let mut window = first.finish();
+    for (leaving, entering) in data.iter().zip(&data[k..]) {
+        let mut next = window.clone();
+        match (entering.to_i128_fast(), leaving.to_i128_fast()) {
+            (Some(entering), Some(leaving)) => {
+                next += entering;
+                next -= leaving;
+            }
+            _ => {
+                next += entering.to_bigint();
+                next -= leaving.to_bigint();
+            }
+        }
+        sums.push(window);
+        window = next;
+    }
+    sums.push(window);
+    sums
+}
+
+/// Computes the exact average of the values as a floored quotient and a non-negative remainder.
+///
+/// The result `(quotient, remainder)` satisfies `quotient * values.len() + remainder == sum` with
+/// `remainder < values.len()`, so negative sums round towards negative infinity. Returns `None` for an empty slice.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::iter::mean_div_rem;
+/// use num::{BigInt, BigUint};
+///
+/// assert_eq!(mean_div_rem(&[-3, 0]), Some((BigInt::from(-2), BigUint::from(1_u8))));
+/// assert_eq!(mean_div_rem::<u8>(&[]), None);
+/// ```
+pub fn mean_div_rem<T: InfallibleToBigInt>(values: &[T]) -> Option<(BigInt, BigUint)> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sum = SumAccumulator::new();
+    for value in values {
+        sum.add(value);
+    }
+    let (quotient, remainder) = sum.finish().div_mod_floor(&BigInt::from(values.len()));
+    Some((quotient, remainder.into_parts().1))
+}
+
+/// Error returned when two slices that are combined element by element differ in length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LengthMismatch {
+    /// The length of the values.
+    pub values: usize,
+    /// The length of the weights.
+    pub weights: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "got {} values but {} weights", self.values, self.weights)
+    }
+}
+
+impl core::error::Error for LengthMismatch {}
+
+/// Computes the exact weighted sum `values[0] * weights[0] + values[1] * weights[1] + ...`.
+///
+/// Each product is taken in `i128` if both factors and the product fit, and the running total uses the same `i128`
+/// fast path as [`sum_as_bigint`]. Only products or totals beyond `i128` allocate a `BigInt`.
+///
+/// Returns an error if the slices have different lengths.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::iter::weighted_sum_bigint;
+/// use num::BigInt;
+///
+/// let sum = weighted_sum_bigint(&[i64::MIN, 3], &[-1_i64, 2]).unwrap();
+/// assert_eq!(sum, -BigInt::from(i64::MIN) + 6);
+/// assert!(weighted_sum_bigint(&[1_u8], &[1_u8, 2]).is_err());
+/// ```
+pub fn weighted_sum_bigint<V: InfallibleToBigInt, W: InfallibleToBigInt>(
+    values: &[V],
+    weights: &[W],
+) -> Result<BigInt, LengthMismatch> {
+    if values.len() != weights.len() {
+        return Err(LengthMismatch {
+            values: values.len(),
+            weights: weights.len(),
+        });
+    }
+    let mut sum = SumAccumulator::new();
+    for (value, weight) in values.iter().zip(weights) {
+        let product = match (value.to_i128_fast(), weight.to_i128_fast()) {
+            (Some(value), Some(weight)) => value.checked_mul(weight),
+            _ => None,
+        };
+        match product {
+            Some(product) => sum.add_i128(product),
+            None => sum.add_bigint(value.to_bigint() * weight.to_bigint()),
+        }
+    }
+    Ok(sum.finish())
+}
+
+/// Extension methods for iterators of values that convert to bignums.
+///
+/// Unlike `map(|value| value.to_bigint())`, the adapters name the conversion they use, so they are not ambiguous
+/// with num's own `ToBigInt`. They keep the size hint of the underlying iterator and are double ended or exact size
+/// whenever it is, so `collect` allocates the right capacity up front.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::iter::ToBigIntIterExt;
+/// use num::{BigInt, BigUint};
+///
+/// let values: Vec<BigInt> = [-1_i8, 2].into_iter().to_bigints().rev().collect();
+/// assert_eq!(values, [BigInt::from(2), BigInt::from(-1)]);
+/// assert_eq!([u64::MAX].iter().to_biguints().next(), Some(BigUint::from(u64::MAX)));
+/// ```
+pub trait ToBigIntIterExt: Iterator + Sized {
+    /// Converts every item into a [`num::BigInt`].
+    fn to_bigints(self) -> ToBigInts<Self>
+    where
+        Self::Item: InfallibleToBigInt,
+    {
+        ToBigInts { iter: self }
+    }
+
+    /// Converts every item into a [`num::BigUint`].
+    fn to_biguints(self) -> ToBigUints<Self>
+    where
+        Self::Item: InfallibleToBigUint,
+    {
+        ToBigUints { iter: self }
+    }
+
+    /// Sums the items exactly, like [`sum_as_bigint`].
+    ///
+    /// Primitives are added in an `i128` as long as the running total fits, so only the total is a bignum. An empty
+    /// iterator sums to 0.
+    fn sum_bigint(self) -> BigInt
+    where
+        Self::Item: InfallibleToBigInt,
+    {
+        sum_as_bigint(self)
+    }
+
+    /// Multiplies the items exactly.
+    ///
+    /// Primitives are multiplied in an `i128` as long as the partial product fits, and only then multiplied into the
+    /// single growing bignum. An empty iterator has the product 1, and no items are consumed after a zero.
+    fn product_bigint(self) -> BigInt
+    where
+        Self::Item: InfallibleToBigInt,
+    {
+        let mut fast: i128 = 1;
+        let mut spill = BigInt::one();
+        for value in self {
+            match value.to_i128_fast() {
+                Some(0) => return BigInt::zero(),
+                Some(value) => match fast.checked_mul(value) {
+                    Some(product) => fast = product,
+                    None => {
+                        spill *= fast;
+                        fast = value;
+                    }
+                },
+                None => {
+                    let value = value.to_bigint();
+                    if value.is_zero() {
+                        return value;
+                    }
+                    spill *= value;
+                }
+            }
+        }
+        spill * fast
+    }
+}
+
+impl<I: Iterator> ToBigIntIterExt for I {}
+
+/// Iterator returned by [`ToBigIntIterExt::to_bigints`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ToBigInts<I> {
+    iter: I,
+}
+
+/// Iterator returned by [`ToBigIntIterExt::to_biguints`].
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ToBigUints<I> {
+    iter: I,
+}
+
+macro_rules! impl_conversion_adapter {
+    ($adapter:ident, $bound:ident, $target:ident, $convert:ident) => {
+        impl<I: Iterator> Iterator for $adapter<I>
+        where
+            I::Item: $bound,
+        {
+            type Item = $target;
+
+            fn next(&mut self) -> Option<$target> {
+                self.iter.next().map(|value| value.$convert())
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.iter.size_hint()
+            }
+        }
+
+        impl<I: DoubleEndedIterator> DoubleEndedIterator for $adapter<I>
+        where
+            I::Item: $bound,
+        {
+            fn next_back(&mut self) -> Option<$target> {
+                self.iter.next_back().map(|value| value.$convert())
+            }
+        }
+
+        impl<I: ExactSizeIterator> ExactSizeIterator for $adapter<I>
+        where
+            I::Item: $bound,
+        {
+            fn len(&self) -> usize {
+                self.iter.len()
+            }
+        }
+
+        impl<I: FusedIterator> FusedIterator for $adapter<I> where I::Item: $bound {}
+    };
+}
+
+impl_conversion_adapter!(ToBigInts, InfallibleToBigInt, BigInt, to_bigint);
+impl_conversion_adapter!(ToBigUints, InfallibleToBigUint, BigUint, to_biguint);
+
+/// Batch conversion of slices into vectors of [`num::BigInt`].
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::iter::ToBigIntSlice;
+/// use num::BigInt;
+///
+/// let samples = [u64::MAX, 1];
+/// assert_eq!(samples.to_bigints(), [BigInt::from(u64::MAX), BigInt::from(1)]);
+///
+/// // reuse one vector across batches
+/// let mut out = Vec::new();
+/// for batch in [[1_u64, 2], [3, 4]] {
+///     batch.to_bigints_into(&mut out);
+/// }
+/// assert_eq!(out, [BigInt::from(3), BigInt::from(4)]);
+/// ```
+pub trait ToBigIntSlice {
+    /// Converts every element, allocating the vector with the length of the slice up front.
+    fn to_bigints(&self) -> Vec<BigInt>;
+
+    /// Replaces the contents of `out` with the converted elements, reusing its allocation.
+    ///
+    /// This is the variant for hot loops that convert many slices, since `out` only grows when a slice is longer
+    /// than every one before it.
+    fn to_bigints_into(&self, out: &mut Vec<BigInt>);
+}
+
+impl<T: InfallibleToBigInt> ToBigIntSlice for [T] {
+    fn to_bigints(&self) -> Vec<BigInt> {
+        let mut out = Vec::with_capacity(self.len());
+        for value in self {
+            out.push(value.to_bigint());
+        }
+        out
+    }
+
+    fn to_bigints_into(&self, out: &mut Vec<BigInt>) {
+        out.clear();
+        out.reserve(self.len());
+        for value in self {
+            out.push(value.to_bigint());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::Cell,
+        collections::{BTreeSet, BinaryHeap, VecDeque},
+        num::NonZeroUsize,
+    };
+
+    use num::{BigInt, BigUint, Zero};
+
+    use super::{
+        binary_heap_to_bigints, btree_set_to_bigints, chunk_sums_bigint, mean_div_rem,
+        rolling_sums_bigint, sum_as_bigint, try_sum_as_bigint, try_to_bigints, try_to_biguints,
+        vec_deque_to_bigints, weighted_sum_bigint, LengthMismatch, ToBigIntIterExt, ToBigIntSlice,
+    };
+
+    /// Error type that is compared by the address of its allocation
+    #[derive(Debug)]
+    struct Unique(Box<u32>);
+
+    /// Yields the given items while counting how many were consumed
+    fn counting<'a, T: Clone>(
+        items: &'a [T],
+        consumed: &'a Cell<usize>,
+    ) -> impl Iterator<Item = T> + 'a {
+        items.iter().map(move |item| {
+            consumed.set(consumed.get() + 1);
+            item.clone()
+        })
+    }
+
+    /// Test inputs without errors
+    #[test]
+    fn test_all_ok() {
+        let items: [Result<i64, ()>; 3] = [Ok(i64::MIN), Ok(0), Ok(i64::MAX)];
+        assert_eq!(
+            try_to_bigints(items),
+            Ok(vec![
+                BigInt::from(i64::MIN),
+                BigInt::from(0),
+                BigInt::from(i64::MAX)
+            ])
+        );
+        let items: [Result<u128, ()>; 2] = [Ok(u128::MAX), Ok(1)];
+        assert_eq!(
+            try_to_biguints(items),
+            Ok(vec![BigUint::from(u128::MAX), BigUint::from(1_u8)])
+        );
+    }
+
+    /// Test that no items are consumed after an error
+    #[test]
+    fn test_short_circuit() {
+        let items = [Ok(1_u32), Ok(2), Err("bad"), Ok(4), Ok(5)];
+        let consumed = Cell::new(0);
+        assert_eq!(try_to_bigints(counting(&items, &consumed)), Err("bad"));
+        assert_eq!(consumed.get(), 3);
+        consumed.set(0);
+        assert_eq!(try_to_biguints(counting(&items, &consumed)), Err("bad"));
+        assert_eq!(consumed.get(), 3);
+    }
+
+    /// Test empty inputs
+    #[test]
+    fn test_empty() {
+        assert_eq!(try_to_bigints(Vec::<Result<i8, ()>>::new()), Ok(vec![]));
+        assert_eq!(try_to_biguints(Vec::<Result<u8, ()>>::new()), Ok(vec![]));
+    }
+
+    /// Test that the error value is passed through unchanged
+    #[test]
+    fn test_error_identity() {
+        let error = Unique(Box::new(7));
+        let address: *const u32 = &*error.0;
+        let items = vec![Ok(1_i16), Err(error), Err(Unique(Box::new(8)))];
+        let returned = try_to_bigints(items).unwrap_err();
+        assert_eq!(&*returned.0 as *const u32, address);
+        assert_eq!(*returned.0, 7);
+    }
+
+    /// Test that summing stops consuming at the first error
+    #[test]
+    fn test_try_sum_short_circuit() {
+        let items = [Ok(i128::MAX), Ok(i128::MAX), Err("bad"), Ok(1)];
+        let consumed = Cell::new(0);
+        assert_eq!(try_sum_as_bigint(counting(&items, &consumed)), Err("bad"));
+        assert_eq!(consumed.get(), 3);
+    }
+
+    /// Test sums exceeding the i128 range in both directions
+    #[test]
+    fn test_sum_beyond_i128() {
+        let items: Vec<Result<i128, ()>> = vec![Ok(i128::MAX); 5];
+        assert_eq!(try_sum_as_bigint(items), Ok(BigInt::from(i128::MAX) * 5));
+        let items = [i128::MIN, i128::MIN, -1, i128::MAX, i128::MIN];
+        assert_eq!(sum_as_bigint(items), BigInt::from(i128::MIN) * 2 - 2);
+        let items = [u128::MAX, u128::MAX, 1];
+        assert_eq!(sum_as_bigint(items), BigInt::from(u128::MAX) * 2 + 1);
+    }
+
+    /// Test empty iterators
+    #[test]
+    fn test_sum_empty() {
+        assert_eq!(
+            try_sum_as_bigint(Vec::<Result<i64, ()>>::new()),
+            Ok(BigInt::zero())
+        );
+        assert_eq!(sum_as_bigint(Vec::<u8>::new()), BigInt::zero());
+    }
+
+    /// Test that try_sum_as_bigint matches sum_as_bigint and a BigInt fold without errors
+    #[test]
+    fn test_sum_parity() {
+        let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+        let values: Vec<i128> = (0..1000)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state as i128) << 64 | state.rotate_left(32) as i128
+            })
+            .collect();
+        let expected: BigInt = values.iter().map(|&value| BigInt::from(value)).sum();
+        assert_eq!(sum_as_bigint(values.iter().copied()), expected);
+        assert_eq!(
+            try_sum_as_bigint(values.iter().map(|&value| Ok::<_, ()>(value))),
+            Ok(expected)
+        );
+    }
+
+    /// Shorthand for a non-zero chunk size
+    fn size(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).unwrap()
+    }
+
+    /// Test chunks of a single element
+    #[test]
+    fn test_chunk_size_one() {
+        let data = [i64::MIN, 0, i64::MAX];
+        let expected: Vec<BigInt> = data.iter().map(|&value| BigInt::from(value)).collect();
+        assert_eq!(chunk_sums_bigint(&data, size(1)), expected);
+    }
+
+    /// Test a chunk size larger than the slice, and an empty slice
+    #[test]
+    fn test_chunk_larger_than_slice() {
+        assert_eq!(
+            chunk_sums_bigint(&[1_u8, 2, 3], size(10)),
+            [BigInt::from(6)]
+        );
+        assert!(chunk_sums_bigint::<u8>(&[], size(3)).is_empty());
+    }
+
+    /// Test exact multiples of the chunk size and ragged tails
+    #[test]
+    fn test_chunk_tails() {
+        let data: Vec<u32> = (1..=12).collect();
+        assert_eq!(
+            chunk_sums_bigint(&data, size(4)),
+            [BigInt::from(10), BigInt::from(26), BigInt::from(42)]
+        );
+        assert_eq!(
+            chunk_sums_bigint(&data, size(5)),
+            [BigInt::from(15), BigInt::from(40), BigInt::from(23)]
+        );
+    }
+
+    /// Test sums overflowing i128 within a single chunk
+    #[test]
+    fn test_chunk_overflow() {
+        let data = [i128::MAX, i128::MAX, i128::MIN, i128::MIN, i128::MIN, 7];
+        assert_eq!(
+            chunk_sums_bigint(&data, size(2)),
+            [
+                BigInt::from(i128::MAX) * 2,
+                BigInt::from(i128::MIN) * 2,
+                BigInt::from(i128::MIN) + 7
+            ]
+        );
+        let data = [u128::MAX; 3];
+        assert_eq!(
+            chunk_sums_bigint(&data, size(3)),
+            [BigInt::from(u128::MAX) * 3]
+        );
+    }
+
+    /// Test that the chunk sums add up to the total sum
+    #[test]
+    fn test_chunk_total() {
+        let data: Vec<i128> = (0..101)
+            .map(|i| {
+                if i % 3 == 0 {
+                    i128::MIN + i
+                } else {
+                    i128::MAX - i
+                }
+            })
+            .collect();
+        let total = sum_as_bigint(data.iter().copied());
+        for chunk in [1, 2, 7, 50, 101, 200] {
+            let sums = chunk_sums_bigint(&data, size(chunk));
+            assert_eq!(sums.len(), data.len().div_ceil(chunk));
+            assert_eq!(sums.into_iter().sum::<BigInt>(), total);
+        }
+    }
+
+    /// Test slices of different lengths
+    #[test]
+    fn test_weighted_length_mismatch() {
+        assert_eq!(
+            weighted_sum_bigint(&[1_i64, 2, 3], &[1_i64]),
+            Err(LengthMismatch {
+                values: 3,
+                weights: 1
+            })
+        );
+        assert!(weighted_sum_bigint::<u8, u8>(&[], &[1]).is_err());
+    }
+
+    /// Test empty slices
+    #[test]
+    fn test_weighted_empty() {
+        assert_eq!(
+            weighted_sum_bigint::<i64, i64>(&[], &[]),
+            Ok(BigInt::zero())
+        );
+    }
+
+    /// Test i64::MIN values with weight -1, whose products and sum overflow i64
+    #[test]
+    fn test_weighted_min() {
+        let values = [i64::MIN; 1000];
+        let weights = [-1_i64; 1000];
+        assert_eq!(
+            weighted_sum_bigint(&values, &weights),
+            Ok(-BigInt::from(i64::MIN) * 1000)
+        );
+        let values = [i128::MIN; 3];
+        assert_eq!(
+            weighted_sum_bigint(&values, &[-1_i8; 3]),
+            Ok(-BigInt::from(i128::MIN) * 3)
+        );
+    }
+
+    /// Test against an all BigInt reference on pseudo random data
+    #[test]
+    fn test_weighted_random() {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for len in [1, 10, 500] {
+            let values: Vec<i64> = (0..len).map(|_| next() as i64).collect();
+            let weights: Vec<u128> = (0..len)
+                .map(|_| (next() as u128) << (next() % 64))
+                .collect();
+            let expected: BigInt = values
+                .iter()
+                .zip(&weights)
+                .map(|(&value, &weight)| BigInt::from(value) * BigInt::from(weight))
+                .sum();
+            assert_eq!(weighted_sum_bigint(&values, &weights), Ok(expected));
+        }
+    }
+
+    /// Checks `quotient * n + remainder == sum` and `remainder < n` for a mean_div_rem result
+    fn check_mean(values: &[i128], quotient: &BigInt, remainder: &BigUint) {
+        let n = BigInt::from(values.len());
+        let sum: BigInt = values.iter().map(|&value| BigInt::from(value)).sum();
+        assert_eq!(quotient * &n + BigInt::from(remainder.clone()), sum);
+        assert!(BigInt::from(remainder.clone()) < n);
+    }
+
+    /// Test averages that divide exactly
+    #[test]
+    fn test_mean_exact() {
+        assert_eq!(
+            mean_div_rem(&[2_u8, 4, 6]),
+            Some((BigInt::from(4), BigUint::zero()))
+        );
+        assert_eq!(
+            mean_div_rem(&[-5_i32, -7]),
+            Some((BigInt::from(-6), BigUint::zero()))
+        );
+        assert_eq!(mean_div_rem::<i64>(&[]), None);
+    }
+
+    /// Test that negative sums are floored with a non-negative remainder
+    #[test]
+    fn test_mean_negative_floor() {
+        let values = [-7_i128, 0, 0];
+        let (quotient, remainder) = mean_div_rem(&values).unwrap();
+        assert_eq!(quotient, BigInt::from(-3));
+        assert_eq!(remainder, BigUint::from(2_u8));
+        check_mean(&values, &quotient, &remainder);
+        let values = [-1_i128, 0];
+        let (quotient, remainder) = mean_div_rem(&values).unwrap();
+        assert_eq!(quotient, BigInt::from(-1));
+        assert_eq!(remainder, BigUint::from(1_u8));
+        check_mean(&values, &quotient, &remainder);
+    }
+
+    /// Test single element slices, which are their own mean
+    #[test]
+    fn test_mean_single() {
+        for value in [i128::MIN, -1, 0, 1, i128::MAX] {
+            assert_eq!(
+                mean_div_rem(&[value]),
+                Some((BigInt::from(value), BigUint::zero()))
+            );
+        }
+    }
+
+    /// Test sums beyond the i128 range in both directions
+    #[test]
+    fn test_mean_beyond_i128() {
+        for values in [
+            vec![i128::MAX, i128::MAX, 1],
+            vec![i128::MIN, i128::MIN, i128::MIN, -2],
+            vec![i128::MAX, i128::MAX, i128::MIN, 5, 4],
+        ] {
+            let (quotient, remainder) = mean_div_rem(&values).unwrap();
+            check_mean(&values, &quotient, &remainder);
+        }
+        let (quotient, remainder) = mean_div_rem(&[u128::MAX, u128::MAX, 1]).unwrap();
+        assert_eq!(quotient, (BigInt::from(u128::MAX) * 2 + 1_u8) / 3_u8);
+        assert_eq!(remainder, BigUint::from(1_u8));
+    }
+
+    /// Test a deque whose contents wrap around the end of its buffer
+    #[test]
+    fn test_vec_deque_wrapped() {
+        let mut deque = VecDeque::with_capacity(4);
+        deque.extend([0_u64; 3]);
+        deque.drain(..3);
+        deque.extend([u64::MAX, 1, 2]);
+        deque.push_front(3);
+        assert!(!deque.as_slices().1.is_empty());
+        let expected: VecDeque<BigInt> = deque.iter().map(|&value| BigInt::from(value)).collect();
+        assert_eq!(vec_deque_to_bigints(&deque), expected);
+        assert_eq!(
+            vec_deque_to_bigints(&deque),
+            [3, u64::MAX, 1, 2].map(BigInt::from)
+        );
+    }
+
+    /// Test that the converted heap pops values in the same numeric order
+    #[test]
+    fn test_binary_heap_order() {
+        let values = [i64::MIN, 5, -5, i64::MAX, 0, 5];
+        let mut heap = BinaryHeap::from(values);
+        let mut converted = binary_heap_to_bigints(&heap);
+        assert_eq!(converted.len(), values.len());
+        while let Some(value) = heap.pop() {
+            assert_eq!(converted.pop(), Some(BigInt::from(value)));
+        }
+        assert!(converted.is_empty());
+    }
+
+    /// Test that the converted set keeps negative values before positive ones
+    #[test]
+    fn test_btree_set_order() {
+        let set = BTreeSet::from([i128::MAX, -1, 1, i128::MIN, 0]);
+        let converted = btree_set_to_bigints(&set);
+        assert!(converted.iter().eq(set
+            .iter()
+            .map(|&value| BigInt::from(value))
+            .collect::<Vec<_>>()
+            .iter()));
+        assert_eq!(converted.first(), Some(&BigInt::from(i128::MIN)));
+        assert_eq!(converted.last(), Some(&BigInt::from(i128::MAX)));
+    }
+
+    /// Test that windows of one value are the converted values
+    #[test]
+    fn test_rolling_one() {
+        let data = [i64::MIN, -1, 0, 1, i64::MAX];
+        let expected: Vec<BigInt> = data.iter().map(|&value| BigInt::from(value)).collect();
+        assert_eq!(rolling_sums_bigint(&data, NonZeroUsize::MIN), expected);
+    }
+
+    /// Test windows as long as the slice, and longer
+    #[test]
+    fn test_rolling_whole() {
+        let data = [u128::MAX; 4];
+        let [len, longer] = [4, 5].map(|k| NonZeroUsize::new(k).unwrap());
+        assert_eq!(
+            rolling_sums_bigint(&data, len),
+            [BigInt::from(u128::MAX) * 4_u8]
+        );
+        assert!(rolling_sums_bigint(&data, longer).is_empty());
+        assert!(rolling_sums_bigint::<u8>(&[], len).is_empty());
+    }
+
+    /// Test the incremental sums against summing every window, on mixed sign data beyond i128
+    #[test]
+    fn test_rolling_parity() {
+        let data: Vec<i128> = (0..200_i128)
+            .map(|i| match i % 5 {
+                0 => i128::MAX,
+                1 => i128::MIN,
+                2 => i128::MAX - i,
+                3 => -i,
+                _ => i128::MIN + i,
+            })
+            .collect();
+        for k in [1, 2, 3, 7, 64, 200] {
+            let k = NonZeroUsize::new(k).unwrap();
+            let expected: Vec<BigInt> = data
+                .windows(k.get())
+                .map(|window| sum_as_bigint(window.iter().copied()))
+                .collect();
+            assert_eq!(rolling_sums_bigint(&data, k), expected);
+        }
+        let data = [u128::MAX, 0, u128::MAX, 3];
+        let two = NonZeroUsize::new(2).unwrap();
+        let expected: Vec<BigInt> = data
+            .windows(2)
+            .map(|window| sum_as_bigint(window.iter().copied()))
+            .collect();
+        assert_eq!(rolling_sums_bigint(&data, two), expected);
+    }
+
+    /// Test the adapters on empty iterators
+    #[test]
+    fn test_adapter_empty() {
+        assert_eq!(core::iter::empty::<i8>().to_bigints().next(), None);
+        assert_eq!(
+            core::iter::empty::<u8>().to_biguints().size_hint(),
+            (0, Some(0))
+        );
+        assert_eq!(Vec::<u8>::new().iter().to_biguints().len(), 0);
+    }
+
+    /// Test that the adapters agree with converting one value at a time, from both ends
+    #[test]
+    fn test_adapter_values() {
+        let values = [i128::MIN, -1, 0, 1, i128::MAX];
+        let expected: Vec<BigInt> = values.iter().map(|&value| BigInt::from(value)).collect();
+        assert_eq!(values.iter().to_bigints().collect::<Vec<_>>(), expected);
+        let reversed: Vec<BigInt> = values.iter().to_bigints().rev().collect();
+        assert_eq!(reversed, expected.iter().rev().cloned().collect::<Vec<_>>());
+        let mut both = values.into_iter().to_bigints();
+        assert_eq!(both.next(), Some(BigInt::from(i128::MIN)));
+        assert_eq!(both.next_back(), Some(BigInt::from(i128::MAX)));
+        assert_eq!(both.len(), 3);
+        let unsigned: Vec<BigUint> = [u128::MAX, 0].iter().to_biguints().rev().collect();
+        assert_eq!(unsigned, [BigUint::zero(), BigUint::from(u128::MAX)]);
+    }
+
+    /// Test that the adapters keep the size hint, so collecting allocates once
+    #[test]
+    fn test_adapter_size_hint() {
+        let adapter = (0_u32..1000).to_bigints();
+        assert_eq!(adapter.size_hint(), (1000, Some(1000)));
+        assert_eq!(adapter.collect::<Vec<_>>().capacity(), 1000);
+        let filtered = (0_u32..10).filter(|value| value % 2 == 0).to_biguints();
+        assert_eq!(filtered.size_hint(), (0, Some(10)));
+    }
+
+    /// Test the sum of u64::MAX a thousand times against the closed form
+    #[test]
+    fn test_sum_bigint() {
+        let sum = core::iter::repeat_n(u64::MAX, 1000).sum_bigint();
+        assert_eq!(sum, BigInt::from(u64::MAX) * 1000);
+        assert_eq!(sum, (BigInt::from(1) << 64_u32) * 1000 - 1000);
+        assert_eq!(core::iter::empty::<i8>().sum_bigint(), BigInt::zero());
+        assert_eq!(
+            [i128::MIN, i128::MIN].iter().sum_bigint(),
+            BigInt::from(i128::MIN) * 2
+        );
+    }
+
+    /// Test products within and beyond the i128 range
+    #[test]
+    fn test_product_bigint() {
+        assert_eq!(core::iter::empty::<u8>().product_bigint(), BigInt::from(1));
+        assert_eq!([-2_i8, 3, -4].iter().product_bigint(), BigInt::from(24));
+        let product = core::iter::repeat_n(u64::MAX, 10).product_bigint();
+        assert_eq!(product, num::pow(BigInt::from(u64::MAX), 10));
+        let mixed = [BigInt::from(u128::MAX) * 3, BigInt::from(-1)];
+        assert_eq!(mixed.iter().product_bigint(), BigInt::from(u128::MAX) * -3);
+        let factorial = (1_u32..=30).product_bigint();
+        assert_eq!(factorial.to_string(), "265252859812191058636308480000000");
+    }
+
+    /// Test that no items are consumed after a zero factor
+    #[test]
+    fn test_product_short_circuit() {
+        let items = [5_u64, u64::MAX, 0, 7, 9];
+        let consumed = Cell::new(0);
+        assert_eq!(counting(&items, &consumed).product_bigint(), BigInt::zero());
+        assert_eq!(consumed.get(), 3);
+        let items = [
+            BigInt::from(u128::MAX) << 10_u32,
+            BigInt::zero(),
+            BigInt::from(2),
+        ];
+        consumed.set(0);
+        assert_eq!(counting(&items, &consumed).product_bigint(), BigInt::zero());
+        assert_eq!(consumed.get(), 2);
+    }
+
+    /// Test slice conversion of empty slices
+    #[test]
+    fn test_slice_empty() {
+        let empty: &[i64] = &[];
+        assert!(empty.to_bigints().is_empty());
+        let mut out = vec![BigInt::from(1)];
+        empty.to_bigints_into(&mut out);
+        assert!(out.is_empty());
+    }
+
+    /// Test that slice conversion agrees with converting one value at a time
+    #[test]
+    fn test_slice_values() {
+        let values: Vec<i128> = (0..100).map(|i| (i - 50) * (i128::MAX / 64)).collect();
+        let expected: Vec<BigInt> = values.iter().map(|&value| BigInt::from(value)).collect();
+        let converted = values.to_bigints();
+        assert_eq!(converted, expected);
+        assert_eq!(converted.capacity(), values.len());
+        let mut out = Vec::new();
+        values.to_bigints_into(&mut out);
+        assert_eq!(out, expected);
+    }
+
+    /// Test that converting into a vector reuses its allocation
+    #[test]
+    fn test_slice_into_reuse() {
+        let mut out = Vec::with_capacity(16);
+        let buffer = out.as_ptr();
+        [u64::MAX; 16].to_bigints_into(&mut out);
+        assert_eq!(out, vec![BigInt::from(u64::MAX); 16]);
+        [7_u64; 3].to_bigints_into(&mut out);
+        assert_eq!(out, vec![BigInt::from(7); 3]);
+        assert_eq!(out.as_ptr(), buffer);
+    }
+}
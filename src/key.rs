@@ -0,0 +1,413 @@
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use core::hash::BuildHasher;
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use num::{bigint::Sign, BigInt, ToPrimitive};
+
+use crate::{ops::sealed, InfallibleToBigInt};
+
+/// The digit type num-bigint stores a `BigUint` in, which determines how it is hashed.
+#[cfg(target_pointer_width = "64")]
+type Digit = u64;
+#[cfg(not(target_pointer_width = "64"))]
+type Digit = u32;
+
+/// The number of digits needed for a `u128`.
+const DIGITS: usize = (u128::BITS / Digit::BITS) as usize;
+
+/// A primitive integer that can be looked up in maps keyed by [`num::BigInt`] without allocating.
+///
+/// Maps keyed by `BigInt` can be queried with `&dyn BigIntLookup`, which every `BigInt` borrows as. A `BigIntKey`
+/// hashes identically to the `BigInt` of the same value and compares equal and ordered consistently with it, so
+/// [`HashMap::get`] and [`BTreeMap::get`] find the same entries as with a converted key.
+///
+/// The hash mirrors the `Hash` impl of num-bigint, which hashes the sign and the digit vector. That layout is not
+/// part of the num-bigint API, and a release that changed it would make `HashMap` lookups through a `BigIntKey` miss
+/// entries that are there. [`hash_map_get`] checks a miss with a converted key, and the ordering that `BTreeMap` and
+/// [`btree_map_get`] use only depends on the values.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+///
+/// use infallible_tobigint::key::{BigIntKey, BigIntLookup};
+/// use num::BigInt;
+///
+/// let mut cache = HashMap::new();
+/// cache.insert(BigInt::from(u64::MAX), "max");
+///
+/// let key = BigIntKey::from(u64::MAX);
+/// assert_eq!(cache.get(&key as &dyn BigIntLookup), Some(&"max"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigIntKey {
+    sign: Sign,
+    magnitude: u128,
+}
+
+impl BigIntKey {
+    /// Creates a key from a sign and a magnitude, normalizing the sign of zero like `BigInt` does.
+    fn new(sign: Sign, magnitude: u128) -> Self {
+        let sign = if magnitude == 0 { Sign::NoSign } else { sign };
+        BigIntKey { sign, magnitude }
+    }
+}
+
+impl From<i128> for BigIntKey {
+    fn from(value: i128) -> Self {
+        BigIntKey::new(
+            if value < 0 { Sign::Minus } else { Sign::Plus },
+            value.unsigned_abs(),
+        )
+    }
+}
+
+impl From<u128> for BigIntKey {
+    fn from(value: u128) -> Self {
+        BigIntKey::new(Sign::Plus, value)
+    }
+}
+
+macro_rules! impl_key_from {
+    ($wide:ty => $($prim:ty),*) => {$(
+        impl From<$prim> for BigIntKey {
+            fn from(value: $prim) -> Self {
+                BigIntKey::from(value as $wide)
+            }
+        }
+    )*};
+}
+
+impl_key_from!(i128 => i8, i16, i32, i64, isize);
+impl_key_from!(u128 => u8, u16, u32, u64, usize);
+
+/// Common view of [`num::BigInt`] and [`BigIntKey`] that maps keyed by `BigInt` can be queried with.
+///
+/// This trait is sealed, its methods are an implementation detail of the `Hash`, `Eq` and `Ord` impls on
+/// `dyn BigIntLookup`.
+pub trait BigIntLookup: sealed::Sealed {
+    #[doc(hidden)]
+    fn lookup_sign(&self) -> Sign;
+
+    /// The magnitude, or `None` if it exceeds `u128::MAX`.
+    #[doc(hidden)]
+    fn lookup_magnitude(&self) -> Option<u128>;
+
+    #[doc(hidden)]
+    fn lookup_bigint(&self) -> Option<&BigInt>;
+
+    #[doc(hidden)]
+    fn lookup_hash(&self, state: &mut dyn Hasher);
+}
+
+impl sealed::Sealed for BigInt {}
+
+impl BigIntLookup for BigInt {
+    fn lookup_sign(&self) -> Sign {
+        self.sign()
+    }
+
+    fn lookup_magnitude(&self) -> Option<u128> {
+        self.magnitude().to_u128()
+    }
+
+    fn lookup_bigint(&self) -> Option<&BigInt> {
+        Some(self)
+    }
+
+    fn lookup_hash(&self, mut state: &mut dyn Hasher) {
+        self.hash(&mut state);
+    }
+}
+
+impl sealed::Sealed for BigIntKey {}
+
+impl BigIntLookup for BigIntKey {
+    fn lookup_sign(&self) -> Sign {
+        self.sign
+    }
+
+    fn lookup_magnitude(&self) -> Option<u128> {
+        Some(self.magnitude)
+    }
+
+    fn lookup_bigint(&self) -> Option<&BigInt> {
+        None
+    }
+
+    fn lookup_hash(&self, mut state: &mut dyn Hasher) {
+        // mirrors the Hash impl of BigInt, which hashes the sign and then the normalized digit vector
+        self.sign.hash(&mut state);
+        if self.sign != Sign::NoSign {
+            let mut digits = [0 as Digit; DIGITS];
+            for (i, digit) in digits.iter_mut().enumerate() {
+                *digit = (self.magnitude >> (i as u32 * Digit::BITS)) as Digit;
+            }
+            let len = DIGITS - (self.magnitude.leading_zeros() / Digit::BITS) as usize;
+            digits[..len].hash(&mut state);
+        }
+    }
+}
+
+impl<'a> Borrow<dyn BigIntLookup + 'a> for BigInt {
+    fn borrow(&self) -> &(dyn BigIntLookup + 'a) {
+        self
+    }
+}
+
+impl Hash for dyn BigIntLookup + '_ {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.lookup_hash(state);
+    }
+}
+
+impl PartialEq for dyn BigIntLookup + '_ {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for dyn BigIntLookup + '_ {}
+
+impl PartialOrd for dyn BigIntLookup + '_ {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for dyn BigIntLookup + '_ {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if let (Some(lhs), Some(rhs)) = (self.lookup_bigint(), other.lookup_bigint()) {
+            return lhs.cmp(rhs);
+        }
+        let sign = self.lookup_sign().cmp(&other.lookup_sign());
+        if sign != Ordering::Equal {
+            return sign;
+        }
+        // at most one side is a BigInt here, and a magnitude beyond u128 is larger than any key
+        let magnitude = match (self.lookup_magnitude(), other.lookup_magnitude()) {
+            (Some(lhs), Some(rhs)) => lhs.cmp(&rhs),
+            (lhs, rhs) => lhs.is_none().cmp(&rhs.is_none()),
+        };
+        if self.lookup_sign() == Sign::Minus {
+            magnitude.reverse()
+        } else {
+            magnitude
+        }
+    }
+}
+
+/// Looks up a primitive key in a `HashMap` keyed by [`num::BigInt`].
+///
+/// Keys that fit into an `i128` are looked up through a [`BigIntKey`] without converting them, anything else is
+/// converted first.
+///
+/// The [`BigIntKey`] hash depends on the internal hash format of num-bigint. So a key that is not found is looked up
+/// once more as a converted `BigInt`, which allocates. Hits never allocate, and the result stays correct even if a
+/// num-bigint release hashes differently. Use [`btree_map_get`] for lookups that never allocate, hit or miss.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+///
+/// use infallible_tobigint::key::hash_map_get;
+/// use num::BigInt;
+///
+/// let map = HashMap::from([(BigInt::from(-7), "seven")]);
+/// assert_eq!(hash_map_get(&map, -7_i64), Some(&"seven"));
+/// assert_eq!(hash_map_get(&map, 7_u8), None);
+/// ```
+#[cfg(feature = "std")]
+pub fn hash_map_get<V, S: BuildHasher>(
+    map: &HashMap<BigInt, V, S>,
+    key: impl InfallibleToBigInt,
+) -> Option<&V> {
+    match key.to_i128_fast() {
+        Some(value) => map
+            .get(&BigIntKey::from(value) as &dyn BigIntLookup)
+            // the borrowed hash relies on the layout of num-bigint, the converted key does not
+            .or_else(|| map.get(&BigInt::from(value))),
+        None => map.get(&key.to_bigint()),
+    }
+}
+
+/// Looks up a primitive key in a `BTreeMap` keyed by [`num::BigInt`].
+///
+/// Keys that fit into an `i128` are compared through a [`BigIntKey`] without converting them, anything else is
+/// converted first.
+///
+/// # Example
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use infallible_tobigint::key::btree_map_get;
+/// use num::BigInt;
+///
+/// let map = BTreeMap::from([(BigInt::from(u128::MAX) * 2, "big"), (BigInt::from(3), "three")]);
+/// assert_eq!(btree_map_get(&map, 3_u32), Some(&"three"));
+/// ```
+pub fn btree_map_get<V>(map: &BTreeMap<BigInt, V>, key: impl InfallibleToBigInt) -> Option<&V> {
+    match key.to_i128_fast() {
+        Some(value) => map.get(&BigIntKey::from(value) as &dyn BigIntLookup),
+        None => map.get(&key.to_bigint()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{
+        cell::Cell,
+        hash::{BuildHasher, Hash, Hasher},
+    };
+    #[cfg(feature = "std")]
+    use std::collections::HashMap;
+    use std::{
+        collections::{hash_map::RandomState, BTreeMap},
+        hash::DefaultHasher,
+    };
+
+    use num::BigInt;
+
+    #[cfg(feature = "std")]
+    use super::hash_map_get;
+    use super::{btree_map_get, BigIntKey, BigIntLookup};
+    use crate::InfallibleToBigInt;
+
+    /// Keys with zero, negative and multi digit magnitudes
+    fn sample_keys() -> Vec<i128> {
+        let mut keys = vec![0, 1, -1, i128::MIN, i128::MAX];
+        for shift in [31, 32, 33, 63, 64, 65, 96, 126] {
+            let power = 1_i128 << shift;
+            keys.extend([power - 1, power, power + 1, -power + 1, -power, -power - 1]);
+        }
+        keys
+    }
+
+    /// Key type that counts how often it was converted to a BigInt
+    struct Spy<'a> {
+        value: i64,
+        conversions: &'a Cell<usize>,
+    }
+
+    impl InfallibleToBigInt for Spy<'_> {
+        fn to_bigint(&self) -> BigInt {
+            self.conversions.set(self.conversions.get() + 1);
+            BigInt::from(self.value)
+        }
+
+        fn to_i128_fast(&self) -> Option<i128> {
+            Some(i128::from(self.value))
+        }
+    }
+
+    /// Test that keys hash exactly like the equal BigInt, with the num-bigint version the tests are built against
+    #[test]
+    fn test_hash_matches_bigint() {
+        let state = RandomState::new();
+        for key in sample_keys() {
+            let lookup = BigIntKey::from(key);
+            let lookup: &dyn BigIntLookup = &lookup;
+            assert_eq!(
+                state.hash_one(lookup),
+                state.hash_one(BigInt::from(key)),
+                "{key}"
+            );
+            let mut hasher = DefaultHasher::new();
+            lookup.hash(&mut hasher);
+            let mut expected = DefaultHasher::new();
+            BigInt::from(key).hash(&mut expected);
+            assert_eq!(hasher.finish(), expected.finish());
+        }
+        let state = RandomState::new();
+        let lookup = BigIntKey::from(u128::MAX);
+        assert_eq!(
+            state.hash_one(&lookup as &dyn BigIntLookup),
+            state.hash_one(BigInt::from(u128::MAX))
+        );
+    }
+
+    /// Test HashMap lookups of negative and multi digit keys
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash_map_lookup() {
+        let keys = sample_keys();
+        let map: HashMap<BigInt, i128> = keys.iter().map(|&key| (BigInt::from(key), key)).collect();
+        for &key in &keys {
+            assert_eq!(hash_map_get(&map, key), Some(&key));
+            assert_eq!(
+                map.get(&BigIntKey::from(key) as &dyn BigIntLookup),
+                Some(&key)
+            );
+        }
+        assert_eq!(hash_map_get(&map, 2_u8), None);
+        assert_eq!(hash_map_get(&map, u128::MAX), None);
+        let mut map = map;
+        map.insert(BigInt::from(u128::MAX), 0);
+        assert_eq!(hash_map_get(&map, u128::MAX), Some(&0));
+        assert_eq!(hash_map_get(&map, u64::MAX - 1), None);
+    }
+
+    /// Test BTreeMap lookups next to keys beyond the u128 range
+    #[test]
+    fn test_btree_map_lookup() {
+        let keys = sample_keys();
+        let mut map: BTreeMap<BigInt, i128> =
+            keys.iter().map(|&key| (BigInt::from(key), key)).collect();
+        let huge = BigInt::from(u128::MAX) * BigInt::from(1000);
+        map.insert(huge.clone(), 1);
+        map.insert(-huge.clone(), -1);
+        for &key in &keys {
+            assert_eq!(btree_map_get(&map, key), Some(&key));
+        }
+        assert_eq!(btree_map_get(&map, 2_i8), None);
+        assert_eq!(btree_map_get(&map, -2_i8), None);
+        assert_eq!(map.get(&huge), Some(&1));
+        assert_eq!(map.get(&(-huge) as &dyn BigIntLookup), Some(&-1));
+    }
+
+    /// Test that u64 and i64 lookups do not convert the key
+    #[test]
+    fn test_lookup_without_conversion() {
+        let conversions = Cell::new(0);
+        let btree: BTreeMap<BigInt, &str> = BTreeMap::from([
+            (BigInt::from(i64::MIN), "min"),
+            (BigInt::from(u64::MAX), "max"),
+        ]);
+        let spy = || Spy {
+            value: i64::MIN,
+            conversions: &conversions,
+        };
+        assert_eq!(btree_map_get(&btree, spy()), Some(&"min"));
+        assert_eq!(btree_map_get(&btree, u64::MAX), Some(&"max"));
+        #[cfg(feature = "std")]
+        {
+            let hash: HashMap<BigInt, &str> = btree.clone().into_iter().collect();
+            assert_eq!(hash_map_get(&hash, spy()), Some(&"min"));
+            assert_eq!(hash_map_get(&hash, u64::MAX), Some(&"max"));
+        }
+        assert_eq!(conversions.get(), 0);
+    }
+
+    /// Test that the ordering of keys agrees with BigInt
+    #[test]
+    fn test_ordering_matches_bigint() {
+        let keys = sample_keys();
+        for &lhs in &keys {
+            for &rhs in &keys {
+                let key = BigIntKey::from(lhs);
+                let big = BigInt::from(rhs);
+                let key: &dyn BigIntLookup = &key;
+                let big: &dyn BigIntLookup = &big;
+                assert_eq!(key.cmp(big), lhs.cmp(&rhs));
+                assert_eq!(big.cmp(key), rhs.cmp(&lhs));
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use core::{cell::OnceCell, cmp::Ordering, fmt, ops::Deref};
+
+use num::BigInt;
+
+use crate::{
+    key::{BigIntKey, BigIntLookup},
+    InfallibleToBigInt,
+};
+
+/// A value whose conversion to [`num::BigInt`] is deferred until it is first needed.
+///
+/// Dereferencing converts the value once and caches the result, so later uses do not allocate again. Comparisons
+/// against `BigInt`s, primitives and other `LazyBigInt`s are decided from the primitive whenever it fits into an
+/// `i128`, so they never force the conversion for the primitive types.
+///
+/// `LazyBigInt` is `Send` if `T` is, but never `Sync`, because the cache is a [`OnceCell`].
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::lazy::LazyBigInt;
+/// use num::BigInt;
+///
+/// let lazy = LazyBigInt::new(u64::MAX);
+/// assert!(lazy > 5_u8);
+/// assert!(lazy.get_if_converted().is_none());
+///
+/// assert_eq!(lazy.to_str_radix(16), "ffffffffffffffff");
+/// assert_eq!(lazy.get_if_converted(), Some(&BigInt::from(u64::MAX)));
+/// ```
+pub struct LazyBigInt<T: InfallibleToBigInt> {
+    value: T,
+    converted: OnceCell<BigInt>,
+}
+
+impl<T: InfallibleToBigInt> LazyBigInt<T> {
+    /// Wraps a value without converting it.
+    pub fn new(value: T) -> Self {
+        LazyBigInt {
+            value,
+            converted: OnceCell::new(),
+        }
+    }
+
+    /// Returns the wrapped value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the converted value if it was already converted, without converting it.
+    pub fn get_if_converted(&self) -> Option<&BigInt> {
+        self.converted.get()
+    }
+
+    /// Returns the converted value, reusing a cached conversion.
+    pub fn into_bigint(self) -> BigInt {
+        match self.converted.into_inner() {
+            Some(converted) => converted,
+            None => self.value.to_bigint(),
+        }
+    }
+
+    /// Returns the value as a key if that is possible without converting it.
+    fn key(&self) -> Option<BigIntKey> {
+        self.value.to_i128_fast().map(BigIntKey::from)
+    }
+}
+
+impl<T: InfallibleToBigInt> Deref for LazyBigInt<T> {
+    type Target = BigInt;
+
+    fn deref(&self) -> &BigInt {
+        self.converted.get_or_init(|| self.value.to_bigint())
+    }
+}
+
+impl<T: InfallibleToBigInt + Clone> Clone for LazyBigInt<T> {
+    fn clone(&self) -> Self {
+        LazyBigInt {
+            value: self.value.clone(),
+            converted: self.converted.clone(),
+        }
+    }
+}
+
+impl<T: InfallibleToBigInt + fmt::Debug> fmt::Debug for LazyBigInt<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyBigInt")
+            .field("value", &self.value)
+            .field("converted", &self.converted.get())
+            .finish()
+    }
+}
+
+impl<T: InfallibleToBigInt, U: InfallibleToBigInt> PartialEq<LazyBigInt<U>> for LazyBigInt<T> {
+    fn eq(&self, other: &LazyBigInt<U>) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<T: InfallibleToBigInt> Eq for LazyBigInt<T> {}
+
+impl<T: InfallibleToBigInt, U: InfallibleToBigInt> PartialOrd<LazyBigInt<U>> for LazyBigInt<T> {
+    fn partial_cmp(&self, other: &LazyBigInt<U>) -> Option<Ordering> {
+        Some(match (self.key(), other.key()) {
+            (Some(lhs), Some(rhs)) => (&lhs as &dyn BigIntLookup).cmp(&rhs),
+            _ => (**self).cmp(&**other),
+        })
+    }
+}
+
+impl<T: InfallibleToBigInt> Ord for LazyBigInt<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T: InfallibleToBigInt> PartialEq<BigInt> for LazyBigInt<T> {
+    fn eq(&self, other: &BigInt) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl<T: InfallibleToBigInt> PartialOrd<BigInt> for LazyBigInt<T> {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(match self.key() {
+            Some(key) => (&key as &dyn BigIntLookup).cmp(other),
+            None => (**self).cmp(other),
+        })
+    }
+}
+
+macro_rules! impl_cmp_prim {
+    ($($prim:ty),*) => {$(
+        impl<T: InfallibleToBigInt> PartialEq<$prim> for LazyBigInt<T> {
+            fn eq(&self, other: &$prim) -> bool {
+                self.partial_cmp(other) == Some(Ordering::Equal)
+            }
+        }
+
+        impl<T: InfallibleToBigInt> PartialOrd<$prim> for LazyBigInt<T> {
+            fn partial_cmp(&self, other: &$prim) -> Option<Ordering> {
+                let other = BigIntKey::from(*other);
+                let other: &dyn BigIntLookup = &other;
+                Some(match self.key() {
+                    Some(key) => (&key as &dyn BigIntLookup).cmp(other),
+                    None => (&**self as &dyn BigIntLookup).cmp(other),
+                })
+            }
+        }
+    )*};
+}
+
+impl_cmp_prim!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+
+    use num::BigInt;
+
+    use super::LazyBigInt;
+    use crate::InfallibleToBigInt;
+
+    /// Value that counts its conversions and optionally hides its i128 fast path
+    #[derive(Debug)]
+    struct Spy<'a> {
+        value: i64,
+        fast: bool,
+        conversions: &'a Cell<usize>,
+    }
+
+    impl InfallibleToBigInt for Spy<'_> {
+        fn to_bigint(&self) -> BigInt {
+            self.conversions.set(self.conversions.get() + 1);
+            BigInt::from(self.value)
+        }
+
+        fn to_i128_fast(&self) -> Option<i128> {
+            self.fast.then_some(i128::from(self.value))
+        }
+    }
+
+    /// Test that the value is converted at most once
+    #[test]
+    fn test_converted_once() {
+        let conversions = Cell::new(0);
+        let lazy = LazyBigInt::new(Spy {
+            value: -42,
+            fast: true,
+            conversions: &conversions,
+        });
+        assert_eq!(conversions.get(), 0);
+        assert_eq!(*lazy, BigInt::from(-42));
+        assert_eq!(lazy.bits(), 6);
+        assert_eq!(lazy.into_bigint(), BigInt::from(-42));
+        assert_eq!(conversions.get(), 1);
+        let lazy = LazyBigInt::new(Spy {
+            value: 7,
+            fast: true,
+            conversions: &conversions,
+        });
+        assert_eq!(lazy.into_bigint(), BigInt::from(7));
+        assert_eq!(conversions.get(), 2);
+    }
+
+    /// Test comparisons before and after forcing the conversion
+    #[test]
+    fn test_comparisons() {
+        let conversions = Cell::new(0);
+        let lazy = LazyBigInt::new(Spy {
+            value: i64::MIN,
+            fast: true,
+            conversions: &conversions,
+        });
+        assert!(lazy == i64::MIN);
+        assert!(lazy < 0_u8);
+        assert!(lazy > i128::MIN);
+        assert!(lazy < u128::MAX);
+        let min = BigInt::from(i64::MIN);
+        assert!(lazy == min);
+        assert!(lazy > BigInt::from(u128::MAX) * -2);
+        assert!(lazy < LazyBigInt::new(1_u8));
+        assert_eq!(conversions.get(), 0);
+        assert!(lazy.get_if_converted().is_none());
+        assert_eq!(*lazy, BigInt::from(i64::MIN));
+        assert!(lazy == i64::MIN);
+        assert!(lazy < BigInt::ZERO);
+        assert_eq!(conversions.get(), 1);
+    }
+
+    /// Test comparisons of values without an i128 fast path, which have to be converted
+    #[test]
+    fn test_comparisons_without_fast_path() {
+        let conversions = Cell::new(0);
+        let lazy = LazyBigInt::new(Spy {
+            value: 10,
+            fast: false,
+            conversions: &conversions,
+        });
+        assert!(lazy > 9_u64);
+        let ten = BigInt::from(10);
+        assert!(lazy == ten);
+        assert!(lazy < LazyBigInt::new(u128::MAX));
+        assert_eq!(conversions.get(), 1);
+    }
+
+    /// Test the Send bound and the Debug output in both states
+    #[test]
+    fn test_send_and_debug() {
+        fn assert_send<T: Send>(_: &T) {}
+        let lazy = LazyBigInt::new(300_u16);
+        assert_send(&lazy);
+        assert_eq!(
+            format!("{lazy:?}"),
+            "LazyBigInt { value: 300, converted: None }"
+        );
+        assert_eq!(lazy.value(), &300);
+        let _ = lazy.bits();
+        assert_eq!(
+            format!("{lazy:?}"),
+            "LazyBigInt { value: 300, converted: Some(300) }"
+        );
+        let cloned = lazy.clone();
+        assert_eq!(cloned.get_if_converted(), Some(&BigInt::from(300)));
+    }
+}
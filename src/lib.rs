@@ -1,5 +1,116 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(any(feature = "bigint", feature = "biguint"))]
+pub mod as_big;
+#[cfg(feature = "ops")]
+pub mod big;
+#[cfg(any(feature = "bigint", feature = "biguint"))]
+pub mod cmp;
+#[cfg(feature = "ops")]
+pub mod consts;
+#[cfg(feature = "encode")]
+pub mod decode;
+#[cfg(any(feature = "bigint", feature = "biguint"))]
+mod delegate;
+#[cfg(feature = "bigint")]
+mod digits;
+#[cfg(feature = "encode")]
+pub mod encode;
+#[cfg(any(feature = "float", feature = "rational"))]
+pub mod float;
+#[cfg(feature = "encode")]
+pub mod fmt;
+#[cfg(feature = "bigint")]
 pub mod infallible_tobigint;
+#[cfg(feature = "rational")]
+pub mod infallible_tobigrational;
+#[cfg(feature = "biguint")]
 pub mod infallible_tobiguint;
+#[cfg(all(feature = "iter", feature = "std"))]
+pub mod interned;
+#[cfg(all(feature = "encode", feature = "std"))]
+pub mod io;
+#[cfg(feature = "iter")]
+pub mod iter;
+#[cfg(feature = "ops")]
+pub mod key;
+#[cfg(feature = "ops")]
+pub mod lazy;
+#[cfg(feature = "macros")]
+mod macros;
+#[cfg(feature = "ops")]
+pub mod ops;
+#[cfg(feature = "encode")]
+pub mod parse;
+#[cfg(any(feature = "bigint", feature = "biguint"))]
+pub mod prim;
+#[cfg(feature = "ops")]
+pub mod scaled;
+#[cfg(any(feature = "bigint", feature = "biguint"))]
+pub mod sync;
+#[cfg(all(feature = "bigint", feature = "std"))]
+pub mod time;
+
+#[cfg(any(feature = "bigint", feature = "biguint"))]
+#[doc(hidden)]
+pub use delegate::support as __delegate;
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use macros::support as __private;
+
+#[cfg(feature = "bigint")]
+pub use infallible_tobigint::{
+    InfallibleIntoBigInt, InfallibleToBigInt, OptionToBigInt, ResultToBigInt, ToBigIntAssign,
+    ToBigIntCow,
+};
+#[cfg(feature = "derive")]
+pub use infallible_tobigint_derive::{
+    bigint_from_env, InfallibleToBigInt, InfallibleToBigUint, ToBigIntFields,
+};
+#[cfg(feature = "rational")]
+pub use infallible_tobigrational::InfallibleToBigRational;
+#[cfg(feature = "biguint")]
+pub use infallible_tobiguint::{
+    InfallibleIntoBigUint, InfallibleToBigUint, OptionToBigUint, ResultToBigUint, ToBigUintAbs,
+    ToSignMagnitude,
+};
+
+/// Smoke tests of the conversions with the `std` feature off, which only use `core` and `alloc` like a `no_std` user.
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    #[cfg(feature = "bigint")]
+    use alloc::vec::Vec;
+
+    #[cfg(feature = "bigint")]
+    use num::BigInt;
+    #[cfg(feature = "biguint")]
+    use num::BigUint;
+
+    #[cfg(feature = "bigint")]
+    use crate::InfallibleToBigInt;
+    #[cfg(feature = "biguint")]
+    use crate::InfallibleToBigUint;
+
+    /// Test the extremes of the primitives
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_to_bigint() {
+        assert_eq!(i128::MIN.to_bigint(), BigInt::from(i128::MIN));
+        assert_eq!(u128::MAX.to_bigint(), BigInt::from(u128::MAX));
+        let values: Vec<BigInt> = [i8::MIN, -1, 0, i8::MAX]
+            .iter()
+            .map(InfallibleToBigInt::to_bigint)
+            .collect();
+        assert_eq!(values[0], BigInt::from(-128));
+    }
 
-pub use infallible_tobigint::InfallibleToBigInt;
-pub use infallible_tobiguint::InfallibleToBigUint;
+    /// Test the unsigned extremes of the primitives
+    #[cfg(feature = "biguint")]
+    #[test]
+    fn test_to_biguint() {
+        assert_eq!(u128::MAX.to_biguint(), BigUint::from(u128::MAX));
+        assert_eq!(0_usize.to_biguint(), BigUint::from(0_u8));
+    }
+}
@@ -0,0 +1,356 @@
+/// Creates a [`num::BigInt`] from an integer literal of any size.
+///
+/// The literal may be negative, use a `0x`, `0o` or `0b` prefix and contain `_` separators. It is validated at compile
+/// time and expands to code that builds the value from a digit array, so no string is parsed at runtime.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::bigint;
+/// use num::BigInt;
+///
+/// let value = bigint!(-123_456_789_012_345_678_901_234_567_890_123_456_789);
+/// assert_eq!(value, "-123456789012345678901234567890123456789".parse::<BigInt>().unwrap());
+/// assert_eq!(bigint!(-0xff), BigInt::from(-255));
+/// ```
+///
+/// Malformed literals do not compile:
+/// ```compile_fail
+/// let value = infallible_tobigint::bigint!(1.5);
+/// ```
+///
+/// Neither do digits that are invalid for the radix:
+/// ```compile_fail
+/// let value = infallible_tobigint::bigint!(0xfg);
+/// ```
+#[macro_export]
+macro_rules! bigint {
+    ($literal:literal) => {{
+        const LITERAL: &str = ::core::stringify!($literal);
+        const LEN: usize =
+            $crate::__private::digit_capacity(LITERAL, $crate::__private::LiteralKind::Signed);
+        const DIGITS: [u32; LEN] = $crate::__private::digits::<LEN>(LITERAL);
+        const NEGATIVE: bool = $crate::__private::is_negative(LITERAL);
+        $crate::__private::LiteralValue::<false>::from_digits(&DIGITS, NEGATIVE)
+    }};
+}
+
+/// Creates a [`num::BigUint`] from an integer literal of any size.
+///
+/// The literal may use a `0x`, `0o` or `0b` prefix and contain `_` separators. It is validated at compile time and
+/// expands to code that builds the value from a digit array, so no string is parsed at runtime.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::biguint;
+/// use num::BigUint;
+///
+/// let value = biguint!(0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FF);
+/// assert_eq!(value, (BigUint::from(u128::MAX) << 8_u32) + 0xff_u8);
+/// ```
+///
+/// Negative literals do not compile, even in other radixes:
+/// ```compile_fail
+/// let value = infallible_tobigint::biguint!(-1);
+/// ```
+///
+/// ```compile_fail
+/// let value = infallible_tobigint::biguint!(-0x1);
+/// ```
+#[macro_export]
+macro_rules! biguint {
+    ($literal:literal) => {{
+        const LITERAL: &str = ::core::stringify!($literal);
+        const LEN: usize =
+            $crate::__private::digit_capacity(LITERAL, $crate::__private::LiteralKind::Unsigned);
+        const DIGITS: [u32; LEN] = $crate::__private::digits::<LEN>(LITERAL);
+        $crate::__private::LiteralValue::<true>::from_digits(&DIGITS, false)
+    }};
+}
+
+/// Creates a [`num::BigInt`], or a [`num::BigUint`] if the literal has a `u` suffix.
+///
+/// This accepts the same literals as [`bigint!`] and [`biguint!`], so `big!(123)` is a `BigInt` and `big!(123u)` a
+/// `BigUint`. Negative literals with a `u` suffix do not compile.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::big;
+/// use num::{BigInt, BigUint};
+///
+/// let signed: BigInt = big!(-5);
+/// let unsigned: BigUint = big!(0xffu);
+/// assert_eq!(signed, BigInt::from(-5));
+/// assert_eq!(unsigned, BigUint::from(255_u8));
+/// ```
+///
+/// ```compile_fail
+/// let value = infallible_tobigint::big!(-1u);
+/// ```
+#[macro_export]
+macro_rules! big {
+    ($literal:literal) => {{
+        const LITERAL: &str = ::core::stringify!($literal);
+        const LEN: usize =
+            $crate::__private::digit_capacity(LITERAL, $crate::__private::LiteralKind::Inferred);
+        const DIGITS: [u32; LEN] = $crate::__private::digits::<LEN>(LITERAL);
+        const NEGATIVE: bool = $crate::__private::is_negative(LITERAL);
+        $crate::__private::LiteralValue::<{ $crate::__private::has_unsigned_suffix(LITERAL) }>::from_digits(
+            &DIGITS, NEGATIVE,
+        )
+    }};
+}
+
+/// Support code for the literal macros, which has to be public but is not part of the API.
+pub mod support {
+    pub use alloc::vec::Vec;
+    pub use num::{BigInt, BigUint};
+
+    use num::bigint::Sign;
+
+    /// Which literals a macro accepts.
+    pub enum LiteralKind {
+        /// Optionally negative, no suffix.
+        Signed,
+        /// Not negative, no suffix.
+        Unsigned,
+        /// Optionally negative without suffix, or not negative with a `u` suffix.
+        Inferred,
+    }
+
+    /// The parts of a scanned literal.
+    struct Scanned {
+        negative: bool,
+        unsigned_suffix: bool,
+        radix: u64,
+        digits_start: usize,
+        digits_end: usize,
+        digit_count: usize,
+    }
+
+    /// Returns the value of an ASCII digit in the given radix, if it is one.
+    const fn digit_value(byte: u8, radix: u64) -> Option<u64> {
+        let value = match byte {
+            b'0'..=b'9' => byte - b'0',
+            b'a'..=b'f' => byte - b'a' + 10,
+            b'A'..=b'F' => byte - b'A' + 10,
+            _ => return None,
+        };
+        if (value as u64) < radix {
+            Some(value as u64)
+        } else {
+            None
+        }
+    }
+
+    /// Splits a literal into sign, radix, digits and suffix, failing compilation if it is malformed.
+    const fn scan(literal: &str) -> Scanned {
+        let bytes = literal.as_bytes();
+        let mut i = 0;
+        let negative = !bytes.is_empty() && bytes[0] == b'-';
+        if negative {
+            i += 1;
+            while i < bytes.len() && bytes[i] == b' ' {
+                i += 1;
+            }
+        }
+        let mut radix = 10;
+        if i + 1 < bytes.len() && bytes[i] == b'0' {
+            radix = match bytes[i + 1] {
+                b'x' => 16,
+                b'o' => 8,
+                b'b' => 2,
+                _ => 10,
+            };
+            if radix != 10 {
+                i += 2;
+            }
+        }
+        let digits_start = i;
+        let mut digit_count = 0;
+        while i < bytes.len() && (bytes[i] == b'_' || digit_value(bytes[i], radix).is_some()) {
+            if bytes[i] != b'_' {
+                digit_count += 1;
+            }
+            i += 1;
+        }
+        let digits_end = i;
+        if digit_count == 0 {
+            panic!("integer literal has no digits");
+        }
+        let unsigned_suffix = if i == bytes.len() {
+            false
+        } else if i + 1 == bytes.len() && bytes[i] == b'u' {
+            true
+        } else {
+            panic!("not an integer literal, or it has an unsupported suffix");
+        };
+        Scanned {
+            negative,
+            unsigned_suffix,
+            radix,
+            digits_start,
+            digits_end,
+            digit_count,
+        }
+    }
+
+    /// Validates the literal for the given macro and returns the number of `u32` digits needed to hold it.
+    pub const fn digit_capacity(literal: &str, kind: LiteralKind) -> usize {
+        let scanned = scan(literal);
+        match kind {
+            LiteralKind::Signed if scanned.unsigned_suffix => {
+                panic!("bigint! does not accept a `u` suffix, use biguint! or big! instead")
+            }
+            LiteralKind::Unsigned if scanned.unsigned_suffix => {
+                panic!("biguint! does not accept a `u` suffix, use big! instead")
+            }
+            LiteralKind::Unsigned if scanned.negative => {
+                panic!("biguint! does not accept negative literals")
+            }
+            LiteralKind::Inferred if scanned.unsigned_suffix && scanned.negative => {
+                panic!("big! does not accept negative literals with a `u` suffix")
+            }
+            _ => {}
+        }
+        // an upper bound of the bits per digit, log2(10) < 10 / 3 for decimal literals
+        let bits = match scanned.radix {
+            2 => scanned.digit_count,
+            8 => scanned.digit_count * 3,
+            16 => scanned.digit_count * 4,
+            _ => (scanned.digit_count * 10).div_ceil(3),
+        };
+        bits.div_ceil(32)
+    }
+
+    /// Converts a literal validated by [`digit_capacity`] into little endian `u32` digits.
+    pub const fn digits<const N: usize>(literal: &str) -> [u32; N] {
+        let scanned = scan(literal);
+        let bytes = literal.as_bytes();
+        let mut digits = [0_u32; N];
+        let mut i = scanned.digits_start;
+        while i < scanned.digits_end {
+            if let Some(value) = digit_value(bytes[i], scanned.radix) {
+                let mut carry = value;
+                let mut j = 0;
+                while j < N {
+                    let product = digits[j] as u64 * scanned.radix + carry;
+                    digits[j] = product as u32;
+                    carry = product >> 32;
+                    j += 1;
+                }
+                if carry != 0 {
+                    panic!("integer literal does not fit the computed digit capacity");
+                }
+            }
+            i += 1;
+        }
+        digits
+    }
+
+    /// Returns whether the literal has a leading minus sign.
+    pub const fn is_negative(literal: &str) -> bool {
+        scan(literal).negative
+    }
+
+    /// Returns whether the literal has a `u` suffix.
+    pub const fn has_unsigned_suffix(literal: &str) -> bool {
+        scan(literal).unsigned_suffix
+    }
+
+    /// Builds the value of a literal, a [`num::BigUint`] if `UNSIGNED` is set and a [`num::BigInt`] otherwise.
+    pub struct LiteralValue<const UNSIGNED: bool>;
+
+    impl LiteralValue<false> {
+        pub fn from_digits(digits: &[u32], negative: bool) -> BigInt {
+            let sign = if negative { Sign::Minus } else { Sign::Plus };
+            BigInt::from_biguint(sign, BigUint::from_slice(digits))
+        }
+    }
+
+    impl LiteralValue<true> {
+        pub fn from_digits(digits: &[u32], _negative: bool) -> BigUint {
+            BigUint::from_slice(digits)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, BigUint, Num, Zero};
+
+    /// Test decimal literals beyond u128
+    #[test]
+    fn test_bigint_decimal() {
+        let expected = "123456789012345678901234567890123456789012345678901234567890";
+        assert_eq!(
+            bigint!(123456789012345678901234567890123456789012345678901234567890),
+            expected.parse::<BigInt>().unwrap()
+        );
+        assert_eq!(
+            bigint!(-123456789012345678901234567890123456789012345678901234567890),
+            -expected.parse::<BigInt>().unwrap()
+        );
+        assert_eq!(bigint!(0), BigInt::zero());
+        assert_eq!(bigint!(-0), BigInt::zero());
+        assert_eq!(bigint!(1_000_000), BigInt::from(1_000_000));
+    }
+
+    /// Test prefixed literals
+    #[test]
+    fn test_biguint_prefixed() {
+        assert_eq!(
+            biguint!(0xFFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FFFF_FF),
+            BigUint::from_str_radix("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF", 16).unwrap()
+        );
+        assert_eq!(biguint!(0xdead_BEEF), BigUint::from(0xdead_beef_u32));
+        assert_eq!(biguint!(0o777), BigUint::from(0o777_u32));
+        assert_eq!(biguint!(0b1010_1010), BigUint::from(0b1010_1010_u32));
+        assert_eq!(bigint!(-0b1), BigInt::from(-1));
+    }
+
+    /// Test big! with and without the unsigned suffix
+    #[test]
+    fn test_big() {
+        let signed: BigInt = big!(-42);
+        let unsigned: BigUint = big!(42u);
+        assert_eq!(signed, BigInt::from(-42));
+        assert_eq!(unsigned, BigUint::from(42_u8));
+        let x: BigUint = big!(5u);
+        assert_eq!(x, BigUint::from(5_u8));
+        assert_eq!(
+            big!(340282366920938463463374607431768211456),
+            BigInt::from(u128::MAX) + 1
+        );
+    }
+
+    /// Test big! with hex literals
+    #[test]
+    fn test_big_hex() {
+        let unsigned: BigUint = big!(0x1_0000_0000_0000_0000_0000_0000_0000_0000u);
+        assert_eq!(unsigned, BigUint::from(u128::MAX) + 1_u8);
+        let signed: BigInt = big!(-0xABCDEF);
+        assert_eq!(signed, BigInt::from(-0xABCDEF));
+    }
+
+    /// Test a literal of several hundred digits in every radix
+    #[test]
+    fn test_long_literals() {
+        let decimal = "123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890";
+        assert_eq!(
+            bigint!(-123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890123456789012345678901234567890),
+            -decimal.parse::<BigInt>().unwrap()
+        );
+        assert_eq!(
+            biguint!(0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff),
+            (BigUint::from(1_u8) << 400_u32) - 1_u8
+        );
+        assert_eq!(
+            biguint!(0b11111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111111),
+            (BigUint::from(1_u8) << 200_u32) - 1_u8
+        );
+        assert_eq!(
+            biguint!(0o1000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000),
+            BigUint::from(1_u8) << 450_u32
+        );
+    }
+}
@@ -0,0 +1,826 @@
+use alloc::vec;
+use core::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+
+use num::{bigint::Sign, pow::Pow, BigInt, BigUint, Integer, ToPrimitive, Zero};
+
+use crate::InfallibleToBigInt;
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
+
+/// A non-zero primitive divisor accepted by [`BigIntOpsExt::div_mod_floor_prim`].
+///
+/// Implemented for all `NonZero*` integer types, so dividing by zero is ruled out by the type system. The remainder is
+/// returned as the underlying primitive type [`PrimDivisor::Prim`].
+pub trait PrimDivisor: sealed::Sealed + Copy {
+    /// The primitive type of the divisor and of the remainder.
+    type Prim;
+
+    #[doc(hidden)]
+    fn is_negative(self) -> bool;
+
+    #[doc(hidden)]
+    fn magnitude(self) -> u128;
+
+    #[doc(hidden)]
+    fn prim_from_parts(negative: bool, magnitude: u128) -> Self::Prim;
+}
+
+macro_rules! impl_prim_divisor_unsigned {
+    ($($non_zero:ty => $prim:ty),*) => {$(
+        impl sealed::Sealed for $non_zero {}
+
+        impl PrimDivisor for $non_zero {
+            type Prim = $prim;
+
+            fn is_negative(self) -> bool {
+                false
+            }
+
+            fn magnitude(self) -> u128 {
+                self.get() as u128
+            }
+
+            fn prim_from_parts(_negative: bool, magnitude: u128) -> $prim {
+                magnitude as $prim
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_prim_divisor_signed {
+    ($($non_zero:ty => $prim:ty),*) => {$(
+        impl sealed::Sealed for $non_zero {}
+
+        impl PrimDivisor for $non_zero {
+            type Prim = $prim;
+
+            fn is_negative(self) -> bool {
+                self.get() < 0
+            }
+
+            fn magnitude(self) -> u128 {
+                self.get().unsigned_abs() as u128
+            }
+
+            fn prim_from_parts(negative: bool, magnitude: u128) -> $prim {
+                // the magnitude of a negative value may be |MIN|, which only wraps back correctly via wrapping_neg
+                if negative {
+                    (magnitude as $prim).wrapping_neg()
+                } else {
+                    magnitude as $prim
+                }
+            }
+        }
+    )*};
+}
+
+impl_prim_divisor_unsigned!(
+    NonZeroU8 => u8, NonZeroU16 => u16, NonZeroU32 => u32, NonZeroU64 => u64, NonZeroU128 => u128,
+    NonZeroUsize => usize
+);
+impl_prim_divisor_signed!(
+    NonZeroI8 => i8, NonZeroI16 => i16, NonZeroI32 => i32, NonZeroI64 => i64, NonZeroI128 => i128,
+    NonZeroIsize => isize
+);
+
+/// A primitive integer type accepted by [`BigIntOpsExt::is_representable_as`].
+///
+/// Implemented for all primitive integer types.
+pub trait PrimTarget: sealed::Sealed {
+    /// The number of bits of the type.
+    const BITS: u32;
+    /// Whether the type is signed.
+    const SIGNED: bool;
+}
+
+macro_rules! impl_prim_target {
+    ($signed:literal => $($prim:ty),*) => {$(
+        impl sealed::Sealed for $prim {}
+
+        impl PrimTarget for $prim {
+            const BITS: u32 = <$prim>::BITS;
+            const SIGNED: bool = $signed;
+        }
+    )*};
+}
+
+impl_prim_target!(false => u8, u16, u32, u64, u128, usize);
+impl_prim_target!(true => i8, i16, i32, i64, i128, isize);
+
+/// Returns whether a value with the given sign and magnitude fits into `T`, without allocating.
+fn is_representable<T: PrimTarget>(negative: bool, magnitude: &BigUint) -> bool {
+    let bits = magnitude.bits();
+    match (T::SIGNED, negative) {
+        (false, true) => false,
+        (false, false) => bits <= u64::from(T::BITS),
+        (true, false) => bits < u64::from(T::BITS),
+        // MIN has a magnitude of exactly 2^(BITS - 1), the only negative value with BITS bits
+        (true, true) => {
+            bits < u64::from(T::BITS)
+                || (bits == u64::from(T::BITS)
+                    && magnitude.trailing_zeros() == Some(u64::from(T::BITS) - 1))
+        }
+    }
+}
+
+/// Returns the number of digits of `magnitude` in `radix`, counting zero as one digit.
+///
+/// The bit length bounds the digit count to an interval that contains at most two integers, which is resolved by one
+/// comparison against a power of the radix. Power of two radices need no comparison at all.
+fn digit_count(magnitude: &BigUint, radix: u32) -> u64 {
+    assert!(radix >= 2, "radix must be at least 2, got {radix}");
+    let bits = magnitude.bits();
+    if bits == 0 {
+        return 1;
+    }
+    if radix.is_power_of_two() {
+        return bits.div_ceil(u64::from(radix.trailing_zeros()));
+    }
+    // magnitude lies in [2^(bits - 1), 2^bits), so its digit count lies in [lower, upper], with the bounds widened
+    // by more than the rounding error of the logarithm and the float products. Truncating the non-negative bounds
+    // rounds them down.
+    let log_2 = 1.0 / log2(radix);
+    let slack = bits as f64 * f64::EPSILON * 8.0;
+    let lower = ((bits - 1) as f64 * log_2 - slack).max(0.0) as u64 + 1;
+    let upper = (bits as f64 * log_2 + slack) as u64 + 1;
+    let mut count = lower;
+    while count < upper && *magnitude >= Pow::pow(BigUint::from(radix), count) {
+        count += 1;
+    }
+    count
+}
+
+/// Returns the binary logarithm of `value`, using only the float arithmetic of `core`.
+fn log2(value: u32) -> f64 {
+    // value = 2^exponent * mantissa, with the mantissa in [1, 2)
+    let exponent = value.ilog2();
+    let mantissa = f64::from(value) / f64::from(1_u32 << exponent);
+    // ln(mantissa) = 2 * atanh(z) with z = (mantissa - 1) / (mantissa + 1) < 1 / 3, so the series has converged to
+    // below the float precision after 20 terms
+    let z = (mantissa - 1.0) / (mantissa + 1.0);
+    let mut power = z;
+    let mut atanh = 0.0;
+    for n in 0..20 {
+        atanh += power / f64::from(2 * n + 1);
+        power *= z * z;
+    }
+    f64::from(exponent) + 2.0 * atanh / core::f64::consts::LN_2
+}
+
+/// Divides `dividend` by a non-zero `divisor`, returning the truncated quotient and the remainder.
+///
+/// Divisors up to `u64::MAX` are handled by a long division over the 64 bit digits, so the remainder never lives in a
+/// `BigUint`.
+fn div_rem_u128(dividend: &BigUint, divisor: u128) -> (BigUint, u128) {
+    let Ok(divisor) = u64::try_from(divisor) else {
+        let (quotient, remainder) = dividend.div_rem(&BigUint::from(divisor));
+        return (quotient, remainder.to_u128().unwrap_or_default());
+    };
+    let divisor = u128::from(divisor);
+    let mut quotient = vec![0_u32; dividend.iter_u64_digits().len() * 2];
+    let mut remainder = 0_u128;
+    for (i, digit) in dividend.iter_u64_digits().enumerate().rev() {
+        let current = (remainder << 64) | u128::from(digit);
+        let digit_quotient = (current / divisor) as u64;
+        remainder = current % divisor;
+        quotient[2 * i] = digit_quotient as u32;
+        quotient[2 * i + 1] = (digit_quotient >> 32) as u32;
+    }
+    (BigUint::new(quotient), remainder)
+}
+
+/// Computes `value % modulus` by folding over the 64 bit digits, without allocating.
+fn rem_u64(value: &BigUint, modulus: NonZeroU64) -> u64 {
+    let modulus = u128::from(modulus.get());
+    value
+        .iter_u64_digits()
+        .rev()
+        .fold(0_u128, |remainder, digit| {
+            ((remainder << 64) | u128::from(digit)) % modulus
+        }) as u64
+}
+
+/// Arithmetic helpers on [`num::BigInt`] that take primitive operands.
+///
+/// # Example
+/// ```
+/// use core::num::NonZeroU32;
+///
+/// use infallible_tobigint::ops::BigIntOpsExt;
+/// use num::BigInt;
+///
+/// let (quotient, remainder) = BigInt::from(-7).div_mod_floor_prim(NonZeroU32::new(2).unwrap());
+/// assert_eq!(quotient, BigInt::from(-4));
+/// assert_eq!(remainder, 1_u32);
+/// ```
+pub trait BigIntOpsExt {
+    /// Computes the floored quotient and remainder of a division by a non-zero primitive.
+    ///
+    /// The semantics match [`num::Integer::div_mod_floor`]: the remainder has the sign of the divisor and its
+    /// magnitude is smaller than the divisor's, so it always fits into the divisor's primitive type.
+    fn div_mod_floor_prim<D: PrimDivisor>(&self, divisor: D) -> (BigInt, D::Prim);
+
+    /// Computes the Euclidean remainder of a division by `modulus`, which is never negative.
+    ///
+    /// Unlike the `%` operator this does not allocate.
+    fn rem_euclid_u64(&self, modulus: NonZeroU64) -> u64;
+
+    /// Computes the Euclidean remainder of a division by `modulus`, which is never negative.
+    ///
+    /// Unlike the `%` operator this does not allocate.
+    fn rem_euclid_u32(&self, modulus: NonZeroU32) -> u32;
+
+    /// Returns whether the value fits into the primitive integer type `T`.
+    ///
+    /// This only inspects the sign and the bit length, plus the lowest set bit at `T::MIN`, so nothing is allocated.
+    fn is_representable_as<T: PrimTarget>(&self) -> bool;
+
+    /// Returns the exact number of digits of the value in `radix`, without the sign and counting zero as one digit.
+    ///
+    /// The count is derived from the bit length plus at most one comparison against a power of the radix, so no
+    /// string is built. Panics if `radix` is smaller than 2.
+    fn digit_count(&self, radix: u32) -> u64;
+}
+
+impl BigIntOpsExt for BigInt {
+    fn div_mod_floor_prim<D: PrimDivisor>(&self, divisor: D) -> (BigInt, D::Prim) {
+        let divisor_negative = divisor.is_negative();
+        let divisor_magnitude = divisor.magnitude();
+        let (mut quotient, remainder) = div_rem_u128(self.magnitude(), divisor_magnitude);
+        let dividend_negative = self.sign() == Sign::Minus;
+        let quotient_sign = if dividend_negative != divisor_negative {
+            Sign::Minus
+        } else {
+            Sign::Plus
+        };
+        let remainder = if remainder != 0 && dividend_negative != divisor_negative {
+            // the truncated remainder has the wrong sign, so step the quotient one further away from zero
+            quotient += 1_u8;
+            divisor_magnitude - remainder
+        } else {
+            remainder
+        };
+        (
+            BigInt::from_biguint(quotient_sign, quotient),
+            D::prim_from_parts(divisor_negative, remainder),
+        )
+    }
+
+    fn rem_euclid_u64(&self, modulus: NonZeroU64) -> u64 {
+        let remainder = rem_u64(self.magnitude(), modulus);
+        if remainder != 0 && self.sign() == Sign::Minus {
+            modulus.get() - remainder
+        } else {
+            remainder
+        }
+    }
+
+    fn rem_euclid_u32(&self, modulus: NonZeroU32) -> u32 {
+        self.rem_euclid_u64(modulus.into()) as u32
+    }
+
+    fn is_representable_as<T: PrimTarget>(&self) -> bool {
+        is_representable::<T>(self.sign() == Sign::Minus, self.magnitude())
+    }
+
+    fn digit_count(&self, radix: u32) -> u64 {
+        digit_count(self.magnitude(), radix)
+    }
+}
+
+/// Arithmetic helpers on [`num::BigUint`] that take primitive operands.
+///
+/// # Example
+/// ```
+/// use core::num::NonZeroU64;
+///
+/// use infallible_tobigint::ops::BigUintOpsExt;
+/// use num::BigUint;
+///
+/// let value = BigUint::from(u128::MAX);
+/// assert_eq!(value.rem_u64(NonZeroU64::new(1000).unwrap()), 455);
+/// ```
+pub trait BigUintOpsExt {
+    /// Computes `self % modulus` without allocating.
+    ///
+    /// The value is folded over its 64 bit digits using 128 bit intermediate arithmetic.
+    fn rem_u64(&self, modulus: NonZeroU64) -> u64;
+
+    /// Computes `self % modulus` without allocating.
+    ///
+    /// See [`BigUintOpsExt::rem_u64`] for details.
+    fn rem_u32(&self, modulus: NonZeroU32) -> u32;
+
+    /// Returns whether the value fits into the primitive integer type `T`.
+    ///
+    /// See [`BigIntOpsExt::is_representable_as`] for details.
+    fn is_representable_as<T: PrimTarget>(&self) -> bool;
+
+    /// Returns the exact number of digits of the value in `radix`, counting zero as one digit.
+    ///
+    /// See [`BigIntOpsExt::digit_count`] for details.
+    fn digit_count(&self, radix: u32) -> u64;
+}
+
+impl BigUintOpsExt for BigUint {
+    fn rem_u64(&self, modulus: NonZeroU64) -> u64 {
+        rem_u64(self, modulus)
+    }
+
+    fn rem_u32(&self, modulus: NonZeroU32) -> u32 {
+        rem_u64(self, modulus.into()) as u32
+    }
+
+    fn is_representable_as<T: PrimTarget>(&self) -> bool {
+        is_representable::<T>(false, self)
+    }
+
+    fn digit_count(&self, radix: u32) -> u64 {
+        digit_count(self, radix)
+    }
+}
+
+/// Evaluates the polynomial `coeffs[0] + coeffs[1] * x + coeffs[2] * x^2 + ...` exactly.
+///
+/// The coefficients are ordered from the lowest to the highest degree. Horner's method is used with all intermediate
+/// values kept in [`num::BigInt`], so nothing can overflow. The empty polynomial evaluates to zero.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::ops::eval_poly_bigint;
+/// use num::BigInt;
+///
+/// // 1 - 2x + 3x^2 at x = 10
+/// assert_eq!(eval_poly_bigint(&[1, -2, 3], 10), BigInt::from(281));
+/// ```
+pub fn eval_poly_bigint<C: InfallibleToBigInt>(coeffs: &[C], x: impl InfallibleToBigInt) -> BigInt {
+    let x = x.to_bigint();
+    coeffs
+        .iter()
+        .rev()
+        .fold(BigInt::zero(), |acc, coeff| acc * &x + coeff.to_bigint())
+}
+
+/// Evaluates the polynomial `coeffs[0] + coeffs[1] * x + coeffs[2] * x^2 + ...` modulo `modulus`.
+///
+/// The coefficients are ordered from the lowest to the highest degree. The intermediate values are reduced after
+/// every step of Horner's method, so they stay below `modulus^2` plus a coefficient. The result lies in
+/// `0..modulus`, also for negative coefficients or a negative `x`.
+///
+/// # Panics
+/// Panics if `modulus` is zero, just like the `%` operator.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::ops::eval_poly_mod;
+/// use num::BigUint;
+///
+/// // 1 - 2x + 3x^2 at x = 10 is 281
+/// assert_eq!(eval_poly_mod(&[1, -2, 3], 10, &BigUint::from(100_u8)), BigUint::from(81_u8));
+/// ```
+pub fn eval_poly_mod<C: InfallibleToBigInt>(
+    coeffs: &[C],
+    x: impl InfallibleToBigInt,
+    modulus: &BigUint,
+) -> BigUint {
+    let modulus = BigInt::from(modulus.clone());
+    let x = x.to_bigint().mod_floor(&modulus);
+    let value = coeffs.iter().rev().fold(BigInt::zero(), |acc, coeff| {
+        (acc * &x + coeff.to_bigint()).mod_floor(&modulus)
+    });
+    value.into_parts().1
+}
+
+#[cfg(test)]
+mod tests {
+    use core::num::{
+        NonZeroI128, NonZeroI64, NonZeroI8, NonZeroU128, NonZeroU32, NonZeroU64, NonZeroU8,
+    };
+
+    use num::{pow::Pow, BigInt, BigUint, Integer, ToPrimitive, Zero};
+
+    use super::{eval_poly_bigint, eval_poly_mod, log2, BigIntOpsExt, BigUintOpsExt};
+
+    /// Simple xorshift generator, so the tests are reproducible without extra dependencies
+    fn pseudo_random_values(count: usize) -> Vec<BigUint> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        (0..count)
+            .map(|i| {
+                let digits: Vec<u32> = (0..i % 9).map(|_| next() as u32).collect();
+                BigUint::new(digits)
+            })
+            .collect()
+    }
+
+    fn dividends() -> Vec<BigInt> {
+        let mut dividends: Vec<BigInt> = [
+            0_i128,
+            1,
+            -1,
+            7,
+            -7,
+            255,
+            -256,
+            i64::MIN as i128,
+            i128::MIN,
+            i128::MAX,
+        ]
+        .into_iter()
+        .map(BigInt::from)
+        .collect();
+        let huge = BigInt::from(u128::MAX) * BigInt::from(u128::MAX) + 12345_u32;
+        dividends.push(-huge.clone());
+        dividends.push(huge);
+        dividends
+    }
+
+    /// Test negative dividends with positive and negative divisors
+    #[test]
+    fn test_negative_dividends() {
+        let two = NonZeroI8::new(2).unwrap();
+        let minus_two = NonZeroI8::new(-2).unwrap();
+        assert_eq!(
+            BigInt::from(-7).div_mod_floor_prim(two),
+            (BigInt::from(-4), 1)
+        );
+        assert_eq!(
+            BigInt::from(-7).div_mod_floor_prim(minus_two),
+            (BigInt::from(3), -1)
+        );
+        assert_eq!(
+            BigInt::from(7).div_mod_floor_prim(minus_two),
+            (BigInt::from(-4), -1)
+        );
+        assert_eq!(
+            BigInt::from(-8).div_mod_floor_prim(two),
+            (BigInt::from(-4), 0)
+        );
+    }
+
+    /// Test the divisor 1
+    #[test]
+    fn test_divisor_one() {
+        for dividend in dividends() {
+            assert_eq!(
+                dividend.div_mod_floor_prim(NonZeroU8::new(1).unwrap()),
+                (dividend.clone(), 0)
+            );
+        }
+    }
+
+    /// Test divisors equal to MAX and MIN of their types
+    #[test]
+    fn test_divisor_limits() {
+        for dividend in dividends() {
+            let (quotient, remainder) =
+                dividend.div_mod_floor_prim(NonZeroU64::new(u64::MAX).unwrap());
+            assert_eq!(
+                (quotient, remainder.into()),
+                dividend.div_mod_floor(&u64::MAX.into())
+            );
+            let (quotient, remainder) =
+                dividend.div_mod_floor_prim(NonZeroU128::new(u128::MAX).unwrap());
+            assert_eq!(
+                (quotient, remainder.into()),
+                dividend.div_mod_floor(&u128::MAX.into())
+            );
+            let (quotient, remainder) =
+                dividend.div_mod_floor_prim(NonZeroI128::new(i128::MIN).unwrap());
+            assert_eq!(
+                (quotient, remainder.into()),
+                dividend.div_mod_floor(&i128::MIN.into())
+            );
+            let (quotient, remainder) =
+                dividend.div_mod_floor_prim(NonZeroI64::new(i64::MIN).unwrap());
+            assert_eq!(
+                (quotient, remainder.into()),
+                dividend.div_mod_floor(&i64::MIN.into())
+            );
+        }
+    }
+
+    /// Test agreement with div_mod_floor on BigInt for a range of divisors
+    #[test]
+    fn test_agreement_with_div_mod_floor() {
+        for dividend in dividends() {
+            for divisor in [1_u32, 2, 3, 10, 1 << 31, u32::MAX] {
+                let (quotient, remainder) =
+                    dividend.div_mod_floor_prim(NonZeroU32::new(divisor).unwrap());
+                assert_eq!(
+                    (quotient, remainder.into()),
+                    dividend.div_mod_floor(&divisor.into())
+                );
+            }
+            for divisor in [-1_i64, -3, 5, -(1 << 40), i64::MAX] {
+                let (quotient, remainder) =
+                    dividend.div_mod_floor_prim(NonZeroI64::new(divisor).unwrap());
+                assert_eq!(
+                    (quotient, remainder.into()),
+                    dividend.div_mod_floor(&divisor.into())
+                );
+            }
+        }
+    }
+
+    /// Test pseudo-random values against the % operator
+    #[test]
+    fn test_rem_random() {
+        for value in pseudo_random_values(500) {
+            for modulus in [
+                1_u64,
+                2,
+                3,
+                10,
+                1 << 32,
+                0xffff_fffb,
+                u64::MAX - 58,
+                u64::MAX,
+            ] {
+                let non_zero = NonZeroU64::new(modulus).unwrap();
+                assert_eq!(BigUint::from(value.rem_u64(non_zero)), &value % modulus);
+                let negative = -BigInt::from(value.clone());
+                assert_eq!(
+                    BigInt::from(negative.rem_euclid_u64(non_zero)),
+                    negative.mod_floor(&modulus.into())
+                );
+            }
+            for modulus in [1_u32, 7, u32::MAX] {
+                let non_zero = NonZeroU32::new(modulus).unwrap();
+                assert_eq!(BigUint::from(value.rem_u32(non_zero)), &value % modulus);
+            }
+        }
+    }
+
+    /// Test the Euclidean remainder of negative values
+    #[test]
+    fn test_rem_euclid_negative() {
+        let seven = NonZeroU64::new(7).unwrap();
+        assert_eq!(BigInt::from(-1).rem_euclid_u64(seven), 6);
+        assert_eq!(BigInt::from(-7).rem_euclid_u64(seven), 0);
+        assert_eq!(
+            BigInt::from(-8).rem_euclid_u32(NonZeroU32::new(7).unwrap()),
+            6
+        );
+        assert_eq!(
+            BigInt::from(i128::MIN).rem_euclid_u64(NonZeroU64::new(u64::MAX).unwrap()),
+            (1 << 63) - 1
+        );
+    }
+
+    /// Test the moduli 1 and u64::MAX
+    #[test]
+    fn test_rem_limits() {
+        let value = BigUint::from(u128::MAX);
+        assert_eq!(value.rem_u64(NonZeroU64::new(1).unwrap()), 0);
+        assert_eq!(value.rem_u64(NonZeroU64::new(u64::MAX).unwrap()), 0);
+        assert_eq!(
+            (value + 5_u8).rem_u64(NonZeroU64::new(u64::MAX).unwrap()),
+            5
+        );
+        assert_eq!(
+            BigUint::default().rem_u64(NonZeroU64::new(u64::MAX).unwrap()),
+            0
+        );
+    }
+
+    /// Test a value with thousands of digits
+    #[test]
+    fn test_rem_many_digits() {
+        let value = BigUint::new(vec![0xdead_beef; 8000]);
+        for modulus in [3_u64, 1_000_000_007, u64::MAX] {
+            assert_eq!(
+                BigUint::from(value.rem_u64(NonZeroU64::new(modulus).unwrap())),
+                &value % modulus
+            );
+        }
+    }
+
+    /// Evaluates the polynomial naively by summing all powers
+    fn naive_poly(coeffs: &[i64], x: i64) -> BigInt {
+        coeffs
+            .iter()
+            .enumerate()
+            .map(|(i, &coeff)| BigInt::from(coeff) * Pow::pow(BigInt::from(x), i))
+            .sum()
+    }
+
+    /// Test the empty polynomial
+    #[test]
+    fn test_poly_empty() {
+        let empty: [u8; 0] = [];
+        assert_eq!(eval_poly_bigint(&empty, 12345), BigInt::zero());
+        assert_eq!(
+            eval_poly_mod(&empty, 12345, &BigUint::from(7_u8)),
+            BigUint::zero()
+        );
+    }
+
+    /// Test constant polynomials
+    #[test]
+    fn test_poly_constant() {
+        assert_eq!(
+            eval_poly_bigint(&[i64::MIN], u128::MAX),
+            BigInt::from(i64::MIN)
+        );
+        assert_eq!(eval_poly_bigint(&[42_u8], 0), BigInt::from(42));
+    }
+
+    /// Test a degree 10 polynomial against naive power summation
+    #[test]
+    fn test_poly_degree_ten() {
+        let coeffs = [3, -1, 4, -1, 5, -9, 2, -6, 5, -3, i64::MAX];
+        for x in [0, 1, -1, 7, -13, i64::MAX, i64::MIN] {
+            assert_eq!(eval_poly_bigint(&coeffs, x), naive_poly(&coeffs, x));
+        }
+    }
+
+    /// Test negative coefficients and negative x
+    #[test]
+    fn test_poly_negative() {
+        // -1 - x^2 at x = -3
+        assert_eq!(eval_poly_bigint(&[-1, 0, -1], -3), BigInt::from(-10));
+        // x^3 at x = -2
+        assert_eq!(eval_poly_bigint(&[0, 0, 0, 1], -2), BigInt::from(-8));
+    }
+
+    /// Test the modular variant against a modpow based reference
+    #[test]
+    fn test_poly_mod() {
+        let coeffs = [3, -1, 4, -1, 5, -9, 2, -6, 5, -3, i64::MAX];
+        let modulus = BigUint::from(1_000_000_007_u32);
+        for x in [0_i64, 1, 2, -1, 7, -13, i64::MAX, i64::MIN] {
+            let modulus_int = BigInt::from(modulus.clone());
+            let x_mod = BigInt::from(x).mod_floor(&modulus_int);
+            let reference = coeffs
+                .iter()
+                .enumerate()
+                .map(|(i, &coeff)| {
+                    BigInt::from(coeff) * x_mod.modpow(&BigInt::from(i), &modulus_int)
+                })
+                .sum::<BigInt>()
+                .mod_floor(&modulus_int);
+            let value = eval_poly_mod(&coeffs, x, &modulus);
+            assert_eq!(BigInt::from(value.clone()), reference);
+            assert!(value < modulus);
+        }
+    }
+
+    /// Checks MIN, MAX and the values one beyond of every primitive type
+    macro_rules! test_representable_limits {
+        ($($name:ident: $prim:ty),*) => {$(
+            #[test]
+            fn $name() {
+                let min = BigInt::from(<$prim>::MIN);
+                let max = BigInt::from(<$prim>::MAX);
+                assert!(min.is_representable_as::<$prim>());
+                assert!(max.is_representable_as::<$prim>());
+                assert!(!(&min - 1_u8).is_representable_as::<$prim>());
+                assert!(!(&max + 1_u8).is_representable_as::<$prim>());
+                assert!(BigInt::zero().is_representable_as::<$prim>());
+                assert!(max.magnitude().is_representable_as::<$prim>());
+                assert!(!(max.magnitude() + 1_u8).is_representable_as::<$prim>());
+            }
+        )*};
+    }
+
+    test_representable_limits!(
+        test_representable_u8: u8, test_representable_u16: u16, test_representable_u32: u32,
+        test_representable_u64: u64, test_representable_u128: u128, test_representable_usize: usize,
+        test_representable_i8: i8, test_representable_i16: i16, test_representable_i32: i32,
+        test_representable_i64: i64, test_representable_i128: i128, test_representable_isize: isize
+    );
+
+    /// Test negative values against unsigned targets
+    #[test]
+    fn test_representable_negative_unsigned() {
+        assert!(!BigInt::from(-1).is_representable_as::<u8>());
+        assert!(!BigInt::from(-1).is_representable_as::<u128>());
+        assert!(BigInt::from(-1).is_representable_as::<i8>());
+        assert!(!BigInt::from(-129).is_representable_as::<i8>());
+        assert!(!BigInt::from(-192).is_representable_as::<i8>());
+    }
+
+    /// Test consistency with the checked downcasts of ToPrimitive
+    #[test]
+    fn test_representable_consistency() {
+        let mut values: Vec<BigInt> = pseudo_random_values(300)
+            .into_iter()
+            .map(|value| value >> 100_u32)
+            .map(BigInt::from)
+            .collect();
+        for shift in 0..130_u32 {
+            let power = BigInt::from(1_u8) << shift;
+            values.extend([&power - 1_u8, power.clone(), &power + 1_u8]);
+        }
+        values.extend(values.clone().into_iter().map(|value| -value));
+        for value in &values {
+            assert_eq!(value.is_representable_as::<u8>(), value.to_u8().is_some());
+            assert_eq!(value.is_representable_as::<u16>(), value.to_u16().is_some());
+            assert_eq!(value.is_representable_as::<u32>(), value.to_u32().is_some());
+            assert_eq!(value.is_representable_as::<u64>(), value.to_u64().is_some());
+            assert_eq!(
+                value.is_representable_as::<u128>(),
+                value.to_u128().is_some()
+            );
+            assert_eq!(
+                value.is_representable_as::<usize>(),
+                value.to_usize().is_some()
+            );
+            assert_eq!(value.is_representable_as::<i8>(), value.to_i8().is_some());
+            assert_eq!(value.is_representable_as::<i16>(), value.to_i16().is_some());
+            assert_eq!(value.is_representable_as::<i32>(), value.to_i32().is_some());
+            assert_eq!(value.is_representable_as::<i64>(), value.to_i64().is_some());
+            assert_eq!(
+                value.is_representable_as::<i128>(),
+                value.to_i128().is_some()
+            );
+            assert_eq!(
+                value.is_representable_as::<isize>(),
+                value.to_isize().is_some()
+            );
+        }
+    }
+
+    /// Test powers of ten and the values just below them in radix 10
+    #[test]
+    fn test_digit_count_powers_of_ten() {
+        for exponent in [1_u32, 2, 9, 18, 19, 20, 38, 39, 100, 1000, 5000] {
+            let power = BigUint::from(10_u8).pow(exponent);
+            assert_eq!(power.digit_count(10), u64::from(exponent) + 1);
+            assert_eq!((&power - 1_u8).digit_count(10), u64::from(exponent));
+        }
+    }
+
+    /// Test the logarithm without std against the one of std, for every radix below 1000 and the largest ones
+    #[test]
+    fn test_log2() {
+        for radix in (2..1000).chain([u32::MAX - 1, u32::MAX]) {
+            let expected = f64::from(radix).log2();
+            assert!(
+                (log2(radix) - expected).abs() <= expected * f64::EPSILON * 2.0,
+                "{radix}"
+            );
+        }
+    }
+
+    /// Test that power of two radices match the bit length
+    #[test]
+    fn test_digit_count_power_of_two_radix() {
+        for value in pseudo_random_values(50) {
+            if value.is_zero() {
+                continue;
+            }
+            assert_eq!(value.digit_count(2), value.bits());
+            assert_eq!(value.digit_count(16), value.to_str_radix(16).len() as u64);
+            assert_eq!(value.digit_count(32), value.to_str_radix(32).len() as u64);
+        }
+        assert_eq!(BigUint::from(0xff_u8).digit_count(16), 2);
+        assert_eq!(BigUint::from(0x100_u16).digit_count(16), 3);
+    }
+
+    /// Test zero and negative values
+    #[test]
+    fn test_digit_count_zero_and_negative() {
+        assert_eq!(BigInt::zero().digit_count(10), 1);
+        assert_eq!(BigUint::zero().digit_count(2), 1);
+        assert_eq!(BigInt::from(-1000).digit_count(10), 4);
+        assert_eq!(BigInt::from(-999).digit_count(10), 3);
+        assert_eq!(BigInt::from(i128::MIN).digit_count(10), 39);
+        assert_eq!(BigInt::from(i128::MIN).digit_count(2), 128);
+    }
+
+    /// Test against to_str_radix for pseudo random values in every radix
+    #[test]
+    fn test_digit_count_against_to_str_radix() {
+        for value in pseudo_random_values(200) {
+            for radix in 2..=36 {
+                let expected = value.to_str_radix(radix).len() as u64;
+                assert_eq!(
+                    value.digit_count(radix),
+                    expected,
+                    "{value} in radix {radix}"
+                );
+                let negative = -BigInt::from(value.clone());
+                assert_eq!(negative.digit_count(radix), expected);
+            }
+        }
+    }
+
+    /// Test that radices below 2 are rejected
+    #[test]
+    #[should_panic(expected = "radix must be at least 2")]
+    fn test_digit_count_invalid_radix() {
+        BigUint::from(5_u8).digit_count(1);
+    }
+}
@@ -0,0 +1,307 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use num::{bigint::Sign, BigInt, BigUint};
+
+/// Why a string is not a valid integer literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiteralErrorKind {
+    /// There are no digits, like in `""`, `-` or `0x`.
+    NoDigits,
+    /// A character is not a digit of the radix, like `g` in `0xfg`.
+    InvalidDigit,
+    /// A `_` is at the start of the digits, like in `_1` or `0x_1`, or at the end of the literal.
+    MisplacedUnderscore,
+    /// An unsigned literal has a `-` sign.
+    NegativeUnsigned,
+}
+
+/// Error returned when a string is not a valid integer literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParseBigIntLiteralError {
+    /// Why the literal is invalid.
+    pub kind: LiteralErrorKind,
+    /// The byte offset of the offending character, or the length of the input if digits are missing at its end.
+    pub offset: usize,
+}
+
+impl fmt::Display for ParseBigIntLiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.kind {
+            LiteralErrorKind::NoDigits => "the literal has no digits",
+            LiteralErrorKind::InvalidDigit => "invalid digit",
+            LiteralErrorKind::MisplacedUnderscore => "`_` cannot start the digits or end a literal",
+            LiteralErrorKind::NegativeUnsigned => "an unsigned literal cannot be negative",
+        };
+        write!(f, "{reason} at byte {}", self.offset)
+    }
+}
+
+impl core::error::Error for ParseBigIntLiteralError {}
+
+/// Parses an integer in Rust literal syntax into a [`num::BigInt`].
+///
+/// The literal may start with `+` or `-`, use a `0x`, `0o` or `0b` prefix and contain `_` separators, which may not
+/// directly follow the sign or prefix or end the literal. Hexadecimal digits may be upper or lower case. Unlike
+/// [`num::Num::from_str_radix`] the value can use the prefixes and separators, and errors carry the byte offset of the
+/// problem.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::parse::{parse_bigint_literal, LiteralErrorKind};
+/// use num::BigInt;
+///
+/// assert_eq!(parse_bigint_literal("-0xFF_FF"), Ok(BigInt::from(-0xffff)));
+/// assert_eq!(parse_bigint_literal("+1_000"), Ok(BigInt::from(1000)));
+/// let error = parse_bigint_literal("0b102").unwrap_err();
+/// assert_eq!((error.kind, error.offset), (LiteralErrorKind::InvalidDigit, 4));
+/// ```
+pub fn parse_bigint_literal(s: &str) -> Result<BigInt, ParseBigIntLiteralError> {
+    let (negative, start) = match s.as_bytes().first() {
+        Some(b'-') => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    };
+    let magnitude = parse_unsigned(s, start)?;
+    let sign = if negative { Sign::Minus } else { Sign::Plus };
+    Ok(BigInt::from_biguint(sign, magnitude))
+}
+
+/// Parses an integer in Rust literal syntax into a [`num::BigUint`].
+///
+/// This accepts the same literals as [`parse_bigint_literal`], except that a `-` sign is an error, even for zero.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::parse::{parse_biguint_literal, LiteralErrorKind};
+/// use num::BigUint;
+///
+/// assert_eq!(parse_biguint_literal("0b1010_1010"), Ok(BigUint::from(0b1010_1010_u8)));
+/// assert_eq!(parse_biguint_literal("-1").unwrap_err().kind, LiteralErrorKind::NegativeUnsigned);
+/// ```
+pub fn parse_biguint_literal(s: &str) -> Result<BigUint, ParseBigIntLiteralError> {
+    match s.as_bytes().first() {
+        Some(b'-') => Err(ParseBigIntLiteralError {
+            kind: LiteralErrorKind::NegativeUnsigned,
+            offset: 0,
+        }),
+        Some(b'+') => parse_unsigned(s, 1),
+        _ => parse_unsigned(s, 0),
+    }
+}
+
+/// Parses the prefix and digits of a literal that start at byte `start`.
+fn parse_unsigned(s: &str, start: usize) -> Result<BigUint, ParseBigIntLiteralError> {
+    let bytes = s.as_bytes();
+    let (radix, digits_start) = match bytes.get(start..start + 2) {
+        Some(b"0x") => (16, start + 2),
+        Some(b"0o") => (8, start + 2),
+        Some(b"0b") => (2, start + 2),
+        _ => (10, start),
+    };
+    let error = |kind, offset| ParseBigIntLiteralError { kind, offset };
+    if bytes.get(digits_start) == Some(&b'_') {
+        return Err(error(LiteralErrorKind::MisplacedUnderscore, digits_start));
+    }
+    let mut values = Vec::with_capacity(s.len() - digits_start);
+    for (offset, ch) in s[digits_start..].char_indices() {
+        if ch == '_' {
+            continue;
+        }
+        match ch.to_digit(radix) {
+            Some(value) => values.push(value as u8),
+            None => return Err(error(LiteralErrorKind::InvalidDigit, digits_start + offset)),
+        }
+    }
+    if values.is_empty() {
+        return Err(error(LiteralErrorKind::NoDigits, s.len()));
+    }
+    if bytes.last() == Some(&b'_') {
+        return Err(error(LiteralErrorKind::MisplacedUnderscore, s.len() - 1));
+    }
+    // every value was checked against the radix, so this is always Some
+    Ok(BigUint::from_radix_be(&values, radix).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{BigInt, BigUint, Num, Zero};
+
+    use super::{
+        parse_bigint_literal, parse_biguint_literal, LiteralErrorKind, ParseBigIntLiteralError,
+    };
+
+    /// Shorthand for an expected error
+    fn error(kind: LiteralErrorKind, offset: usize) -> ParseBigIntLiteralError {
+        ParseBigIntLiteralError { kind, offset }
+    }
+
+    /// Test literals in every radix against from_str_radix on the digits without sign, prefix and separators
+    #[test]
+    fn test_round_trip() {
+        for literal in [
+            "0",
+            "-0",
+            "+7",
+            "-0xFFFF_FFFF_FFFF_FFFF_FFFF",
+            "0b1010_1010",
+            "0o777_777",
+            "0xdead_BEEF",
+            "123_456_789_012_345_678_901_234_567_890_123_456_789",
+            "-340282366920938463463374607431768211456",
+            "0x1__0",
+        ] {
+            let (negative, unsigned) = match literal.strip_prefix('-') {
+                Some(unsigned) => (true, unsigned),
+                None => (false, literal.trim_start_matches('+')),
+            };
+            let (radix, digits) = match unsigned.get(..2) {
+                Some("0x") => (16, &unsigned[2..]),
+                Some("0o") => (8, &unsigned[2..]),
+                Some("0b") => (2, &unsigned[2..]),
+                _ => (10, unsigned),
+            };
+            let digits = digits.replace('_', "");
+            let magnitude = BigInt::from_str_radix(&digits, radix).unwrap();
+            let expected = if negative { -magnitude } else { magnitude };
+            assert_eq!(
+                parse_bigint_literal(literal),
+                Ok(expected.clone()),
+                "{literal}"
+            );
+            if !negative {
+                assert_eq!(
+                    parse_biguint_literal(literal),
+                    Ok(expected.into_parts().1),
+                    "{literal}"
+                );
+            }
+        }
+    }
+
+    /// Test values far beyond u128
+    #[test]
+    fn test_large() {
+        let literal = format!("-0x{}", "f".repeat(200));
+        let expected = 1 - (BigInt::from(1) << 800_u32);
+        assert_eq!(parse_bigint_literal(&literal), Ok(expected));
+        let literal = format!("1{}", "_000".repeat(100));
+        assert_eq!(
+            parse_biguint_literal(&literal),
+            Ok(num::pow(BigUint::from(10_u8), 300))
+        );
+    }
+
+    /// Test inputs without digits
+    #[test]
+    fn test_no_digits() {
+        for literal in ["", "-", "+", "0x", "-0b"] {
+            assert_eq!(
+                parse_bigint_literal(literal),
+                Err(error(LiteralErrorKind::NoDigits, literal.len())),
+                "{literal}"
+            );
+        }
+        assert_eq!(
+            parse_biguint_literal("0o"),
+            Err(error(LiteralErrorKind::NoDigits, 2))
+        );
+    }
+
+    /// Test characters that are not digits of the radix
+    #[test]
+    fn test_invalid_digit() {
+        assert_eq!(
+            parse_bigint_literal("12a"),
+            Err(error(LiteralErrorKind::InvalidDigit, 2))
+        );
+        assert_eq!(
+            parse_bigint_literal("-0b012"),
+            Err(error(LiteralErrorKind::InvalidDigit, 5))
+        );
+        assert_eq!(
+            parse_bigint_literal("0o8"),
+            Err(error(LiteralErrorKind::InvalidDigit, 2))
+        );
+        assert_eq!(
+            parse_bigint_literal("0xfg"),
+            Err(error(LiteralErrorKind::InvalidDigit, 3))
+        );
+        assert_eq!(
+            parse_bigint_literal("0X10"),
+            Err(error(LiteralErrorKind::InvalidDigit, 1))
+        );
+        assert_eq!(
+            parse_bigint_literal("--1"),
+            Err(error(LiteralErrorKind::InvalidDigit, 1))
+        );
+        assert_eq!(
+            parse_bigint_literal(" 1"),
+            Err(error(LiteralErrorKind::InvalidDigit, 0))
+        );
+        assert_eq!(
+            parse_bigint_literal("1é"),
+            Err(error(LiteralErrorKind::InvalidDigit, 1))
+        );
+        assert_eq!(
+            parse_biguint_literal("+-1"),
+            Err(error(LiteralErrorKind::InvalidDigit, 1))
+        );
+    }
+
+    /// Test underscores at the start of the digits, after the sign or prefix, and at the end of the literal
+    #[test]
+    fn test_misplaced_underscore() {
+        assert_eq!(
+            parse_bigint_literal("_1"),
+            Err(error(LiteralErrorKind::MisplacedUnderscore, 0))
+        );
+        assert_eq!(
+            parse_bigint_literal("-_1"),
+            Err(error(LiteralErrorKind::MisplacedUnderscore, 1))
+        );
+        assert_eq!(
+            parse_bigint_literal("1_"),
+            Err(error(LiteralErrorKind::MisplacedUnderscore, 1))
+        );
+        assert_eq!(
+            parse_biguint_literal("0xff_"),
+            Err(error(LiteralErrorKind::MisplacedUnderscore, 4))
+        );
+        assert_eq!(
+            parse_bigint_literal("0x_1"),
+            Err(error(LiteralErrorKind::MisplacedUnderscore, 2))
+        );
+        assert_eq!(
+            parse_bigint_literal("-0b_1"),
+            Err(error(LiteralErrorKind::MisplacedUnderscore, 3))
+        );
+        assert_eq!(
+            parse_biguint_literal("0x_"),
+            Err(error(LiteralErrorKind::MisplacedUnderscore, 2))
+        );
+        assert_eq!(
+            parse_bigint_literal("+0o__"),
+            Err(error(LiteralErrorKind::MisplacedUnderscore, 3))
+        );
+        assert_eq!(parse_bigint_literal("0x1_0"), Ok(BigInt::from(16)));
+    }
+
+    /// Test that unsigned literals reject a minus sign, even for zero
+    #[test]
+    fn test_negative_unsigned() {
+        assert_eq!(
+            parse_biguint_literal("-1"),
+            Err(error(LiteralErrorKind::NegativeUnsigned, 0))
+        );
+        assert_eq!(
+            parse_biguint_literal("-0"),
+            Err(error(LiteralErrorKind::NegativeUnsigned, 0))
+        );
+        assert_eq!(parse_biguint_literal("+0"), Ok(BigUint::zero()));
+        assert_eq!(
+            error(LiteralErrorKind::NegativeUnsigned, 0).to_string(),
+            "an unsigned literal cannot be negative at byte 0"
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+
+#[cfg(feature = "biguint")]
+use num::BigUint;
+#[cfg(feature = "bigint")]
+use num::{bigint::Sign, BigInt};
+
+/// Values with more decimal digits than this are rendered with the middle digits elided.
+const MAX_RENDERED_DIGITS: usize = 40;
+
+/// Why a bignum does not fit into a primitive integer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FromBigIntErrorKind {
+    /// The value is larger than the maximum of the type.
+    Overflow,
+    /// The value is smaller than the minimum of a signed type.
+    Underflow,
+    /// The value is negative and the type is unsigned.
+    Negative,
+}
+
+/// Error returned when a bignum does not fit into a primitive integer type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FromBigIntError {
+    /// Why the value does not fit.
+    pub kind: FromBigIntErrorKind,
+    /// The value in decimal. Values with more than 40 digits keep their first and last digits and the digit count.
+    pub value: String,
+    /// The name of the target type, like `u64`.
+    pub target: &'static str,
+    /// The minimum of the target type.
+    pub min: i128,
+    /// The maximum of the target type.
+    pub max: u128,
+}
+
+impl FromBigIntError {
+    fn new<T: fmt::Display>(
+        kind: FromBigIntErrorKind,
+        value: &T,
+        target: &'static str,
+        min: i128,
+        max: u128,
+    ) -> Self {
+        FromBigIntError {
+            kind,
+            value: render(value),
+            target,
+            min,
+            max,
+        }
+    }
+}
+
+impl fmt::Display for FromBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.kind {
+            FromBigIntErrorKind::Overflow => "it is too large",
+            FromBigIntErrorKind::Underflow => "it is too small",
+            FromBigIntErrorKind::Negative => "it is negative",
+        };
+        write!(
+            f,
+            "{} does not fit into {}, which holds {}..={}, because {reason}",
+            self.value, self.target, self.min, self.max
+        )
+    }
+}
+
+impl core::error::Error for FromBigIntError {}
+
+/// Renders a value in decimal, eliding the middle digits of long values.
+fn render<T: fmt::Display>(value: &T) -> String {
+    let rendered = value.to_string();
+    let (sign, digits) = match rendered.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", rendered.as_str()),
+    };
+    if digits.len() <= MAX_RENDERED_DIGITS {
+        return rendered;
+    }
+    // decimal digits are ASCII, so these are char boundaries
+    let (head, tail) = (&digits[..20], &digits[digits.len() - 10..]);
+    format!("{sign}{head}...{tail} ({} digits)", digits.len())
+}
+
+/// Checked conversion of a [`num::BigInt`] back into a primitive integer type.
+///
+/// Unlike `ToPrimitive`, the error says why the value does not fit and carries the value and the range of the type.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::prim::{FromBigIntErrorKind, TryFromBigInt};
+/// use num::BigInt;
+///
+/// assert_eq!(u64::try_from_bigint(&BigInt::from(u64::MAX)), Ok(u64::MAX));
+/// let error = u8::try_from_bigint(&BigInt::from(-1)).unwrap_err();
+/// assert_eq!(error.kind, FromBigIntErrorKind::Negative);
+/// assert_eq!(error.to_string(), "-1 does not fit into u8, which holds 0..=255, because it is negative");
+/// ```
+#[cfg(feature = "bigint")]
+pub trait TryFromBigInt: Sized {
+    fn try_from_bigint(value: &BigInt) -> Result<Self, FromBigIntError>;
+}
+
+/// Checked conversion of a [`num::BigUint`] back into an unsigned primitive integer type.
+///
+/// See [`TryFromBigInt`] for details. A `BigUint` can only overflow.
+#[cfg(feature = "biguint")]
+pub trait TryFromBigUint: Sized {
+    fn try_from_biguint(value: &BigUint) -> Result<Self, FromBigIntError>;
+}
+
+#[cfg(feature = "bigint")]
+macro_rules! impl_try_from_bigint {
+    ($($prim:ident),*) => {$(
+        impl TryFromBigInt for $prim {
+            fn try_from_bigint(value: &BigInt) -> Result<Self, FromBigIntError> {
+                $prim::try_from(value).map_err(|_| {
+                    let kind = match value.sign() {
+                        Sign::Minus if $prim::MIN == 0 => FromBigIntErrorKind::Negative,
+                        Sign::Minus => FromBigIntErrorKind::Underflow,
+                        _ => FromBigIntErrorKind::Overflow,
+                    };
+                    FromBigIntError::new(kind, value, stringify!($prim), $prim::MIN as i128, $prim::MAX as u128)
+                })
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "bigint")]
+impl_try_from_bigint!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(feature = "biguint")]
+macro_rules! impl_try_from_biguint {
+    ($($prim:ident),*) => {$(
+        impl TryFromBigUint for $prim {
+            fn try_from_biguint(value: &BigUint) -> Result<Self, FromBigIntError> {
+                $prim::try_from(value).map_err(|_| {
+                    FromBigIntError::new(
+                        FromBigIntErrorKind::Overflow,
+                        value,
+                        stringify!($prim),
+                        0,
+                        $prim::MAX as u128,
+                    )
+                })
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "biguint")]
+impl_try_from_biguint!(u8, u16, u32, u64, u128, usize);
+
+/// Conversion of a [`num::BigInt`] into a primitive integer type that clamps to the range of the type.
+///
+/// Values above the range give `MAX` and values below it give `MIN`, which is 0 for the unsigned types. The
+/// conversion does not allocate, and values that are wider than the type are clamped from their sign and bit length
+/// alone.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::prim::SaturatingFromBigInt;
+/// use num::BigInt;
+///
+/// assert_eq!(u8::saturating_from_bigint(&BigInt::from(300)), u8::MAX);
+/// assert_eq!(u8::saturating_from_bigint(&BigInt::from(-1)), 0);
+/// assert_eq!(i8::saturating_from_bigint(&BigInt::from(-100)), -100);
+/// ```
+#[cfg(feature = "bigint")]
+pub trait SaturatingFromBigInt {
+    fn saturating_from_bigint(value: &BigInt) -> Self;
+}
+
+/// Conversion of a [`num::BigInt`] into a primitive integer type that keeps the low bits.
+///
+/// The value is reduced modulo 2<sup>N</sup> for an N bit type, and signed types reinterpret the result in two's
+/// complement, so the conversion agrees with an `as` cast and with wrapping arithmetic. It does not allocate.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::prim::WrappingFromBigInt;
+/// use num::BigInt;
+///
+/// assert_eq!(u8::wrapping_from_bigint(&BigInt::from(-1)), u8::MAX);
+/// assert_eq!(i8::wrapping_from_bigint(&BigInt::from(200)), -56);
+/// assert_eq!(u16::wrapping_from_bigint(&(BigInt::from(1) << 200_u32)), 0);
+/// ```
+#[cfg(feature = "bigint")]
+pub trait WrappingFromBigInt {
+    fn wrapping_from_bigint(value: &BigInt) -> Self;
+}
+
+#[cfg(feature = "bigint")]
+macro_rules! impl_saturating_wrapping_from_bigint {
+    ($($prim:ident),*) => {$(
+        impl SaturatingFromBigInt for $prim {
+            fn saturating_from_bigint(value: &BigInt) -> Self {
+                let clamped = || if value.sign() == Sign::Minus { $prim::MIN } else { $prim::MAX };
+                if value.bits() > u64::from($prim::BITS) {
+                    return clamped();
+                }
+                $prim::try_from(value).unwrap_or_else(|_| clamped())
+            }
+        }
+
+        impl WrappingFromBigInt for $prim {
+            fn wrapping_from_bigint(value: &BigInt) -> Self {
+                let mut digits = value.iter_u64_digits();
+                let low = digits.next().unwrap_or(0);
+                let high = digits.next().unwrap_or(0);
+                let magnitude = u128::from(high) << 64 | u128::from(low);
+                let bits = if value.sign() == Sign::Minus {
+                    magnitude.wrapping_neg()
+                } else {
+                    magnitude
+                };
+                bits as $prim
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "bigint")]
+impl_saturating_wrapping_from_bigint!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "bigint")]
+    use num::BigInt;
+    #[cfg(feature = "biguint")]
+    use num::BigUint;
+
+    #[cfg(feature = "biguint")]
+    use super::TryFromBigUint;
+    use super::{render, FromBigIntErrorKind};
+    #[cfg(feature = "bigint")]
+    use super::{FromBigIntError, SaturatingFromBigInt, TryFromBigInt, WrappingFromBigInt};
+
+    /// Checks that MIN and MAX convert and that one beyond either end fails with the given kinds
+    #[cfg(feature = "bigint")]
+    fn check<T>(min: T, max: T, below: FromBigIntErrorKind)
+    where
+        T: TryFromBigInt + Into<BigInt> + PartialEq + core::fmt::Debug + Copy,
+    {
+        let (min_big, max_big): (BigInt, BigInt) = (min.into(), max.into());
+        assert_eq!(T::try_from_bigint(&min_big), Ok(min));
+        assert_eq!(T::try_from_bigint(&max_big), Ok(max));
+        let (above, below_min): (BigInt, BigInt) = (max_big + 1, min_big - 1);
+        let above_error = T::try_from_bigint(&above).unwrap_err();
+        assert_eq!(above_error.kind, FromBigIntErrorKind::Overflow);
+        assert_eq!(above_error.value, above.to_string());
+        let below_error = T::try_from_bigint(&below_min).unwrap_err();
+        assert_eq!(below_error.kind, below);
+        assert_eq!(below_error.value, below_min.to_string());
+    }
+
+    /// Test the ends of the range of every signed type
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_signed() {
+        check(i8::MIN, i8::MAX, FromBigIntErrorKind::Underflow);
+        check(i16::MIN, i16::MAX, FromBigIntErrorKind::Underflow);
+        check(i32::MIN, i32::MAX, FromBigIntErrorKind::Underflow);
+        check(i64::MIN, i64::MAX, FromBigIntErrorKind::Underflow);
+        check(i128::MIN, i128::MAX, FromBigIntErrorKind::Underflow);
+        check(isize::MIN, isize::MAX, FromBigIntErrorKind::Underflow);
+    }
+
+    /// Test the ends of the range of every unsigned type, where values below zero are negative
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_unsigned() {
+        check(u8::MIN, u8::MAX, FromBigIntErrorKind::Negative);
+        check(u16::MIN, u16::MAX, FromBigIntErrorKind::Negative);
+        check(u32::MIN, u32::MAX, FromBigIntErrorKind::Negative);
+        check(u64::MIN, u64::MAX, FromBigIntErrorKind::Negative);
+        check(u128::MIN, u128::MAX, FromBigIntErrorKind::Negative);
+        check(usize::MIN, usize::MAX, FromBigIntErrorKind::Negative);
+        let huge_negative = -(BigInt::from(u128::MAX) << 64_u32);
+        assert_eq!(
+            u8::try_from_bigint(&huge_negative).unwrap_err().kind,
+            FromBigIntErrorKind::Negative
+        );
+    }
+
+    /// Test the target name and range in the error
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_error_range() {
+        let error = i16::try_from_bigint(&BigInt::from(40000)).unwrap_err();
+        assert_eq!(
+            error,
+            FromBigIntError {
+                kind: FromBigIntErrorKind::Overflow,
+                value: "40000".into(),
+                target: "i16",
+                min: -32768,
+                max: 32767,
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "40000 does not fit into i16, which holds -32768..=32767, because it is too large"
+        );
+        let error = i128::try_from_bigint(&(BigInt::from(i128::MIN) - 1)).unwrap_err();
+        assert_eq!(error.min, i128::MIN);
+        assert!(error.to_string().ends_with("because it is too small"));
+    }
+
+    /// Test the out of range BigUint conversions
+    #[cfg(feature = "biguint")]
+    #[test]
+    fn test_biguint() {
+        assert_eq!(u8::try_from_biguint(&BigUint::from(255_u8)), Ok(u8::MAX));
+        assert_eq!(u8::try_from_biguint(&BigUint::from(0_u8)), Ok(0));
+        assert_eq!(
+            u128::try_from_biguint(&BigUint::from(u128::MAX)),
+            Ok(u128::MAX)
+        );
+        let error = u64::try_from_biguint(&(BigUint::from(u64::MAX) + 1_u8)).unwrap_err();
+        assert_eq!(error.kind, FromBigIntErrorKind::Overflow);
+        assert_eq!(error.target, "u64");
+        assert_eq!((error.min, error.max), (0, u64::MAX.into()));
+        assert_eq!(error.value, "18446744073709551616");
+    }
+
+    /// Checks the clamping one beyond either end of the range
+    #[cfg(feature = "bigint")]
+    fn check_saturating<T>(min: T, max: T)
+    where
+        T: SaturatingFromBigInt + Into<BigInt> + PartialEq + core::fmt::Debug + Copy,
+    {
+        let (min_big, max_big): (BigInt, BigInt) = (min.into(), max.into());
+        assert_eq!(T::saturating_from_bigint(&min_big), min);
+        assert_eq!(T::saturating_from_bigint(&max_big), max);
+        let (above, below): (BigInt, BigInt) = (&max_big + 1, &min_big - 1);
+        assert_eq!(T::saturating_from_bigint(&above), max);
+        assert_eq!(T::saturating_from_bigint(&below), min);
+        let (huge, huge_negative): (BigInt, BigInt) =
+            (BigInt::from(7) << 1000_u32, BigInt::from(-7) << 1000_u32);
+        assert_eq!(T::saturating_from_bigint(&huge), max);
+        assert_eq!(T::saturating_from_bigint(&huge_negative), min);
+    }
+
+    /// Test the clamping of every type
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_saturating() {
+        check_saturating(i8::MIN, i8::MAX);
+        check_saturating(i16::MIN, i16::MAX);
+        check_saturating(i32::MIN, i32::MAX);
+        check_saturating(i64::MIN, i64::MAX);
+        check_saturating(i128::MIN, i128::MAX);
+        check_saturating(isize::MIN, isize::MAX);
+        check_saturating(u8::MIN, u8::MAX);
+        check_saturating(u16::MIN, u16::MAX);
+        check_saturating(u32::MIN, u32::MAX);
+        check_saturating(u64::MIN, u64::MAX);
+        check_saturating(u128::MIN, u128::MAX);
+        check_saturating(usize::MIN, usize::MAX);
+        let zero = BigInt::from(0);
+        assert_eq!(i8::saturating_from_bigint(&zero), 0);
+        assert_eq!(u128::saturating_from_bigint(&zero), 0);
+        assert_eq!(u8::saturating_from_bigint(&BigInt::from(-1)), 0);
+        assert_eq!(i64::saturating_from_bigint(&BigInt::from(-5)), -5);
+    }
+
+    /// Test that wrapping agrees with `as` casts for values that fit into i128
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_wrapping_matches_casts() {
+        let mut values = vec![0, 1, -1, 127, 128, -128, -129, 255, 256, -300, 65535, 65536];
+        values.extend([
+            i64::MIN as i128,
+            i64::MAX as i128,
+            u64::MAX as i128,
+            i128::MIN,
+            i128::MAX,
+        ]);
+        // a deterministic spread of values across the whole range
+        let mut state = 0x2545_f491_4f6c_dd1d_u128;
+        for _ in 0..1000 {
+            state = state
+                .wrapping_mul(0x5851_f42d_4c95_7f2d_1405_7b7e_f767_814f)
+                .wrapping_add(1);
+            values.push((state as i128) >> (state % 127));
+        }
+        for value in values {
+            let big = BigInt::from(value);
+            assert_eq!(i8::wrapping_from_bigint(&big), value as i8, "{value}");
+            assert_eq!(i16::wrapping_from_bigint(&big), value as i16, "{value}");
+            assert_eq!(i32::wrapping_from_bigint(&big), value as i32, "{value}");
+            assert_eq!(i64::wrapping_from_bigint(&big), value as i64, "{value}");
+            assert_eq!(i128::wrapping_from_bigint(&big), value, "{value}");
+            assert_eq!(isize::wrapping_from_bigint(&big), value as isize, "{value}");
+            assert_eq!(u8::wrapping_from_bigint(&big), value as u8, "{value}");
+            assert_eq!(u16::wrapping_from_bigint(&big), value as u16, "{value}");
+            assert_eq!(u32::wrapping_from_bigint(&big), value as u32, "{value}");
+            assert_eq!(u64::wrapping_from_bigint(&big), value as u64, "{value}");
+            assert_eq!(u128::wrapping_from_bigint(&big), value as u128, "{value}");
+            assert_eq!(usize::wrapping_from_bigint(&big), value as usize, "{value}");
+        }
+    }
+
+    /// Test wrapping values that are wider than 128 bits
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_wrapping_wide() {
+        let one = BigInt::from(1);
+        let power: BigInt = &one << 128_u32;
+        assert_eq!(u128::wrapping_from_bigint(&power), 0);
+        assert_eq!(u128::wrapping_from_bigint(&(&power - 1)), u128::MAX);
+        assert_eq!(i128::wrapping_from_bigint(&(&power - 1)), -1);
+        assert_eq!(u128::wrapping_from_bigint(&(&power + 5)), 5);
+        assert_eq!(u8::wrapping_from_bigint(&-&power), 0);
+        assert_eq!(u8::wrapping_from_bigint(&(-&power - 1)), u8::MAX);
+        assert_eq!(i64::wrapping_from_bigint(&(-&power - 2)), -2);
+        let wide: BigInt = (BigInt::from(0xdead_beef_u32) << 300_u32) + 0x1234_5678_9abc_def0_u64;
+        assert_eq!(u64::wrapping_from_bigint(&wide), 0x1234_5678_9abc_def0);
+        assert_eq!(u16::wrapping_from_bigint(&wide), 0xdef0);
+        assert_eq!(i16::wrapping_from_bigint(&wide), 0xdef0_u16 as i16);
+        assert_eq!(
+            u64::wrapping_from_bigint(&-&wide),
+            0x1234_5678_9abc_def0_u64.wrapping_neg()
+        );
+    }
+
+    /// Test that long values are rendered with their first and last digits
+    #[test]
+    fn test_render() {
+        assert_eq!(render(&"-1234"), "-1234");
+        let long = "1".repeat(40);
+        assert_eq!(render(&long), long);
+        let long = format!("-{}{}", "9".repeat(30), "0".repeat(30));
+        assert_eq!(
+            render(&long),
+            "-99999999999999999999...0000000000 (60 digits)"
+        );
+    }
+}
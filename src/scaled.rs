@@ -0,0 +1,217 @@
+use num::{bigint::Sign, BigInt, BigUint, Zero};
+
+#[cfg(feature = "biguint")]
+use crate::InfallibleToBigUint;
+use crate::{
+    consts::{ten_pow, MAX_TEN_POW_U128, TEN_POW_U128},
+    InfallibleToBigInt,
+};
+
+/// The largest exponent that is applied by multiplying with the `u128` powers of ten one after another.
+///
+/// Every multiplication is a pass over the digits, so larger exponents build `10^exp10` once and multiply by it.
+const MAX_CHUNKED_EXP: u32 = 1024;
+
+/// Conversion to a [`num::BigInt`] scaled by a power of ten, for fixed-point values stored as integer minor units.
+///
+/// `value.to_bigint_scaled(exp10)` equals `value.to_bigint() * 10^exp10`, but builds a single result. Values with an
+/// `i128` fast path whose scaled value still fits are multiplied without allocating a `BigInt` first. Other values
+/// are multiplied in place with powers of ten from a table of `u128` values, and only exponents above 1024 build the
+/// power as a `BigInt`. Zero stays zero without looking at the exponent, so even `u32::MAX` is cheap.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::scaled::ToBigIntScaled;
+/// use num::BigInt;
+///
+/// // -12.34 stored as cents, scaled to units of 10^-6
+/// assert_eq!((-1234_i64).to_bigint_scaled(4), BigInt::from(-12_340_000));
+/// assert_eq!(0_u8.to_bigint_scaled(u32::MAX), BigInt::from(0));
+/// ```
+pub trait ToBigIntScaled {
+    fn to_bigint_scaled(&self, exp10: u32) -> BigInt;
+}
+
+impl<T: InfallibleToBigInt + ?Sized> ToBigIntScaled for T {
+    fn to_bigint_scaled(&self, exp10: u32) -> BigInt {
+        let (sign, mut magnitude) = match self.to_i128_fast() {
+            Some(0) => return BigInt::zero(),
+            Some(value) => {
+                if let Some(power) = TEN_POW_U128.get(exp10 as usize) {
+                    // powers up to 10^38 fit into an i128
+                    if let Some(scaled) = value.checked_mul(*power as i128) {
+                        return BigInt::from(scaled);
+                    }
+                }
+                let sign = if value < 0 { Sign::Minus } else { Sign::Plus };
+                (sign, BigUint::from(value.unsigned_abs()))
+            }
+            None => self.to_bigint().into_parts(),
+        };
+        scale_in_place(&mut magnitude, exp10);
+        BigInt::from_biguint(sign, magnitude)
+    }
+}
+
+/// Conversion to a [`num::BigUint`] scaled by a power of ten.
+///
+/// See [`ToBigIntScaled`] for details. The fast path uses `u128` instead of `i128`.
+///
+/// # Example
+/// ```
+/// use infallible_tobigint::scaled::ToBigUintScaled;
+/// use num::BigUint;
+///
+/// assert_eq!(u128::MAX.to_biguint_scaled(2), BigUint::from(u128::MAX) * 100_u8);
+/// ```
+#[cfg(feature = "biguint")]
+pub trait ToBigUintScaled {
+    fn to_biguint_scaled(&self, exp10: u32) -> BigUint;
+}
+
+#[cfg(feature = "biguint")]
+impl<T: InfallibleToBigUint + ?Sized> ToBigUintScaled for T {
+    fn to_biguint_scaled(&self, exp10: u32) -> BigUint {
+        let mut magnitude = match self.to_u128_fast() {
+            Some(0) => return BigUint::zero(),
+            Some(value) => {
+                let scaled = TEN_POW_U128
+                    .get(exp10 as usize)
+                    .and_then(|power| value.checked_mul(*power));
+                if let Some(scaled) = scaled {
+                    return BigUint::from(scaled);
+                }
+                BigUint::from(value)
+            }
+            None => self.to_biguint(),
+        };
+        scale_in_place(&mut magnitude, exp10);
+        magnitude
+    }
+}
+
+/// Multiplies `magnitude` by `10^exp10` in place.
+fn scale_in_place(magnitude: &mut BigUint, exp10: u32) {
+    if magnitude.is_zero() || exp10 == 0 {
+        return;
+    }
+    if exp10 > MAX_CHUNKED_EXP {
+        *magnitude *= ten_pow(exp10);
+        return;
+    }
+    let mut remaining = exp10;
+    while remaining > MAX_TEN_POW_U128 {
+        *magnitude *= TEN_POW_U128[MAX_TEN_POW_U128 as usize];
+        remaining -= MAX_TEN_POW_U128;
+    }
+    *magnitude *= TEN_POW_U128[remaining as usize];
+}
+
+#[cfg(test)]
+mod tests {
+    use num::{pow::Pow, BigInt, Zero};
+    #[cfg(feature = "biguint")]
+    use num::{BigUint, One};
+
+    use super::ToBigIntScaled;
+    #[cfg(feature = "biguint")]
+    use super::ToBigUintScaled;
+    use crate::InfallibleToBigInt;
+    #[cfg(feature = "biguint")]
+    use crate::InfallibleToBigUint;
+
+    /// Exponents around the table limit, the chunk boundaries and the switch to a single power
+    const EXPONENTS: [u32; 16] = [
+        0, 1, 2, 18, 19, 37, 38, 39, 76, 77, 100, 500, 1024, 1025, 2000, 3000,
+    ];
+
+    /// Test signed values of several widths against the naive formula
+    #[test]
+    fn test_against_naive() {
+        let wide = BigInt::from(u128::MAX) * BigInt::from(u128::MAX);
+        let values = [
+            BigInt::zero(),
+            BigInt::from(1),
+            BigInt::from(-1),
+            BigInt::from(-1234),
+            BigInt::from(i64::MAX),
+            BigInt::from(i128::MIN),
+            BigInt::from(i128::MAX),
+            BigInt::from(u128::MAX),
+            -wide.clone(),
+            wide,
+        ];
+        for value in &values {
+            for exp10 in EXPONENTS {
+                let expected = value * Pow::pow(BigInt::from(10), exp10);
+                assert_eq!(value.to_bigint_scaled(exp10), expected, "{value} {exp10}");
+            }
+        }
+        for exp10 in EXPONENTS {
+            let expected = BigInt::from(i128::MIN) * Pow::pow(BigInt::from(10), exp10);
+            assert_eq!(i128::MIN.to_bigint_scaled(exp10), expected);
+            let expected = BigInt::from(-7) * Pow::pow(BigInt::from(10), exp10);
+            assert_eq!((-7_i8).to_bigint_scaled(exp10), expected);
+        }
+    }
+
+    /// Test that the exponent zero is the plain conversion
+    #[test]
+    fn test_exponent_zero() {
+        for value in [i128::MIN, -1, 0, 1, i128::MAX] {
+            assert_eq!(value.to_bigint_scaled(0), value.to_bigint());
+        }
+        assert_eq!(u128::MAX.to_bigint_scaled(0), u128::MAX.to_bigint());
+    }
+
+    /// Test that zero ignores even the largest exponent, which would not fit into memory
+    #[test]
+    fn test_zero_huge_exponent() {
+        assert!(0_i32.to_bigint_scaled(u32::MAX).is_zero());
+        assert!(BigInt::zero().to_bigint_scaled(u32::MAX).is_zero());
+        #[cfg(feature = "biguint")]
+        {
+            assert!(0_u64.to_biguint_scaled(u32::MAX).is_zero());
+            assert!(BigUint::zero().to_biguint_scaled(u32::MAX).is_zero());
+        }
+    }
+
+    /// Test that negative values keep their sign on the fast path, when spilling and for large exponents
+    #[test]
+    fn test_sign() {
+        assert_eq!((-5_i64).to_bigint_scaled(3), BigInt::from(-5000));
+        assert_eq!(
+            (-5_i64).to_bigint_scaled(40),
+            BigInt::from(-5) * Pow::pow(BigInt::from(10), 40_u32)
+        );
+        assert!((-5_i64).to_bigint_scaled(5000) < BigInt::zero());
+    }
+
+    /// Test unsigned values against the naive formula
+    #[cfg(feature = "biguint")]
+    #[test]
+    fn test_unsigned_against_naive() {
+        let values = [
+            BigUint::one(),
+            BigUint::from(u64::MAX),
+            BigUint::from(u128::MAX),
+            BigUint::from(u128::MAX) << 100_u32,
+        ];
+        for value in &values {
+            for exp10 in EXPONENTS {
+                let expected = value * Pow::pow(BigUint::from(10_u8), exp10);
+                assert_eq!(value.to_biguint_scaled(exp10), expected, "{value} {exp10}");
+                assert_eq!(
+                    value.to_bigint_scaled(exp10),
+                    BigInt::from(expected),
+                    "{value} {exp10}"
+                );
+            }
+        }
+        for exp10 in EXPONENTS {
+            let expected = BigUint::from(u128::MAX) * Pow::pow(BigUint::from(10_u8), exp10);
+            assert_eq!(u128::MAX.to_biguint_scaled(exp10), expected);
+        }
+        assert_eq!(42_u8.to_biguint_scaled(0), 42_u8.to_biguint());
+    }
+}
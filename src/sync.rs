@@ -0,0 +1,389 @@
+#[cfg(feature = "std")]
+use core::fmt;
+use core::sync::atomic::{self, Ordering};
+#[cfg(feature = "std")]
+use std::sync::{Mutex, PoisonError, RwLock};
+
+#[cfg(feature = "bigint")]
+use num::BigInt;
+#[cfg(feature = "biguint")]
+use num::BigUint;
+
+#[cfg(feature = "bigint")]
+use crate::InfallibleToBigInt;
+#[cfg(feature = "biguint")]
+use crate::InfallibleToBigUint;
+
+/// Error returned when a value behind a lock is not converted because the lock is poisoned.
+///
+/// A lock is poisoned when a thread panicked while holding it, so the value may be in the middle of an update. Use
+/// the `_ignore_poison` methods to convert it anyway.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PoisonedConversion;
+
+#[cfg(feature = "std")]
+impl fmt::Display for PoisonedConversion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the lock holding the value is poisoned")
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::error::Error for PoisonedConversion {}
+
+/// Conversions of a value behind a [`Mutex`] or [`RwLock`] into a [`num::BigInt`].
+///
+/// The lock is held only for the duration of the conversion, and an `RwLock` is only locked for reading.
+///
+/// # Example
+/// ```
+/// use std::sync::{Arc, Mutex};
+///
+/// use infallible_tobigint::sync::LockToBigIntExt;
+/// use num::BigInt;
+///
+/// let counter = Arc::new(Mutex::new(u64::MAX));
+/// assert_eq!(counter.lock_to_bigint(), Ok(BigInt::from(u64::MAX)));
+/// ```
+#[cfg(all(feature = "bigint", feature = "std"))]
+pub trait LockToBigIntExt {
+    /// Locks the value and converts it, failing if the lock is poisoned.
+    fn lock_to_bigint(&self) -> Result<BigInt, PoisonedConversion>;
+
+    /// Locks the value and converts it, even if the lock is poisoned.
+    fn lock_to_bigint_ignore_poison(&self) -> BigInt;
+}
+
+#[cfg(all(feature = "bigint", feature = "std"))]
+impl<T: InfallibleToBigInt + ?Sized> LockToBigIntExt for Mutex<T> {
+    fn lock_to_bigint(&self) -> Result<BigInt, PoisonedConversion> {
+        let guard = self.lock().map_err(|_| PoisonedConversion)?;
+        Ok(guard.to_bigint())
+    }
+
+    fn lock_to_bigint_ignore_poison(&self) -> BigInt {
+        self.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .to_bigint()
+    }
+}
+
+#[cfg(all(feature = "bigint", feature = "std"))]
+impl<T: InfallibleToBigInt + ?Sized> LockToBigIntExt for RwLock<T> {
+    fn lock_to_bigint(&self) -> Result<BigInt, PoisonedConversion> {
+        let guard = self.read().map_err(|_| PoisonedConversion)?;
+        Ok(guard.to_bigint())
+    }
+
+    fn lock_to_bigint_ignore_poison(&self) -> BigInt {
+        self.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .to_bigint()
+    }
+}
+
+/// Conversions of a value behind a [`Mutex`] or [`RwLock`] into a [`num::BigUint`].
+///
+/// See [`LockToBigIntExt`] for details.
+#[cfg(all(feature = "biguint", feature = "std"))]
+pub trait LockToBigUintExt {
+    /// Locks the value and converts it, failing if the lock is poisoned.
+    fn lock_to_biguint(&self) -> Result<BigUint, PoisonedConversion>;
+
+    /// Locks the value and converts it, even if the lock is poisoned.
+    fn lock_to_biguint_ignore_poison(&self) -> BigUint;
+}
+
+#[cfg(all(feature = "biguint", feature = "std"))]
+impl<T: InfallibleToBigUint + ?Sized> LockToBigUintExt for Mutex<T> {
+    fn lock_to_biguint(&self) -> Result<BigUint, PoisonedConversion> {
+        let guard = self.lock().map_err(|_| PoisonedConversion)?;
+        Ok(guard.to_biguint())
+    }
+
+    fn lock_to_biguint_ignore_poison(&self) -> BigUint {
+        self.lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .to_biguint()
+    }
+}
+
+#[cfg(all(feature = "biguint", feature = "std"))]
+impl<T: InfallibleToBigUint + ?Sized> LockToBigUintExt for RwLock<T> {
+    fn lock_to_biguint(&self) -> Result<BigUint, PoisonedConversion> {
+        let guard = self.read().map_err(|_| PoisonedConversion)?;
+        Ok(guard.to_biguint())
+    }
+
+    fn lock_to_biguint_ignore_poison(&self) -> BigUint {
+        self.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .to_biguint()
+    }
+}
+
+/// Conversion of the value of an atomic integer into a [`num::BigInt`], loaded with an explicit memory ordering.
+///
+/// The atomics are implemented where the target supports them, so `AtomicU64` is missing on targets without 64 bit
+/// atomics.
+///
+/// # Panics
+/// Like [`AtomicU64::load`](std::sync::atomic::AtomicU64::load), panics if `order` is `Release` or `AcqRel`.
+///
+/// # Example
+/// ```
+/// use std::sync::atomic::{AtomicU64, Ordering};
+///
+/// use infallible_tobigint::sync::AtomicToBigInt;
+/// use num::BigInt;
+///
+/// let sent = AtomicU64::new(u64::MAX);
+/// assert_eq!(sent.load_bigint(Ordering::Relaxed), BigInt::from(u64::MAX));
+/// ```
+#[cfg(feature = "bigint")]
+pub trait AtomicToBigInt {
+    /// Loads the value and converts it.
+    fn load_bigint(&self, order: Ordering) -> BigInt;
+}
+
+/// Conversion of the value of an unsigned atomic integer into a [`num::BigUint`].
+///
+/// See [`AtomicToBigInt`] for details.
+#[cfg(feature = "biguint")]
+pub trait AtomicToBigUint {
+    /// Loads the value and converts it.
+    fn load_biguint(&self, order: Ordering) -> BigUint;
+}
+
+#[cfg(feature = "bigint")]
+macro_rules! impl_atomic_to_bigint {
+    ($($width:literal: $($atomic:ident),*;)*) => {$($(
+        #[cfg(target_has_atomic = $width)]
+        impl AtomicToBigInt for atomic::$atomic {
+            fn load_bigint(&self, order: Ordering) -> BigInt {
+                self.load(order).to_bigint()
+            }
+        }
+    )*)*};
+}
+
+#[cfg(feature = "bigint")]
+impl_atomic_to_bigint! {
+    "8": AtomicI8, AtomicU8;
+    "16": AtomicI16, AtomicU16;
+    "32": AtomicI32, AtomicU32;
+    "64": AtomicI64, AtomicU64;
+    "ptr": AtomicIsize, AtomicUsize;
+}
+
+#[cfg(feature = "biguint")]
+macro_rules! impl_atomic_to_biguint {
+    ($($width:literal: $atomic:ident;)*) => {$(
+        #[cfg(target_has_atomic = $width)]
+        impl AtomicToBigUint for atomic::$atomic {
+            fn load_biguint(&self, order: Ordering) -> BigUint {
+                self.load(order).to_biguint()
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "biguint")]
+impl_atomic_to_biguint! {
+    "8": AtomicU8;
+    "16": AtomicU16;
+    "32": AtomicU32;
+    "64": AtomicU64;
+    "ptr": AtomicUsize;
+}
+
+#[cfg(test)]
+mod tests {
+    use core::sync::atomic::Ordering;
+    #[cfg(feature = "std")]
+    use std::sync::{Mutex, RwLock};
+    #[cfg(any(feature = "bigint", feature = "std"))]
+    use std::thread;
+    #[cfg(all(feature = "bigint", feature = "std"))]
+    use std::{sync::Barrier, time::Duration};
+
+    #[cfg(feature = "bigint")]
+    use num::BigInt;
+    #[cfg(all(feature = "biguint", feature = "std"))]
+    use num::BigUint;
+
+    #[cfg(feature = "bigint")]
+    use super::AtomicToBigInt;
+    #[cfg(feature = "biguint")]
+    use super::AtomicToBigUint;
+    #[cfg(all(feature = "bigint", feature = "std"))]
+    use super::LockToBigIntExt;
+    #[cfg(all(feature = "biguint", feature = "std"))]
+    use super::LockToBigUintExt;
+    #[cfg(feature = "std")]
+    use super::PoisonedConversion;
+    #[cfg(feature = "bigint")]
+    use crate::InfallibleToBigInt;
+    #[cfg(feature = "biguint")]
+    use crate::InfallibleToBigUint;
+
+    /// Panics while holding the mutex, which poisons it
+    #[cfg(feature = "std")]
+    fn poison<T: Send>(mutex: &Mutex<T>) {
+        let result = thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let _guard = mutex.lock();
+                    panic!("poisoning the mutex");
+                })
+                .join()
+        });
+        assert!(result.is_err());
+        assert!(mutex.is_poisoned());
+    }
+
+    /// Test conversions of unpoisoned locks
+    #[cfg(all(feature = "bigint", feature = "std"))]
+    #[test]
+    fn test_lock_to_bigint() {
+        let mutex = Mutex::new(i128::MIN);
+        assert_eq!(mutex.lock_to_bigint(), Ok(BigInt::from(i128::MIN)));
+        assert_eq!(
+            mutex.lock_to_bigint_ignore_poison(),
+            BigInt::from(i128::MIN)
+        );
+        let lock = RwLock::new(-5_i8);
+        assert_eq!(lock.lock_to_bigint(), Ok(BigInt::from(-5)));
+        assert_eq!(lock.lock_to_bigint_ignore_poison(), BigInt::from(-5));
+        // the lock is released again after each conversion
+        assert!(mutex.try_lock().is_ok());
+        assert!(lock.try_write().is_ok());
+    }
+
+    /// Test unsigned conversions of unpoisoned locks
+    #[cfg(all(feature = "biguint", feature = "std"))]
+    #[test]
+    fn test_lock_to_biguint() {
+        let mutex = Mutex::new(u128::MAX);
+        assert_eq!(mutex.lock_to_biguint(), Ok(BigUint::from(u128::MAX)));
+        let lock = RwLock::new(7_u16);
+        assert_eq!(lock.lock_to_biguint_ignore_poison(), BigUint::from(7_u8));
+    }
+
+    /// Test both methods on a poisoned mutex
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_poisoned() {
+        let mutex = Mutex::new(42_u64);
+        poison(&mutex);
+        #[cfg(feature = "bigint")]
+        {
+            assert_eq!(mutex.lock_to_bigint(), Err(PoisonedConversion));
+            assert_eq!(mutex.lock_to_bigint_ignore_poison(), BigInt::from(42));
+        }
+        #[cfg(feature = "biguint")]
+        {
+            assert_eq!(mutex.lock_to_biguint(), Err(PoisonedConversion));
+            assert_eq!(mutex.lock_to_biguint_ignore_poison(), BigUint::from(42_u8));
+        }
+        assert_eq!(
+            PoisonedConversion.to_string(),
+            "the lock holding the value is poisoned"
+        );
+    }
+
+    /// Test that a conversion waits for a writer holding the lock and sees its update
+    #[cfg(all(feature = "bigint", feature = "std"))]
+    #[test]
+    fn test_rw_lock_concurrent_writer() {
+        let lock = RwLock::new(1_u64);
+        let locked = Barrier::new(2);
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                let mut guard = lock.write().unwrap();
+                locked.wait();
+                thread::sleep(Duration::from_millis(50));
+                *guard = u64::MAX;
+            });
+            locked.wait();
+            assert_eq!(lock.lock_to_bigint(), Ok(BigInt::from(u64::MAX)));
+        });
+        // concurrent readers do not block each other
+        let _reader = lock.read().unwrap();
+        assert_eq!(lock.lock_to_bigint(), Ok(BigInt::from(u64::MAX)));
+    }
+
+    /// Test loading the MIN and MAX values of every atomic with SeqCst and Relaxed
+    #[cfg(all(
+        feature = "bigint",
+        target_has_atomic = "64",
+        target_has_atomic = "ptr"
+    ))]
+    #[test]
+    fn test_atomic_to_bigint() {
+        use core::sync::atomic::{
+            AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize, AtomicU16, AtomicU32,
+            AtomicU64, AtomicU8, AtomicUsize,
+        };
+
+        fn check(atomic: &impl AtomicToBigInt, expected: impl InfallibleToBigInt) {
+            for order in [Ordering::SeqCst, Ordering::Relaxed] {
+                assert_eq!(atomic.load_bigint(order), expected.to_bigint());
+            }
+        }
+        check(&AtomicI8::new(i8::MIN), i8::MIN);
+        check(&AtomicI16::new(i16::MIN), i16::MIN);
+        check(&AtomicI32::new(i32::MIN), i32::MIN);
+        check(&AtomicI64::new(i64::MIN), i64::MIN);
+        check(&AtomicIsize::new(isize::MIN), isize::MIN);
+        check(&AtomicI64::new(i64::MAX), i64::MAX);
+        check(&AtomicU8::new(u8::MAX), u8::MAX);
+        check(&AtomicU16::new(u16::MAX), u16::MAX);
+        check(&AtomicU32::new(u32::MAX), u32::MAX);
+        check(&AtomicU64::new(u64::MAX), u64::MAX);
+        check(&AtomicUsize::new(usize::MAX), usize::MAX);
+    }
+
+    /// Test loading the MAX values of every unsigned atomic with SeqCst and Relaxed
+    #[cfg(all(
+        feature = "biguint",
+        target_has_atomic = "64",
+        target_has_atomic = "ptr"
+    ))]
+    #[test]
+    fn test_atomic_to_biguint() {
+        use core::sync::atomic::{AtomicU16, AtomicU32, AtomicU64, AtomicU8, AtomicUsize};
+
+        fn check(atomic: &impl AtomicToBigUint, expected: impl InfallibleToBigUint) {
+            for order in [Ordering::SeqCst, Ordering::Relaxed] {
+                assert_eq!(atomic.load_biguint(order), expected.to_biguint());
+            }
+        }
+        check(&AtomicU8::new(u8::MAX), u8::MAX);
+        check(&AtomicU16::new(u16::MAX), u16::MAX);
+        check(&AtomicU32::new(u32::MAX), u32::MAX);
+        check(&AtomicU64::new(u64::MAX), u64::MAX);
+        check(&AtomicUsize::new(usize::MAX), usize::MAX);
+        check(&AtomicU64::new(0), 0_u64);
+    }
+
+    /// Test that a load sees the stores of another thread
+    #[cfg(all(feature = "bigint", target_has_atomic = "64"))]
+    #[test]
+    fn test_atomic_concurrent_stores() {
+        use core::sync::atomic::AtomicU64;
+
+        let counter = AtomicU64::new(0);
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..1000 {
+                        counter.fetch_add(1, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+        assert_eq!(counter.load_bigint(Ordering::Acquire), BigInt::from(4000));
+    }
+}
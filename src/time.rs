@@ -0,0 +1,57 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use num::BigInt;
+
+use crate::InfallibleToBigInt;
+
+/// Converts a [`SystemTime`] to the signed number of nanoseconds since the Unix epoch.
+///
+/// Times before [`UNIX_EPOCH`] are negative. The result is exact on every platform, which an `i128` would only be as
+/// long as the platform keeps its times within about `10^21` years of the epoch.
+///
+/// # Example
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use infallible_tobigint::time::system_time_to_bigint_nanos;
+/// use num::BigInt;
+///
+/// assert_eq!(system_time_to_bigint_nanos(UNIX_EPOCH + Duration::from_millis(1)), BigInt::from(1_000_000));
+/// assert_eq!(system_time_to_bigint_nanos(UNIX_EPOCH - Duration::from_secs(1)), BigInt::from(-1_000_000_000));
+/// ```
+pub fn system_time_to_bigint_nanos(t: SystemTime) -> BigInt {
+    match t.duration_since(UNIX_EPOCH) {
+        Ok(since) => since.to_bigint(),
+        Err(error) => -error.duration().to_bigint(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use num::{BigInt, Zero};
+
+    use super::system_time_to_bigint_nanos;
+
+    /// Test times at, after and before the epoch
+    #[test]
+    fn test_system_time() {
+        assert!(system_time_to_bigint_nanos(UNIX_EPOCH).is_zero());
+        let after = UNIX_EPOCH + Duration::new(3, 5);
+        assert_eq!(
+            system_time_to_bigint_nanos(after),
+            BigInt::from(3_000_000_005_u32)
+        );
+        let before = UNIX_EPOCH - Duration::from_secs(1);
+        assert_eq!(
+            system_time_to_bigint_nanos(before),
+            BigInt::from(-1_000_000_000)
+        );
+        let before = UNIX_EPOCH - Duration::new(1, 1);
+        assert_eq!(
+            system_time_to_bigint_nanos(before),
+            BigInt::from(-1_000_000_001)
+        );
+    }
+}
@@ -0,0 +1,36 @@
+#!/usr/bin/env bash
+# Checks the crate, including its tests, with no features, every feature on its own, every pair of features, the
+# default features and all features, and runs the tests and doctests with no features and every feature on its own.
+# Without `std` the library is `no_std`, and it is also built for an embedded target if that is installed.
+set -euo pipefail
+
+cd "$(dirname "$0")/.."
+# unused imports only show up in some combinations, so they have to fail the check
+export RUSTFLAGS="${RUSTFLAGS:--D warnings}"
+
+features=(std bigint biguint rational float iter encode ops macros derive)
+combinations=("" "${features[@]}")
+for ((i = 0; i < ${#features[@]}; i++)); do
+    for ((j = i + 1; j < ${#features[@]}; j++)); do
+        combinations+=("${features[i]},${features[j]}")
+    done
+done
+
+for combination in "${combinations[@]}"; do
+    echo "checking features: ${combination:-<none>}"
+    cargo check --quiet --all-targets --no-default-features --features "$combination"
+done
+for feature in "" "${features[@]}"; do
+    echo "testing features: ${feature:-<none>}"
+    cargo test --quiet --no-default-features --features "$feature"
+done
+echo "checking default features"
+cargo check --quiet --all-targets
+echo "checking all features"
+cargo check --quiet --all-targets --all-features
+echo "checking without std"
+cargo build --quiet --no-default-features --features "${features[*]:1}"
+if rustup target list --installed 2>/dev/null | grep -qx thumbv7em-none-eabihf; then
+    echo "checking without std for thumbv7em-none-eabihf"
+    cargo build --quiet --no-default-features --features "${features[*]:1}" --target thumbv7em-none-eabihf
+fi
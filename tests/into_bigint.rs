@@ -0,0 +1,132 @@
+//! Checks, by counting allocations, that consuming or borrowing a bignum reuses its digits instead of copying them, and
+//! that assigning and comparing primitives does not allocate.
+#![cfg(feature = "bigint")]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+#[cfg(feature = "biguint")]
+use infallible_tobigint::InfallibleIntoBigUint;
+use infallible_tobigint::{cmp::CmpBigInt, InfallibleIntoBigInt, ToBigIntAssign, ToBigIntCow};
+use num::{BigInt, BigUint};
+
+/// Allocator that counts the allocations of the current thread
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Test that converting a BigUint by value does not allocate
+#[test]
+fn test_biguint_moved() {
+    let value = BigUint::from(u128::MAX) << 1000_u32;
+    let expected = BigInt::from(value.clone());
+    let before = allocations();
+    let converted = value.into_bigint();
+    assert_eq!(allocations(), before);
+    assert_eq!(converted, expected);
+}
+
+/// Test that converting a primitive by value allocates only the result
+#[test]
+fn test_primitive_converted() {
+    let before = allocations();
+    let converted = u64::MAX.into_bigint();
+    assert_eq!(allocations(), before + 1);
+    assert_eq!(converted, BigInt::from(u64::MAX));
+}
+
+/// Test that converting a BigInt by value does not allocate
+#[test]
+fn test_bigint_moved() {
+    let value = -(BigInt::from(u128::MAX) << 1000_u32);
+    let expected = value.clone();
+    let before = allocations();
+    let converted = value.into_bigint();
+    assert_eq!(allocations(), before);
+    assert_eq!(converted, expected);
+}
+
+/// Test that converting a BigUint by value to a BigUint does not allocate
+#[cfg(feature = "biguint")]
+#[test]
+fn test_biguint_moved_unsigned() {
+    let value = BigUint::from(u128::MAX) << 1000_u32;
+    let expected = value.clone();
+    let before = allocations();
+    let converted = value.into_biguint();
+    assert_eq!(allocations(), before);
+    assert_eq!(converted, expected);
+}
+
+/// Test that borrowing a BigInt as a Cow does not allocate
+#[test]
+fn test_bigint_cow_borrowed() {
+    let value = BigInt::from(u128::MAX) << 1000_u32;
+    let before = allocations();
+    let borrowed = value.to_bigint_cow();
+    assert_eq!(allocations(), before);
+    assert_eq!(*borrowed, value);
+}
+
+/// Test that assigning a million values into one BigInt only allocates its digit buffer once
+#[test]
+fn test_assign_reuses_buffer() {
+    let mut target = BigInt::from(0);
+    u128::MAX.to_bigint_into(&mut target);
+    let before = allocations();
+    let mut checksum = 0_u64;
+    for i in 0..1_000_000_u64 {
+        let value = i.wrapping_mul(0x9e37_79b9_7f4a_7c15) as i64;
+        value.to_bigint_into(&mut target);
+        checksum = checksum.wrapping_add(target.iter_u64_digits().next().unwrap_or(0));
+    }
+    assert_eq!(allocations(), before);
+    let expected = (0..1_000_000_u64).fold(0_u64, |sum, i| {
+        let value = i.wrapping_mul(0x9e37_79b9_7f4a_7c15) as i64;
+        sum.wrapping_add(value.unsigned_abs())
+    });
+    assert_eq!(checksum, expected);
+}
+
+/// Test that comparing primitives against BigInts does not allocate
+#[test]
+fn test_compare_without_allocating() {
+    let others = [
+        BigInt::from(0),
+        BigInt::from(i64::MIN),
+        BigInt::from(u128::MAX) << 64_u32,
+        -(BigInt::from(u128::MAX) << 64_u32),
+    ];
+    let before = allocations();
+    let mut equal = 0;
+    for value in [i64::MIN, -1, 0, i64::MAX] {
+        for other in &others {
+            equal += usize::from(value.eq_bigint(other));
+            equal += usize::from(value.unsigned_abs().eq_bigint(other));
+        }
+    }
+    assert_eq!(allocations(), before);
+    assert_eq!(equal, 3);
+}